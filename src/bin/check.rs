@@ -0,0 +1,39 @@
+use std::process::exit;
+
+use clap::{App, load_yaml};
+
+use dogs_color::color::validate_solution;
+use dogs_color::util::{load_instance, load_solution};
+
+/** validates a solution file against a coloring instance, auto-detecting both the instance
+and solution formats (DIMACS or CGSHOP). Unlike [`dogs_color::color::checker`], reports every
+duplicated/uncolored vertex and every conflicting edge instead of stopping at the first. */
+pub fn main() {
+    // parse arguments
+    let yaml = load_yaml!("check.yml");
+    let main_args = App::from_yaml(yaml).get_matches();
+    let inst_filename = main_args.value_of("instance").unwrap();
+    let sol_filename = main_args.value_of("solution").unwrap();
+    // read files
+    let (instance, instance_type) = load_instance(inst_filename);
+    let solution = load_solution(sol_filename);
+    println!("instance type: {}", instance_type);
+    // validate
+    match validate_solution(instance.as_ref(), &solution) {
+        Ok(nb_colors) => {
+            println!("OK: {} colors", nb_colors);
+        },
+        Err(e) => {
+            for v in &e.duplicated {
+                println!("ERROR: vertex {} colored more than once", v);
+            }
+            for v in &e.uncolored {
+                println!("ERROR: vertex {} not colored", v);
+            }
+            for (u, v) in &e.conflicting_edges {
+                println!("ERROR: vertices {} and {} are adjacent but share a color", u, v);
+            }
+            exit(1);
+        }
+    };
+}
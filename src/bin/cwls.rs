@@ -7,16 +7,43 @@ use dogs::search_algorithm::TimeStoppingCriterion;
 use dogs_color::color::{CheckerResult, checker};
 use dogs_color::cgshop::CGSHOPInstance;
 use dogs_color::solvers::cgshop::cgshop_aog::cgshop_aog;
-use dogs_color::solvers::coloring::conflict_weighting::{coloring_conflict_weighting};
+use dogs_color::solvers::coloring::conflict_weighting::{coloring_conflict_weighting_with_checkpointing, coloring_conflict_weighting_with_log_format, coloring_conflict_weighting_with_lower_bound};
 use dogs_color::solvers::coloring::greedy_dsatur::greedy_dsatur;
-use dogs_color::util::read_params;
+use dogs_color::solvers::coloring::portfolio::{run_portfolio, PortfolioSolver};
+use dogs_color::util::{load_solution, read_params, LogFormat, RunClock, RunSummary, TimeBasis};
 
 
-/** solves a coloring instance using a DSATUR greedy */
+/** reads any coloring instance, builds a greedy initial solution (DSATUR, or the better of
+DSATUR and AOG on CGSHOP instances; or loads one from `--warmstart` instead, skipping the
+greedy), then improves it with the conflict-weighting local
+search for the given time limit, writing the solution and perf files passed on the command
+line. */
 pub fn main() {
     // parse arguments
     let yaml = load_yaml!("cwls.yml");
     let main_args = App::from_yaml(yaml).get_matches();
+    let csv_file = main_args.value_of("csv").map(|e| e.to_string());
+    let resume_file = main_args.value_of("resume").map(|e| e.to_string());
+    let warmstart_file = main_args.value_of("warmstart").map(|e| e.to_string());
+    let checkpoint_file = main_args.value_of("checkpoint").map(|e| e.to_string())
+        .or_else(|| resume_file.clone());
+    let checkpoint_interval:f32 = main_args.value_of("checkpoint-interval")
+        .map(|e| e.parse().expect("unable to parse --checkpoint-interval"))
+        .unwrap_or(300.);
+    let time_basis = match main_args.value_of("time-basis") {
+        None | Some("wall") => TimeBasis::Wall,
+        Some("cpu") => TimeBasis::Cpu,
+        Some(other) => panic!("unrecognized --time-basis {} (valid: 'wall', 'cpu')", other),
+    };
+    let clique_lower_bound_secs:Option<f32> = main_args.value_of("clique-lower-bound")
+        .map(|e| e.parse().expect("unable to parse --clique-lower-bound"));
+    let nb_workers:Option<usize> = main_args.value_of("workers")
+        .map(|e| e.parse().expect("unable to parse --workers"));
+    let log_format = match main_args.value_of("log-format") {
+        None | Some("text") => LogFormat::Text,
+        Some("json") => LogFormat::Json,
+        Some(other) => panic!("unrecognized --log-format {} (valid: 'text', 'json')", other),
+    };
     let (
         inst_filename,
         instance,
@@ -26,48 +53,102 @@ pub fn main() {
         perf_file
     ) = read_params(main_args);
     let time_init = Instant::now();
+    let run_clock = RunClock::start();
     // solve it
-    let initial_solution = match instance.coloring() {
-        None => {
-            let sol_greedy = match instance_type.as_str() {
-                "dimacs" => { greedy_dsatur(instance.clone(), false) }
-                "cgshop" => {
-                    let sol_dsatur = greedy_dsatur(instance.clone(), false);
-                    let instance = Rc::new(CGSHOPInstance::from_file(&inst_filename));
-                    let sol_orientation_greedy = cgshop_aog(instance, true);
-                    if sol_dsatur.len() < sol_orientation_greedy.len() {
-                        sol_dsatur
-                    } else {
-                        sol_orientation_greedy
-                    }
-                },
-                _ => { panic!("unrecognized instance type {} (valid: 'dimacs', 'cgshop')", instance_type.as_str())}
-            };
-            println!("greedy found {} colors in {:.3} seconds", sol_greedy.len(), time_init.elapsed().as_secs_f32());
-            sol_greedy
+    let initial_solution = match &warmstart_file {
+        Some(path) => load_solution(path),
+        None => match instance.coloring() {
+            None => {
+                let sol_greedy = match instance_type.as_str() {
+                    "dimacs" | "edgelist" => { greedy_dsatur(instance.clone(), false) }
+                    "cgshop" => {
+                        let sol_dsatur = greedy_dsatur(instance.clone(), false);
+                        let instance = Rc::new(CGSHOPInstance::from_file(&inst_filename));
+                        let sol_orientation_greedy = cgshop_aog(instance, true);
+                        if sol_dsatur.len() < sol_orientation_greedy.len() {
+                            sol_dsatur
+                        } else {
+                            sol_orientation_greedy
+                        }
+                    },
+                    _ => { panic!("unrecognized instance type {} (valid: 'dimacs', 'cgshop', 'edgelist')", instance_type.as_str())}
+                };
+                sol_greedy
+            }
+            Some(sol) => { sol }
         }
-        Some(sol) => { sol }
     };
     assert_eq!(
         checker(instance.clone(), &initial_solution),
         CheckerResult::Ok(initial_solution.len())
     );
-    println!("initial coloring: {}", initial_solution.len());
-    match instance.clique() {
-        None => {},
-        Some(c) => {
-            println!("clique: {}", c.len());
-            if c.len() == initial_solution.len() {
-                println!("optimal solution is already found!");
-                return;
-            }
+    let best_clique = instance.clique().map(|c| c.len());
+    let summary = |best_colors:usize, sol_file:Option<String>, perf_file:Option<String>| RunSummary {
+        instance: inst_filename.clone(),
+        preset: None,
+        best_colors: Some(best_colors),
+        best_clique,
+        iterations: None,
+        restarts: None,
+        time_to_best: time_init.elapsed().as_secs_f32(),
+        total_time: time_init.elapsed().as_secs_f32(),
+        total_cpu_time: Some(run_clock.cpu_secs()),
+        sol_file,
+        perf_file,
+        tag: None,
+    };
+    if let Some(q) = best_clique {
+        if q == initial_solution.len() {
+            let s = summary(initial_solution.len(), sol_file, perf_file);
+            s.display();
+            if let Some(csv) = &csv_file { s.append_csv(csv); }
+            return;
         }
     }
-    coloring_conflict_weighting(
-        instance,
-        &initial_solution,
-        perf_file,
-        sol_file,
-        TimeStoppingCriterion::new(t)
-    );
+    let final_solution = match nb_workers {
+        Some(workers) if workers > 1 => run_portfolio(
+            &inst_filename,
+            &initial_solution,
+            PortfolioSolver::ConflictWeighting,
+            workers,
+            t,
+            1_000,
+            perf_file.clone(),
+            sol_file.clone(),
+        ),
+        _ => match (clique_lower_bound_secs, &checkpoint_file) {
+            (Some(clique_time_secs), _) => coloring_conflict_weighting_with_lower_bound(
+                instance,
+                &initial_solution,
+                t,
+                time_basis,
+                clique_time_secs,
+                perf_file.clone(),
+                sol_file.clone(),
+            ),
+            (None, Some(checkpoint)) => coloring_conflict_weighting_with_checkpointing(
+                instance,
+                &initial_solution,
+                Default::default(),
+                t,
+                time_basis,
+                checkpoint_interval,
+                checkpoint,
+                resume_file.is_some(),
+                perf_file.clone(),
+                sol_file.clone(),
+            ),
+            (None, None) => coloring_conflict_weighting_with_log_format(
+                instance,
+                &initial_solution,
+                perf_file.clone(),
+                sol_file.clone(),
+                TimeStoppingCriterion::new(t),
+                log_format,
+            ),
+        },
+    };
+    let s = summary(final_solution.len(), sol_file, perf_file);
+    s.display();
+    if let Some(csv) = &csv_file { s.append_csv(csv); }
 }
\ No newline at end of file
@@ -6,6 +6,7 @@ use serde_json::json;
 
 use dogs_color::cgshop::CGSHOPInstance;
 use dogs_color::solvers::cgshop::cgshop_aog::cgshop_aog;
+use dogs_color::solvers::cgshop::orientation_buckets::orientation_buckets;
 use dogs_color::util::{read_params, export_results};
 
 
@@ -14,6 +15,9 @@ pub fn main() {
     // parse arguments
     let yaml = load_yaml!("greedy_cgshop_aog.yml");
     let main_args = App::from_yaml(yaml).get_matches();
+    let init = main_args.value_of("init").unwrap_or("aog");
+    let buckets:usize = main_args.value_of("buckets").unwrap_or("8").parse()
+        .expect("unable to parse --buckets");
     let (
         inst_filename,
         _, _, _,
@@ -23,7 +27,10 @@ pub fn main() {
     let instance = Rc::new(CGSHOPInstance::from_file(&inst_filename));
     // solve it
     let t_start = Instant::now();
-    let solution = cgshop_aog(instance.clone(), true);
+    let solution = match init {
+        "orientation-buckets" => orientation_buckets(instance.clone(), buckets, true),
+        _ => cgshop_aog(instance.clone(), true),
+    };
     let duration = t_start.elapsed().as_secs_f32();
     let nb_colors = solution.len();
     println!("AOG took {:.3} seconds. Nb colors: {}", duration, nb_colors);
@@ -7,9 +7,10 @@ use dogs::search_algorithm::TimeStoppingCriterion;
 use dogs_color::cgshop::CGSHOPInstance;
 use dogs_color::color::{CheckerResult, checker};
 use dogs_color::solvers::cgshop::cgshop_aog::cgshop_aog;
-use dogs_color::solvers::coloring::partial_weighting::{coloring_partial_weighting};
+use dogs_color::solvers::coloring::partial_weighting::{coloring_partial_weighting_with_checkpointing, coloring_partial_weighting_with_log_format, coloring_partial_weighting_with_lower_bound};
 use dogs_color::solvers::coloring::greedy_dsatur::greedy_dsatur;
-use dogs_color::util::read_params;
+use dogs_color::solvers::coloring::portfolio::{run_portfolio, PortfolioSolver};
+use dogs_color::util::{load_solution, read_params, LogFormat, TimeBasis};
 
 
 /** solves a coloring instance using a DSATUR greedy */
@@ -17,6 +18,27 @@ pub fn main() {
     // parse arguments
     let yaml = load_yaml!("pwls.yml");
     let main_args = App::from_yaml(yaml).get_matches();
+    let resume_file = main_args.value_of("resume").map(|e| e.to_string());
+    let warmstart_file = main_args.value_of("warmstart").map(|e| e.to_string());
+    let checkpoint_file = main_args.value_of("checkpoint").map(|e| e.to_string())
+        .or_else(|| resume_file.clone());
+    let checkpoint_interval:f32 = main_args.value_of("checkpoint-interval")
+        .map(|e| e.parse().expect("unable to parse --checkpoint-interval"))
+        .unwrap_or(300.);
+    let time_basis = match main_args.value_of("time-basis") {
+        None | Some("wall") => TimeBasis::Wall,
+        Some("cpu") => TimeBasis::Cpu,
+        Some(other) => panic!("unrecognized --time-basis {} (valid: 'wall', 'cpu')", other),
+    };
+    let clique_lower_bound_secs:Option<f32> = main_args.value_of("clique-lower-bound")
+        .map(|e| e.parse().expect("unable to parse --clique-lower-bound"));
+    let nb_workers:Option<usize> = main_args.value_of("workers")
+        .map(|e| e.parse().expect("unable to parse --workers"));
+    let log_format = match main_args.value_of("log-format") {
+        None | Some("text") => LogFormat::Text,
+        Some("json") => LogFormat::Json,
+        Some(other) => panic!("unrecognized --log-format {} (valid: 'text', 'json')", other),
+    };
     let (
         inst_filename,
         instance,
@@ -27,26 +49,29 @@ pub fn main() {
     ) = read_params(main_args);
     let time_init = Instant::now();
     // solve it
-    let initial_solution = match instance.coloring() {
-        None => {
-            let sol_greedy = match instance_type.as_str() {
-                "dimacs" => { greedy_dsatur(instance.clone(), false) }
-                "cgshop" => {
-                    let sol_dsatur = greedy_dsatur(instance.clone(), false);
-                    let instance = Rc::new(CGSHOPInstance::from_file(&inst_filename));
-                    let sol_orientation_greedy = cgshop_aog(instance, true);
-                    if sol_dsatur.len() < sol_orientation_greedy.len() {
-                        sol_dsatur
-                    } else {
-                        sol_orientation_greedy
-                    }
-                },
-                _ => { panic!("unrecognized instance type {} (valid: 'dimacs', 'cgshop')", instance_type.as_str())}
-            };
-            println!("greedy found {} colors in {:.3} seconds", sol_greedy.len(), time_init.elapsed().as_secs_f32());
-            sol_greedy
+    let initial_solution = match &warmstart_file {
+        Some(path) => load_solution(path),
+        None => match instance.coloring() {
+            None => {
+                let sol_greedy = match instance_type.as_str() {
+                    "dimacs" | "edgelist" => { greedy_dsatur(instance.clone(), false) }
+                    "cgshop" => {
+                        let sol_dsatur = greedy_dsatur(instance.clone(), false);
+                        let instance = Rc::new(CGSHOPInstance::from_file(&inst_filename));
+                        let sol_orientation_greedy = cgshop_aog(instance, true);
+                        if sol_dsatur.len() < sol_orientation_greedy.len() {
+                            sol_dsatur
+                        } else {
+                            sol_orientation_greedy
+                        }
+                    },
+                    _ => { panic!("unrecognized instance type {} (valid: 'dimacs', 'cgshop', 'edgelist')", instance_type.as_str())}
+                };
+                println!("greedy found {} colors in {:.3} seconds", sol_greedy.len(), time_init.elapsed().as_secs_f32());
+                sol_greedy
+            }
+            Some(sol) => { sol }
         }
-        Some(sol) => { sol }
     };
     assert_eq!(
         checker(instance.clone(), &initial_solution),
@@ -63,11 +88,46 @@ pub fn main() {
             }
         }
     }
-    coloring_partial_weighting(
-        instance,
-        &initial_solution,
-        perf_file,
-        sol_file,
-        TimeStoppingCriterion::new(t)
-    );
+    match nb_workers {
+        Some(workers) if workers > 1 => { run_portfolio(
+            &inst_filename,
+            &initial_solution,
+            PortfolioSolver::PartialWeighting,
+            workers,
+            t,
+            1_000,
+            perf_file,
+            sol_file,
+        ); }
+        _ => match (clique_lower_bound_secs, &checkpoint_file) {
+            (Some(clique_time_secs), _) => { coloring_partial_weighting_with_lower_bound(
+                instance,
+                &initial_solution,
+                t,
+                time_basis,
+                clique_time_secs,
+                perf_file,
+                sol_file,
+            ); }
+            (None, Some(checkpoint)) => { coloring_partial_weighting_with_checkpointing(
+                instance,
+                &initial_solution,
+                t,
+                time_basis,
+                checkpoint_interval,
+                checkpoint,
+                resume_file.is_some(),
+                perf_file,
+                sol_file,
+            ); }
+            (None, None) => { coloring_partial_weighting_with_log_format(
+                instance,
+                &initial_solution,
+                perf_file,
+                sol_file,
+                TimeStoppingCriterion::new(t),
+                log_format,
+            ); }
+        },
+    }
 }
\ No newline at end of file
@@ -0,0 +1,54 @@
+use std::time::Instant;
+
+use clap::{App, load_yaml};
+use serde_json::json;
+
+use dogs_color::color::{CheckerResult, checker};
+use dogs_color::pipeline::solve;
+use dogs_color::util::{read_params, export_results};
+
+
+/** hybrid clique-then-color pipeline: preprocessing, a greedy clique improved into a
+chromatic lower bound, the better of DSATUR and RLF construction, conflict-weighting local
+search then partial-weighting local search, mirroring the phase order documented in the
+conflict-weighting and partial-weighting solver notes. */
+pub fn main() {
+    // parse arguments
+    let yaml = load_yaml!("solve.yml");
+    let main_args = App::from_yaml(yaml).get_matches();
+    let clique_fraction:f32 = main_args.value_of("clique-fraction")
+        .map(|e| e.parse().expect("unable to parse --clique-fraction"))
+        .unwrap_or(0.1);
+    let conflict_weighting_fraction:f32 = main_args.value_of("conflict-weighting-fraction")
+        .map(|e| e.parse().expect("unable to parse --conflict-weighting-fraction"))
+        .unwrap_or(0.4);
+    let partial_weighting_fraction:f32 = main_args.value_of("partial-weighting-fraction")
+        .map(|e| e.parse().expect("unable to parse --partial-weighting-fraction"))
+        .unwrap_or(0.4);
+    let (
+        _inst_filename,
+        instance,
+        t,
+        _, sol_file, perf_file
+    ) = read_params(main_args);
+
+    // solve it
+    let t_start = Instant::now();
+    let (solution, report) = solve(
+        instance.clone(), t, clique_fraction, conflict_weighting_fraction, partial_weighting_fraction
+    );
+    let duration = t_start.elapsed().as_secs_f32();
+    println!("solve took {:.3} seconds. Nb colors: {}", duration, solution.len());
+    for step in &report {
+        println!("\t{:?}: {} ({:.3}s)", step.phase, step.value, step.elapsed);
+    }
+    assert_eq!(checker(instance.clone(), &solution), CheckerResult::Ok(solution.len()));
+    let stats = json!({
+        "primal_list": vec![solution.len()],
+        "time_searched": duration,
+        "report": report,
+    });
+
+    // export results
+    export_results(instance, &solution, &stats, perf_file, sol_file, true);
+}
@@ -0,0 +1,107 @@
+//! process-wide registry of the best lower/upper bounds seen by any solver invocation in
+//! the current run, keyed by a cheap instance hash. Lets sequential phases (and tests)
+//! automatically inherit bounds instead of threading numbers through every call site by
+//! hand.
+
+use std::collections::HashMap;
+use std::hash::{Hash, Hasher};
+use std::sync::{Mutex, OnceLock};
+
+use crate::color::ColoringInstance;
+
+/// best bounds known for a given instance
+#[derive(Clone,Copy,Debug,Default,PartialEq,Eq)]
+pub struct Bounds {
+    /// best (largest) lower bound known, typically the size of the best clique found so far
+    pub lower_bound: Option<usize>,
+    /// best (smallest) upper bound known, typically the number of colors of the best coloring found so far
+    pub upper_bound: Option<usize>,
+}
+
+impl Bounds {
+    /// true iff the lower and upper bound match (the instance is proven optimal)
+    pub fn is_proven_optimal(&self) -> bool {
+        matches!((self.lower_bound, self.upper_bound), (Some(l), Some(u)) if l == u)
+    }
+}
+
+fn registry() -> &'static Mutex<HashMap<u64, Bounds>> {
+    static REGISTRY: OnceLock<Mutex<HashMap<u64, Bounds>>> = OnceLock::new();
+    REGISTRY.get_or_init(|| Mutex::new(HashMap::new()))
+}
+
+/** cheap hash key identifying an instance, based on its full adjacency structure (each
+vertex's sorted neighbor list, not just its degree): two instances with the same vertex count
+and degree sequence but different edges still hash to different keys. Collisions are still
+possible in theory but extremely unlikely in practice for the instance sizes this crate deals
+with. */
+pub fn instance_key(inst:&dyn ColoringInstance) -> u64 {
+    let mut hasher = std::collections::hash_map::DefaultHasher::new();
+    let n = inst.nb_vertices();
+    n.hash(&mut hasher);
+    for v in 0..n {
+        let mut neighbors = inst.neighbors(v);
+        neighbors.sort_unstable();
+        neighbors.hash(&mut hasher);
+    }
+    hasher.finish()
+}
+
+/// records a newly found lower bound, keeping the best (largest) one seen so far; returns the
+/// resulting bounds for `key`
+pub fn record_lower_bound(key:u64, lb:usize) -> Bounds {
+    let mut reg = registry().lock().unwrap();
+    let entry = reg.entry(key).or_default();
+    entry.lower_bound = Some(entry.lower_bound.map_or(lb, |cur| cur.max(lb)));
+    *entry
+}
+
+/// records a newly found upper bound, keeping the best (smallest) one seen so far; returns the
+/// resulting bounds for `key`
+pub fn record_upper_bound(key:u64, ub:usize) -> Bounds {
+    let mut reg = registry().lock().unwrap();
+    let entry = reg.entry(key).or_default();
+    entry.upper_bound = Some(entry.upper_bound.map_or(ub, |cur| cur.min(ub)));
+    *entry
+}
+
+/// returns the bounds currently known for `key` (defaulting to `Bounds::default()` if unseen)
+pub fn get(key:u64) -> Bounds {
+    registry().lock().unwrap().get(&key).copied().unwrap_or_default()
+}
+
+/// clears the whole registry (mainly useful for tests that genuinely need a pristine
+/// registry; prefer [`remove`] when only one key needs resetting, since `clear()` wipes every
+/// other key too and races with any other test concurrently recording bounds of its own)
+pub fn clear() {
+    registry().lock().unwrap().clear();
+}
+
+/// removes only `key`'s entry, so a test can reset the one instance it cares about without
+/// disturbing bounds concurrently recorded under other keys
+pub fn remove(key:u64) {
+    registry().lock().unwrap().remove(&key);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dimacs::DimacsInstance;
+
+    #[test]
+    fn test_record_and_get() {
+        let inst = DimacsInstance::from_file("insts/grid-instances/grid2x2");
+        let key = instance_key(&inst);
+        remove(key);
+        assert_eq!(get(key), Bounds::default());
+        record_lower_bound(key, 2);
+        record_upper_bound(key, 4);
+        record_lower_bound(key, 1); // should not decrease the lower bound
+        let bounds = get(key);
+        assert_eq!(bounds.lower_bound, Some(2));
+        assert_eq!(bounds.upper_bound, Some(4));
+        assert!(!bounds.is_proven_optimal());
+        record_upper_bound(key, 2);
+        assert!(get(key).is_proven_optimal());
+    }
+}
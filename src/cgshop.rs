@@ -6,11 +6,13 @@ Implements:
 */
 use bit_set::BitSet;
 use serde::{Deserialize, Serialize};
+use std::cell::OnceCell;
 use std::cmp::{max, min};
 use std::fs;
 use std::fs::File;
 use std::io::{BufReader, Write};
 use std::rc::Rc;
+use std::thread;
 
 use crate::color::{ColoringInstance, VertexId};
 
@@ -23,6 +25,31 @@ struct PreprocessedData {
     dominations: Vec<(VertexId, VertexId)>,
 }
 
+/** deserializes a JSON array of (possibly fractional) numbers directly into a `Vec<i64>`,
+truncating each element as it streams out of the array instead of first collecting a `Vec<f64>`
+and casting it afterwards: on the biggest CGSHOP instances the `x`/`y` arrays are tens of
+millions of points long, so avoiding that intermediate `Vec<f64>` roughly halves their peak
+memory use while loading. */
+fn deserialize_coords_as_i64<'de, D>(deserializer: D) -> Result<Vec<i64>, D::Error>
+where D: serde::Deserializer<'de> {
+    struct CoordVisitor;
+    impl<'de> serde::de::Visitor<'de> for CoordVisitor {
+        type Value = Vec<i64>;
+        fn expecting(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+            write!(f, "an array of numbers")
+        }
+        fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+        where A: serde::de::SeqAccess<'de> {
+            let mut res = Vec::with_capacity(seq.size_hint().unwrap_or(0));
+            while let Some(v) = seq.next_element::<f64>()? {
+                res.push(v as i64);
+            }
+            Ok(res)
+        }
+    }
+    deserializer.deserialize_seq(CoordVisitor)
+}
+
 /** data structure to represent a CGSHOP instance */
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct CGSHOPInstance {
@@ -30,10 +57,13 @@ pub struct CGSHOPInstance {
     n: usize,
     /// number of edges
     m: usize,
-    /// x coordinates for points
-    x: Vec<f64>,
-    /// y coordinates for points
-    y: Vec<f64>,
+    /// x coordinates for points, truncated to integers as they stream in (see
+    /// [`deserialize_coords_as_i64`]) rather than collected as `f64` and cast afterwards
+    #[serde(deserialize_with = "deserialize_coords_as_i64")]
+    x: Vec<i64>,
+    /// y coordinates for points, see [`deserialize_coords_as_i64`]
+    #[serde(deserialize_with = "deserialize_coords_as_i64")]
+    y: Vec<i64>,
     /// edge_i[i]: first endpoint of the ith edge
     edge_i: Vec<usize>,
     /// edge_j[i]: second endpoint of the ith edge
@@ -42,15 +72,24 @@ pub struct CGSHOPInstance {
     id: String,
     /// meta-data
     meta: serde_json::Value,
-    /// adjacency list
+    /// adjacency list; empty once compacted into `csr` (see [`NeighborStorage::Sparse`])
+    #[serde(skip)]
+    neighbors: Vec<NeighborSet>,
+    /// flat CSR adjacency, populated instead of `neighbors` when loaded with
+    /// [`NeighborStorage::Sparse`] (see [`CGSHOPInstance::from_file_with_options`])
     #[serde(skip)]
-    neighbors: Vec<BitSet>,
+    csr: Option<Csr>,
     /// integer coordinates
     #[serde(skip)]
     coordinates: Vec<((i64, i64), (i64, i64))>,
     /// bitset of dominated vertices
     #[serde(skip)]
     dominated: BitSet,
+    /// `(u, v)` pairs with `u < v`, built once from `neighbors`/`csr` on first call to
+    /// [`ColoringInstance::edges`] and cached from then on (see [`crate::color::distance2::Distance2Instance`]
+    /// for the same pattern)
+    #[serde(skip)]
+    edges: OnceCell<Vec<(VertexId, VertexId)>>,
     /// pre-processed data
     preprocessed: Option<PreprocessedData>,
     /// best-so-far coloring
@@ -69,11 +108,24 @@ impl ColoringInstance for CGSHOPInstance {
     }
 
     fn neighbors(&self, u: VertexId) -> Vec<VertexId> {
-        self.neighbors[u].iter().collect()
+        match &self.csr {
+            Some(csr) => csr.neighbors(u),
+            None => self.neighbors[u].iter().collect(),
+        }
+    }
+
+    fn for_each_neighbor(&self, u: VertexId, f: &mut dyn FnMut(VertexId)) {
+        match &self.csr {
+            Some(csr) => csr.row(u).iter().for_each(|&v| f(v as VertexId)),
+            None => self.neighbors[u].iter().for_each(f),
+        }
     }
 
     fn are_adjacent(&self, u: VertexId, v: VertexId) -> bool {
-        self.neighbors[u].contains(v)
+        match &self.csr {
+            Some(csr) => csr.contains(u, v),
+            None => self.neighbors[u].contains(v),
+        }
     }
 
     fn display_statistics(&self) {
@@ -90,12 +142,33 @@ impl ColoringInstance for CGSHOPInstance {
     }
 
     fn write_solution(&self, filename: &str, solution: &[Vec<usize>]) {
-        // TODO change solution to match preprossed segments
-        CGSHOPSolution::from_solution(self.id(), solution).to_file(filename);
+        // `solution` is always expressed in `self`'s own vertex ids, which are the original
+        // segment ids; it may still omit segments precomputed as dominated, so lift those
+        // back in before `from_solution` counts colors for all `self.m()` segments
+        let lifted = self.lift_dominated(solution);
+        CGSHOPSolution::from_solution(self.id(), &lifted).to_file(filename);
     }
 
     fn edges(&self) -> &[(VertexId, VertexId)] {
-        todo!()
+        self.edges.get_or_init(|| {
+            let mut res = Vec::new();
+            for u in 0..self.nb_vertices() {
+                for v in self.neighbors(u) {
+                    if u < v { res.push((u, v)); }
+                }
+            }
+            res
+        })
+    }
+
+    /** streams edges directly from this instance's own adjacency (`csr`/`neighbors`), one
+    vertex's worth at a time, instead of materializing and caching the full edge list like
+    [`Self::edges`] does — the biggest CGSHOP instances have tens of millions of conflict
+    edges, not worth keeping around just to count them once. */
+    fn edges_iter(&self) -> Box<dyn Iterator<Item = (VertexId, VertexId)> + '_> {
+        Box::new((0..self.nb_vertices()).flat_map(move |u| {
+            self.neighbors(u).into_iter().filter(move |&v| u < v).map(move |v| (u, v))
+        }))
     }
 
     fn is_dominated(&self, v: VertexId) -> bool {
@@ -133,6 +206,10 @@ impl ColoringInstance for CGSHOPInstance {
 
     fn complementary(&self) -> std::rc::Rc<dyn ColoringInstance> {
         let mut res = self.clone();
+        // the mutation loop below indexes `res.neighbors` directly, so materialize it from the
+        // flat CSR buffer first if that's how `self` is currently stored
+        res.neighbors = self.neighbor_sets();
+        res.csr = None;
         let mut degrees = vec![0; self.nb_vertices()];
         // invert the neighbors
         let vertices = self.vertices().collect::<Vec<VertexId>>();
@@ -151,6 +228,7 @@ impl ColoringInstance for CGSHOPInstance {
             }
         }
         res.dominated = BitSet::default();
+        res.edges = OnceCell::new();
         res.coloring = None;
         res.clique = None;
         res.preprocessed = Some(PreprocessedData {
@@ -161,31 +239,170 @@ impl ColoringInstance for CGSHOPInstance {
     }
 }
 
+/** where [`CGSHOPInstance::from_file_with_cache`] keeps the degree/domination pre-computation
+([`PreprocessedData`]) across runs. The instance file itself is never rewritten (unlike the
+historical behavior of [`CGSHOPInstance::from_file`] and friends, which used to overwrite it
+in place — breaking read-only instance directories and surprising callers who did not expect
+their input to be mutated): every policy other than [`CachePolicy::InMemory`] stores the
+pre-computation in a sidecar file instead, named by appending `.preprocessed.json` to the
+instance filename. */
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum CachePolicy {
+    /// load the sidecar cache file if present; otherwise compute the pre-processing and write
+    /// it there for next time
+    Sidecar,
+    /// ignore any existing sidecar cache file and recompute, overwriting it with the result
+    RefreshSidecar,
+    /// never read or write a cache file: always recompute the pre-processing in memory only
+    InMemory,
+}
+
 impl CGSHOPInstance {
-    /** reads a CGSHOP instance from a file. */
+    /** reads a CGSHOP instance from a file, storing its conflict graph as one `BitSet` per
+    vertex (see [`Self::from_file_with_options`] for the memory-constrained alternative).
+    `filename` is transparently gunzipped/unxzed first if it is gzip/xz compressed (by
+    extension or magic bytes). */
     pub fn from_file(filename: &str) -> Self {
-        let str = fs::read_to_string(filename).expect("Error while reading the file...");
+        Self::from_file_with_options(filename, NeighborStorage::Dense)
+    }
+
+    /** same as [`Self::from_file`], but lets the caller pick the conflict graph's
+    [`NeighborStorage`] backing representation. Either way, the conflict graph is built by a
+    sweep over segments ordered by `x`-interval (see [`build_neighbors_by_sweep`]) instead of
+    the naive all-pairs scan, which matters on the largest (~75K-segment) CGSHOP instances. */
+    pub fn from_file_with_options(filename: &str, storage: NeighborStorage) -> Self {
+        Self::from_file_with_neighbor_builder(filename, storage, CachePolicy::Sidecar, build_neighbors_by_sweep)
+    }
+
+    /** same as [`Self::from_file_with_options`], but spreads the conflict graph construction
+    across `nb_threads` threads using a spatial grid (see [`build_neighbors_by_grid_concurrent`])
+    instead of the single-threaded sweep: on the largest (~75K-segment) instances the sweep
+    itself becomes the bottleneck once I/O and domination pre-computation are no longer it. */
+    pub fn from_file_concurrent(filename: &str, storage: NeighborStorage, nb_threads: usize) -> Self {
+        Self::from_file_with_neighbor_builder(
+            filename, storage, CachePolicy::Sidecar,
+            move |coords, s| build_neighbors_by_grid_concurrent(coords, s, nb_threads)
+        )
+    }
+
+    /** same as [`Self::from_file`], but lets the caller control how the degree/domination
+    pre-computation is cached across runs via `cache_policy` (see [`CachePolicy`]), instead of
+    always reading and writing the `.preprocessed.json` sidecar. */
+    pub fn from_file_with_cache(filename: &str, cache_policy: CachePolicy) -> Self {
+        Self::from_file_with_neighbor_builder(filename, NeighborStorage::Dense, cache_policy, build_neighbors_by_sweep)
+    }
+
+    /** returns a [`ConflictGraphBuilder`] that computes `filename`'s conflict graph grid-cell
+    by grid-cell instead of all at once, so a caller can overlap the rest of the construction
+    with a solver already running against [`ConflictGraphBuilder::snapshot`] — see
+    [`ConflictGraphBuilder`] for the tradeoff this makes. Skips the degree/domination
+    pre-computation and sidecar caching that the `from_file*` family does, since those both
+    assume a finished conflict graph; build the rest of a regular [`CGSHOPInstance`] from
+    [`ConflictGraphBuilder::finish`]'s adjacency once you're done using partial snapshots, by
+    reloading through [`Self::from_file_with_options`] as usual. */
+    pub fn conflict_graph_builder(filename: &str, storage: NeighborStorage) -> ConflictGraphBuilder {
+        let reader = BufReader::new(crate::compress::open(filename).expect("Error while reading the file..."));
+        let res: Self = serde_json::from_reader(reader).expect("Error while deserializing the json file");
+        let coordinates: Vec<((i64, i64), (i64, i64))> = (0..res.m()).map(|s| res.build_coordinates(s)).collect();
+        ConflictGraphBuilder::new(coordinates, storage)
+    }
+
+    /// path of the sidecar cache file [`CachePolicy::Sidecar`]/[`CachePolicy::RefreshSidecar`]
+    /// read and write the [`PreprocessedData`] of `filename`'s instance to
+    fn cache_path(filename: &str) -> String {
+        format!("{}.preprocessed.json", filename)
+    }
+
+    /** lifts `solution` so that every segment this instance has precomputed as dominated (see
+    [`Self::is_dominated`]) is present, even if `solution` itself left it out because it was
+    found on a view that skipped dominated segments directly rather than through the generic
+    [`crate::reduce`]/[`crate::color::preprocess`] wrappers (whose `write_solution` only maps
+    ids back before delegating to [`Self::write_solution`]; the vertices they removed are
+    restored by their own `expand_solution`, not there). Each missing segment joins its
+    dominator's class, walking the domination chain (a dominator can itself be dominated by a
+    third segment) until it reaches one actually present in `solution`. A no-op when
+    `solution` already covers every segment, or when this instance has no domination
+    pre-computation at all. */
+    fn lift_dominated(&self, solution:&[Vec<VertexId>]) -> Vec<Vec<VertexId>> {
+        let dominations = match &self.preprocessed {
+            Some(p) => &p.dominations,
+            None => return solution.to_vec(),
+        };
+        let mut dominator_of:Vec<Option<VertexId>> = vec![None ; self.m()];
+        for &(by, v) in dominations { dominator_of[v] = Some(by); }
+        let mut class_of:Vec<Option<usize>> = vec![None ; self.m()];
+        for (c, class) in solution.iter().enumerate() {
+            for &v in class { class_of[v] = Some(c); }
+        }
+        let mut result = solution.to_vec();
+        for v in 0..self.m() {
+            if class_of[v].is_some() { continue; }
+            let mut chain = vec![v];
+            let mut cur = v;
+            let class = loop {
+                match dominator_of[cur] {
+                    None => panic!(
+                        "CGSHOPInstance::write_solution: segment {} has no color and is not dominated by a colored segment",
+                        v
+                    ),
+                    Some(by) => match class_of[by] {
+                        Some(c) => break c,
+                        None => { chain.push(by); cur = by; }
+                    }
+                }
+            };
+            for &u in &chain { result[class].push(u); class_of[u] = Some(class); }
+        }
+        result
+    }
+
+    /// the per-vertex [`NeighborSet`] view of this instance's adjacency, materializing it from
+    /// the flat CSR buffer first if that's how it is currently stored (see [`Self::complementary`])
+    fn neighbor_sets(&self) -> Vec<NeighborSet> {
+        match &self.csr {
+            Some(csr) => (0..self.nb_vertices())
+                .map(|u| NeighborSet::Sparse(csr.row(u).to_vec()))
+                .collect(),
+            None => self.neighbors.clone(),
+        }
+    }
+
+    /// shared body of [`Self::from_file_with_options`], [`Self::from_file_concurrent`] and
+    /// [`Self::from_file_with_cache`]: everything but the conflict graph construction itself,
+    /// which is delegated to `build`
+    fn from_file_with_neighbor_builder(
+        filename: &str,
+        storage: NeighborStorage,
+        cache_policy: CachePolicy,
+        build: impl FnOnce(&[((i64, i64), (i64, i64))], NeighborStorage) -> Vec<NeighborSet>,
+    ) -> Self {
+        let reader = BufReader::new(crate::compress::open(filename).expect("Error while reading the file..."));
         let mut res: Self =
-            serde_json::from_str(&str).expect("Error while deserializing the json file");
+            serde_json::from_reader(reader).expect("Error while deserializing the json file");
         // pre-process informations if needed
         println!("CGSHOP Instance: compute neighbors...");
         let n = res.nb_vertices();
         res.coordinates = (0..res.m()).map(|s| res.build_coordinates(s)).collect();
-        res.neighbors = vec![BitSet::with_capacity(n); n];
+        res.neighbors = build(&res.coordinates, storage);
+        // shrink adjacency sets
         for i in 0..n {
-            if i % 1000 == 0 {
-                println!("computing neighbors ({} / {})...", i, n);
-            }
-            for j in 0..i {
-                if are_intersecting(&res.coordinates[i], &res.coordinates[j]) {
-                    res.neighbors[i].insert(j);
-                    res.neighbors[j].insert(i);
+            res.neighbors[i].shrink_to_fit();
+        }
+        // `res.preprocessed` may already be `Some` here from a legacy instance file written by
+        // a pre-[`CachePolicy`] version of this loader; every policy except `Sidecar` ignores it
+        match cache_policy {
+            CachePolicy::Sidecar if res.preprocessed.is_none() => {
+                if let Ok(cached) = fs::read_to_string(Self::cache_path(filename)) {
+                    res.preprocessed = serde_json::from_str(&cached).ok();
                 }
             }
+            CachePolicy::Sidecar => {}
+            CachePolicy::RefreshSidecar | CachePolicy::InMemory => { res.preprocessed = None; }
         }
-        // shrink bitsets
-        for i in 0..n {
-            res.neighbors[i].shrink_to_fit();
+        if let Some(preprocessed) = &res.preprocessed {
+            for (_, v) in &preprocessed.dominations {
+                res.dominated.insert(*v);
+            }
         }
         if res.preprocessed.is_none() {
             let degrees: Vec<usize> = (0..n).map(|i| res.neighbors[i].len()).collect();
@@ -210,7 +427,7 @@ impl CGSHOPInstance {
                     // no need to check because domination is transitive
                     let mut dominating = not_dominated.clone();
                     for j in res.neighbors[i].iter() {
-                        dominating.intersect_with(&res.neighbors[j]);
+                        dominating = dominating.iter().filter(|v| res.neighbors[j].contains(*v)).collect();
                         if dominating.is_empty() {
                             break;
                         } // stop if no more remaining vertex
@@ -231,12 +448,21 @@ impl CGSHOPInstance {
                 degrees,
                 dominations,
             });
-            // write the new instance
-            let res_str = serde_json::to_string(&res).unwrap();
-            let mut file =
-                std::fs::File::create(filename).expect("unable to re-open instance file.");
-            file.write_all(res_str.as_bytes())
-                .expect("unable to write instance file.");
+            // write the pre-computation to the sidecar cache, never to the instance file itself
+            if matches!(cache_policy, CachePolicy::Sidecar | CachePolicy::RefreshSidecar) {
+                let cache_str = serde_json::to_string(&res.preprocessed).unwrap();
+                let mut file = File::create(Self::cache_path(filename))
+                    .expect("unable to create the preprocessing cache file.");
+                file.write_all(cache_str.as_bytes())
+                    .expect("unable to write the preprocessing cache file.");
+            }
+        }
+        // compact the per-vertex adjacency into a single flat CSR buffer once construction and
+        // domination pre-computation (both of which index `res.neighbors` vertex by vertex) are
+        // done: one pair of allocations for the whole graph instead of one `Vec` per vertex
+        if matches!(storage, NeighborStorage::Sparse) {
+            res.csr = Some(Csr::from_neighbor_sets(&res.neighbors));
+            res.neighbors = Vec::new();
         }
         res
     }
@@ -258,8 +484,8 @@ impl CGSHOPInstance {
 
     /// squared length of a segment
     pub fn squared_length(&self, i: usize) -> f64 {
-        let dx = self.x[self.edge_j[i]] - self.x[self.edge_i[i]];
-        let dy = self.y[self.edge_j[i]] - self.y[self.edge_i[i]];
+        let dx = (self.x[self.edge_j[i]] - self.x[self.edge_i[i]]) as f64;
+        let dy = (self.y[self.edge_j[i]] - self.y[self.edge_i[i]]) as f64;
         dx * dx + dy * dy
     }
 
@@ -271,8 +497,8 @@ impl CGSHOPInstance {
     /// edge coordinate for segment i (x1,y1,x2,y2)
     pub fn build_coordinates(&self, i: usize) -> ((i64, i64), (i64, i64)) {
         (
-            (self.x[self.edge_i[i]] as i64, self.y[self.edge_i[i]] as i64),
-            (self.x[self.edge_j[i]] as i64, self.y[self.edge_j[i]] as i64),
+            (self.x[self.edge_i[i]], self.y[self.edge_i[i]]),
+            (self.x[self.edge_j[i]], self.y[self.edge_j[i]]),
         )
     }
 
@@ -284,9 +510,34 @@ impl CGSHOPInstance {
         (dy / dx).atan() * 180. / PI
     }
 
+    /** re-tests a random sample of the adjacency bitsets against the geometric intersection
+    predicate, returning the pairs `(u,v)` for which the cached adjacency disagrees with a
+    fresh geometric test. This guards against a stale or corrupted `neighbors`/`preprocessed`
+    cache silently producing a solution the checker would consider "valid" against the wrong
+    graph. Intended as opt-in instrumentation (not run by default, as it re-does real
+    intersection tests), e.g. after loading an instance whose cache provenance is unclear. */
+    pub fn audit_adjacency_sample(&self, sample_size:usize, rng:&mut fastrand::Rng) -> Vec<(VertexId, VertexId)> {
+        let n = self.nb_vertices();
+        let sample_size = sample_size.clamp(1, n);
+        let mut discrepancies = Vec::new();
+        for _ in 0..sample_size {
+            let u = rng.usize(0..n);
+            let v = rng.usize(0..n);
+            if u == v {
+                continue;
+            }
+            let cached = self.are_adjacent(u, v);
+            let fresh = are_intersecting(&self.coordinates[u], &self.coordinates[v]);
+            if cached != fresh {
+                discrepancies.push((u, v));
+            }
+        }
+        discrepancies
+    }
+
     /// writes the list of edges to a file
     pub fn write_adj_list_file(&self, filename: &str) {
-        let m: u64 = self.neighbors.iter().map(|u| u.len() as u64).sum();
+        let m: u64 = self.vertices().map(|u| self.degree(u) as u64).sum();
         let mut s = String::new();
         s += format!("{} {}\n", self.nb_vertices(), m).as_str();
         for i in self.vertices() {
@@ -304,7 +555,7 @@ impl CGSHOPInstance {
     /// writes the instance to the dimacs format
     pub fn write_dimacs(&self, filename: &str) {
         let n: usize = self.nb_vertices();
-        let m: u64 = self.neighbors.iter().map(|u| u.len() as u64).sum();
+        let m: u64 = self.vertices().map(|u| self.degree(u) as u64).sum();
         let mut s = String::new();
         s += format!("c original: {}\n", self.id).as_str();
         s += format!("p edge {} {}\n", n, m).as_str();
@@ -322,6 +573,314 @@ impl CGSHOPInstance {
     }
 }
 
+/// backing representation for [`CGSHOPInstance`]'s per-vertex conflict sets, chosen at load time
+/// (see [`CGSHOPInstance::from_file_with_options`])
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum NeighborStorage {
+    /// one `BitSet` per vertex (default): O(1) membership, O(n) memory per vertex regardless of
+    /// degree
+    Dense,
+    /// compacted into a single flat CSR buffer ([`Csr`]) once the conflict graph is fully built:
+    /// O(log degree) membership via binary search, O(degree) memory and one allocation for the
+    /// whole graph instead of one per vertex — cuts peak memory substantially on large sparse
+    /// instances (e.g. the 75K-segment CGSHOP benchmarks)
+    Sparse,
+}
+
+/// a single vertex's conflict set, backed by either representation selected through
+/// [`NeighborStorage`]
+#[derive(Clone, Debug)]
+enum NeighborSet {
+    Dense(BitSet),
+    Sparse(Vec<u32>),
+}
+
+impl NeighborSet {
+    fn empty(storage: NeighborStorage) -> Self {
+        match storage {
+            NeighborStorage::Dense => NeighborSet::Dense(BitSet::new()),
+            NeighborStorage::Sparse => NeighborSet::Sparse(Vec::new()),
+        }
+    }
+
+    fn contains(&self, v: VertexId) -> bool {
+        match self {
+            NeighborSet::Dense(b) => b.contains(v),
+            NeighborSet::Sparse(l) => l.binary_search(&(v as u32)).is_ok(),
+        }
+    }
+
+    fn insert(&mut self, v: VertexId) {
+        match self {
+            NeighborSet::Dense(b) => { b.insert(v); },
+            NeighborSet::Sparse(l) => {
+                let v32 = v as u32;
+                if let Err(pos) = l.binary_search(&v32) { l.insert(pos, v32); }
+            }
+        }
+    }
+
+    fn remove(&mut self, v: VertexId) {
+        match self {
+            NeighborSet::Dense(b) => { b.remove(v); },
+            NeighborSet::Sparse(l) => {
+                if let Ok(pos) = l.binary_search(&(v as u32)) { l.remove(pos); }
+            }
+        }
+    }
+
+    fn len(&self) -> usize {
+        match self {
+            NeighborSet::Dense(b) => b.len(),
+            NeighborSet::Sparse(l) => l.len(),
+        }
+    }
+
+    fn iter(&self) -> Box<dyn Iterator<Item = VertexId> + '_> {
+        match self {
+            NeighborSet::Dense(b) => Box::new(b.iter()),
+            NeighborSet::Sparse(l) => Box::new(l.iter().map(|v| *v as usize)),
+        }
+    }
+
+    fn shrink_to_fit(&mut self) {
+        match self {
+            NeighborSet::Dense(b) => b.shrink_to_fit(),
+            NeighborSet::Sparse(l) => l.shrink_to_fit(),
+        }
+    }
+}
+
+/** flat compressed-sparse-row adjacency: `targets[offsets[u]..offsets[u+1]]` is vertex `u`'s
+sorted neighbor list. One pair of heap allocations for the whole graph instead of one `Vec`
+per vertex (see [`NeighborSet::Sparse`]), which matters once `n` reaches the tens of thousands:
+per-`Vec` allocator overhead and heap fragmentation otherwise rival the payload itself on the
+largest (~75K-segment) CGSHOP instances. Built once, after the conflict graph and its domination
+pre-computation are both done (see [`CGSHOPInstance::from_file_with_neighbor_builder`]); never
+mutated afterwards, unlike [`NeighborSet`] (see [`CGSHOPInstance::complementary`]). */
+#[derive(Clone, Debug, Default)]
+struct Csr {
+    offsets: Vec<u32>,
+    targets: Vec<u32>,
+}
+
+impl Csr {
+    /// compacts one [`NeighborSet`] per vertex into a single flat CSR buffer
+    fn from_neighbor_sets(neighbors: &[NeighborSet]) -> Self {
+        let mut offsets = Vec::with_capacity(neighbors.len() + 1);
+        let mut targets = Vec::new();
+        offsets.push(0);
+        for set in neighbors {
+            targets.extend(set.iter().map(|v| v as u32));
+            offsets.push(targets.len() as u32);
+        }
+        Self { offsets, targets }
+    }
+
+    fn row(&self, u: VertexId) -> &[u32] {
+        &self.targets[self.offsets[u] as usize .. self.offsets[u + 1] as usize]
+    }
+
+    fn contains(&self, u: VertexId, v: VertexId) -> bool {
+        self.row(u).binary_search(&(v as u32)).is_ok()
+    }
+
+    fn neighbors(&self, u: VertexId) -> Vec<VertexId> {
+        self.row(u).iter().map(|&v| v as usize).collect()
+    }
+}
+
+/** builds the conflict graph's adjacency by sweeping over segments ordered by their `x`-interval
+endpoints, maintaining the set of segments "active" (whose `[xmin,xmax]` currently overlaps the
+sweep position) and testing [`are_intersecting`] only against that set, rather than against
+every earlier segment as the original all-pairs scan did. On the CGSHOP visibility-graph
+instances (locally dense, globally sparse) the active set stays small, so this runs close to the
+O((n+k) log n) of a full Bentley-Ottmann sweep without its bookkeeping overhead; the worst case
+(many segments sharing the same x-span) degrades back to the O(n^2) scan it replaces, but never
+below it. */
+fn build_neighbors_by_sweep(
+    coordinates: &[((i64, i64), (i64, i64))],
+    storage: NeighborStorage,
+) -> Vec<NeighborSet> {
+    let n = coordinates.len();
+    let mut neighbors = vec![NeighborSet::empty(storage); n];
+    // event kind 0 (segment start) sorts before kind 1 (segment end) at the same x, so segments
+    // that only touch at a shared x-coordinate are still both active when tested
+    let mut events: Vec<(i64, u8, usize)> = Vec::with_capacity(2 * n);
+    for (i, &((ax, _), (bx, _))) in coordinates.iter().enumerate() {
+        events.push((min(ax, bx), 0, i));
+        events.push((max(ax, bx), 1, i));
+    }
+    events.sort_unstable();
+    let mut active: Vec<usize> = Vec::new();
+    for (_, kind, i) in events {
+        if kind == 0 {
+            for &j in &active {
+                if are_intersecting(&coordinates[i], &coordinates[j]) {
+                    neighbors[i].insert(j);
+                    neighbors[j].insert(i);
+                }
+            }
+            active.push(i);
+        } else {
+            active.retain(|&j| j != i);
+        }
+    }
+    neighbors
+}
+
+/// bounding box (min/max over both endpoints) of a segment, used by
+/// [`build_neighbors_by_grid_concurrent`] to bucket segments into grid cells
+fn segment_bbox(&((ax, ay), (bx, by)): &((i64, i64), (i64, i64))) -> (i64, i64, i64, i64) {
+    (min(ax, bx), max(ax, bx), min(ay, by), max(ay, by))
+}
+
+/** buckets `coordinates` into a uniform spatial grid by bounding box (cell side chosen so the
+average cell holds O(1) segments), returning each cell's member segment indices. A segment
+spanning several cells appears in each of them, so a pair sharing more than one cell together
+is found once per shared cell — harmless for [`build_neighbors_by_grid_concurrent`] and
+[`ConflictGraphBuilder`], since re-inserting an already-known conflict is a no-op. Shared by
+both: the grid itself is the unit of parallelism for the former and of incremental progress for
+the latter. */
+fn bucket_into_grid_cells(coordinates: &[((i64, i64), (i64, i64))]) -> Vec<Vec<usize>> {
+    let n = coordinates.len();
+    if n == 0 { return Vec::new(); }
+    let bboxes: Vec<(i64, i64, i64, i64)> = coordinates.iter().map(segment_bbox).collect();
+    let (mut min_x, mut max_x, mut min_y, mut max_y) = (i64::MAX, i64::MIN, i64::MAX, i64::MIN);
+    for &(x0, x1, y0, y1) in &bboxes {
+        min_x = min(min_x, x0); max_x = max(max_x, x1);
+        min_y = min(min_y, y0); max_y = max(max_y, y1);
+    }
+    // aim for roughly one segment per cell on average, never degenerating to a single cell
+    let extent = max((max_x - min_x).max(max_y - min_y), 1);
+    let cell_size = max(extent / ((n as f64).sqrt() as i64).max(1), 1);
+    let cell_range = |lo: i64, hi: i64| ((lo - min_x) / cell_size, (hi - min_x) / cell_size);
+    let mut cells: std::collections::HashMap<(i64, i64), Vec<usize>> = std::collections::HashMap::new();
+    for (i, &(x0, x1, y0, y1)) in bboxes.iter().enumerate() {
+        let (cx0, cx1) = cell_range(x0, x1);
+        let (cy0, cy1) = ((y0 - min_y) / cell_size, (y1 - min_y) / cell_size);
+        for cx in cx0..=cx1 {
+            for cy in cy0..=cy1 {
+                cells.entry((cx, cy)).or_default().push(i);
+            }
+        }
+    }
+    cells.into_values().collect()
+}
+
+/** incrementally computes a CGSHOP instance's conflict graph one spatial grid cell (see
+[`bucket_into_grid_cells`]) at a time, instead of the single all-at-once pass every
+[`CGSHOPInstance::from_file`]-family constructor takes. [`Self::step`] processes exactly one
+remaining cell; [`Self::snapshot`] exposes whatever has been found so far as a plain
+[`ColoringInstance`], so a caller willing to run a solver (e.g.
+[`crate::solvers::coloring::greedy_dsatur::greedy_dsatur`]) against an under-approximated
+(monotonically growing, never losing an already-found conflict) conflict graph can start before
+[`Self::is_complete`] — overlapping the rest of the grid sweep with the solver's own work instead
+of paying for them back to back. Built by [`CGSHOPInstance::conflict_graph_builder`]. */
+pub struct ConflictGraphBuilder {
+    coordinates: Vec<((i64, i64), (i64, i64))>,
+    cells: Vec<Vec<usize>>,
+    next_cell: usize,
+    neighbors: Vec<NeighborSet>,
+}
+
+impl ConflictGraphBuilder {
+    fn new(coordinates: Vec<((i64, i64), (i64, i64))>, storage: NeighborStorage) -> Self {
+        let n = coordinates.len();
+        let cells = bucket_into_grid_cells(&coordinates);
+        Self { coordinates, cells, next_cell: 0, neighbors: vec![NeighborSet::empty(storage) ; n] }
+    }
+
+    /// number of grid cells left to process
+    pub fn remaining_cells(&self) -> usize { self.cells.len() - self.next_cell }
+
+    /// true once every cell has been processed, meaning [`Self::snapshot`] is now the complete,
+    /// exact conflict graph rather than an under-approximation of it
+    pub fn is_complete(&self) -> bool { self.next_cell >= self.cells.len() }
+
+    /** processes exactly one more grid cell's pairwise intersection tests, folding any newly
+    found conflicts into the running adjacency; returns `false` (without doing anything) once
+    every cell has already been processed. Call this from whatever event loop is driving the
+    search, interleaved with solver steps, to amortize the remaining construction cost across
+    the search instead of paying it all up front. */
+    pub fn step(&mut self) -> bool {
+        if self.is_complete() { return false; }
+        let segments = &self.cells[self.next_cell];
+        for (a, &i) in segments.iter().enumerate() {
+            for &j in &segments[(a + 1)..] {
+                if are_intersecting(&self.coordinates[i], &self.coordinates[j]) {
+                    self.neighbors[i].insert(j);
+                    self.neighbors[j].insert(i);
+                }
+            }
+        }
+        self.next_cell += 1;
+        true
+    }
+
+    /// runs every remaining [`Self::step`] in one call, leaving [`Self::is_complete`] true
+    pub fn finish(&mut self) { while self.step() {} }
+
+    /** the conflict graph as computed so far, as a plain [`ColoringInstance`] (an
+    [`crate::color::AdjListInstance`] built from the current adjacency) — exact once
+    [`Self::is_complete`], an under-approximation (some conflicting pairs may still be missing)
+    otherwise. Vertex ids match [`CGSHOPInstance`]'s own segment ids, so a solution produced
+    against an early snapshot stays valid to feed back once the builder finishes, as long as
+    nothing removed between the two snapshots — which never happens, since [`Self::step`] only
+    ever inserts. */
+    pub fn snapshot(&self) -> Rc<dyn ColoringInstance> {
+        let adj_list: Vec<Vec<VertexId>> = self.neighbors.iter().map(|s| s.iter().collect()).collect();
+        Rc::new(crate::color::AdjListInstance::from_adjacency_list(adj_list))
+    }
+}
+
+/** builds the conflict graph like [`build_neighbors_by_sweep`], but spreads the work across
+`nb_threads` threads instead of running the sweep on one: segments are bucketed into a uniform
+grid (cell side chosen so the average cell holds O(1) segments) by their bounding box, and each
+thread tests `are_intersecting` only within the cells it owns (a segment spanning several cells
+is tested once per cell it is bucketed into, so a pair sharing more than one cell is found, and
+inserted into `neighbors`, more than once — harmless, since [`NeighborSet::insert`] is
+idempotent). As with [`crate::twins::find_twins_concurrent`], `inst` itself is never shared
+across threads: only the plain `coordinates` slice and the grid built from it are. */
+fn build_neighbors_by_grid_concurrent(
+    coordinates: &[((i64, i64), (i64, i64))],
+    storage: NeighborStorage,
+    nb_threads: usize,
+) -> Vec<NeighborSet> {
+    let n = coordinates.len();
+    if n == 0 { return Vec::new(); }
+    let cell_groups = bucket_into_grid_cells(coordinates);
+    let nb_threads = nb_threads.max(1);
+    let chunk_size = cell_groups.len().div_ceil(nb_threads).max(1);
+    let mut found_pairs: Vec<Vec<(usize, usize)>> = Vec::new();
+    thread::scope(|scope| {
+        let handles: Vec<_> = cell_groups.chunks(chunk_size).map(|chunk| {
+            scope.spawn(|| {
+                let mut pairs = Vec::new();
+                for segments in chunk {
+                    for (a, &i) in segments.iter().enumerate() {
+                        for &j in &segments[(a + 1)..] {
+                            if are_intersecting(&coordinates[i], &coordinates[j]) {
+                                pairs.push((i, j));
+                            }
+                        }
+                    }
+                }
+                pairs
+            })
+        }).collect();
+        for handle in handles { found_pairs.push(handle.join().unwrap()); }
+    });
+    let mut neighbors = vec![NeighborSet::empty(storage); n];
+    for pairs in found_pairs {
+        for (i, j) in pairs {
+            neighbors[i].insert(j);
+            neighbors[j].insert(i);
+        }
+    }
+    neighbors
+}
+
 /** 3 point orientation (either collinear, clockwise or counterclockwise) */
 #[derive(Debug, Eq, PartialEq)]
 enum Orientation {
@@ -547,6 +1106,70 @@ mod tests {
         inst.write_adj_list_file("tmp/rvisp3499.adjlist.txt")
     }
 
+    #[test]
+    fn test_audit_adjacency_sample_tiny() {
+        let inst = CGSHOPInstance::from_file(
+            "./insts/CGSHOP_22_original/cgshop_2022_examples_01/tiny.json",
+        );
+        let mut rng = fastrand::Rng::new();
+        let discrepancies = inst.audit_adjacency_sample(inst.nb_vertices() * inst.nb_vertices(), &mut rng);
+        assert!(discrepancies.is_empty());
+    }
+
+    #[test]
+    fn test_sparse_storage_matches_dense_storage() {
+        let filename = "./insts/cgshop_22_examples/visp_5K.instance.json";
+        let dense = CGSHOPInstance::from_file_with_options(filename, NeighborStorage::Dense);
+        let sparse = CGSHOPInstance::from_file_with_options(filename, NeighborStorage::Sparse);
+        assert_eq!(dense.nb_vertices(), sparse.nb_vertices());
+        for u in dense.vertices() {
+            assert_eq!(dense.degree(u), sparse.degree(u));
+            for v in dense.vertices() {
+                assert_eq!(dense.are_adjacent(u, v), sparse.are_adjacent(u, v));
+            }
+        }
+    }
+
+    #[test]
+    fn test_grid_concurrent_matches_single_threaded_sweep() {
+        let filename = "./insts/CGSHOP_22_original/cgshop_2022_examples_01/example_instances_visp/visp_5K.instance.json";
+        let sequential = CGSHOPInstance::from_file_with_options(filename, NeighborStorage::Dense);
+        for nb_threads in [1, 4, 8] {
+            let concurrent = CGSHOPInstance::from_file_concurrent(filename, NeighborStorage::Dense, nb_threads);
+            assert_eq!(sequential.nb_vertices(), concurrent.nb_vertices());
+            for u in sequential.vertices() {
+                assert_eq!(sequential.degree(u), concurrent.degree(u));
+                for v in sequential.vertices() {
+                    assert_eq!(sequential.are_adjacent(u, v), concurrent.are_adjacent(u, v));
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_cache_policy_never_rewrites_the_instance_file() {
+        let filename = "./insts/CGSHOP_22_original/cgshop_2022_examples_01/tiny.json";
+        let cache_path = format!("{}.preprocessed.json", filename);
+        let _ = fs::remove_file(&cache_path);
+        let original = fs::read_to_string(filename).unwrap();
+
+        let _ = CGSHOPInstance::from_file_with_cache(filename, CachePolicy::Sidecar);
+        assert_eq!(fs::read_to_string(filename).unwrap(), original, "the instance file must never be rewritten");
+        assert!(fs::metadata(&cache_path).is_ok(), "CachePolicy::Sidecar should have written the sidecar file");
+
+        // a second load should reuse the sidecar cache rather than recomputing it
+        let cached = fs::read_to_string(&cache_path).unwrap();
+        let _ = CGSHOPInstance::from_file_with_cache(filename, CachePolicy::Sidecar);
+        assert_eq!(fs::read_to_string(&cache_path).unwrap(), cached);
+
+        let _ = fs::remove_file(&cache_path);
+        let _ = CGSHOPInstance::from_file_with_cache(filename, CachePolicy::InMemory);
+        assert_eq!(fs::read_to_string(filename).unwrap(), original);
+        assert!(fs::metadata(&cache_path).is_err(), "CachePolicy::InMemory must never touch disk");
+
+        let _ = fs::remove_file(&cache_path);
+    }
+
     #[test]
     fn test_export_dimacs() {
         let name = "vispecn2518";
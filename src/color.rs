@@ -1,7 +1,19 @@
 use bit_set::BitSet;
+use std::fs;
 use std::rc::Rc;
 use std::fmt::Debug;
 
+/// graph reduction pre-processing (dominated and low-degree vertex removal), reusable by
+/// any [`ColoringInstance`] backend ahead of any solver
+pub mod preprocess;
+
+/// distance-2 (squared-graph) coloring: wraps a [`ColoringInstance`] into its square
+pub mod distance2;
+
+/// connected-component decomposition: splits a [`ColoringInstance`] into per-component
+/// sub-instances, solvable independently and merged back with [`crate::solvers::coloring::merge`]
+pub mod components;
+
 /** Vertex Id */
 pub type VertexId = usize;
 
@@ -22,6 +34,18 @@ pub trait ColoringInstance:Debug {
     /// returns the neighbors of vertex u
     fn neighbors(&self, u:VertexId) -> Vec<VertexId>;
 
+    /** calls `f` once for every neighbor of `u`, without collecting them into a `Vec` first.
+    The default implementation is just `neighbors(u).into_iter().for_each(f)`, paying the
+    same allocation `neighbors` always pays; backends that already hold `u`'s adjacency as a
+    slice or other by-reference iterable (e.g. [`AdjListInstance`]) should override this to
+    walk it directly. Intended for hot inner loops (e.g. the local searches in
+    [`crate::solvers::coloring::conflict_weighting`] and
+    [`crate::solvers::coloring::partial_weighting`]) that would otherwise allocate a fresh
+    `Vec` per visited vertex. */
+    fn for_each_neighbor(&self, u:VertexId, f:&mut dyn FnMut(VertexId)) {
+        self.neighbors(u).into_iter().for_each(f);
+    }
+
     /// returns true iff u and v are adjacent
     fn are_adjacent(&self, u:VertexId, v:VertexId) -> bool;
 
@@ -34,12 +58,73 @@ pub trait ColoringInstance:Debug {
     /// returns all edges in the instance
     fn edges(&self) -> &[(VertexId, VertexId)];
 
+    /** writes this instance's graph (not a coloring, see [`Self::write_solution`]) as a plain
+    text DIMACS (`p edge`) file at `path`, built generically from [`Self::nb_vertices`] and
+    [`Self::edges`] — unlike [`crate::dimacs::DimacsInstance::write_binary_file`], which only
+    [`crate::dimacs::DimacsInstance`] itself can produce (it needs the instance's own binary
+    upper-triangle layout). Useful for exporting an instance built programmatically (e.g.
+    [`AdjListInstance`]) or reduced by preprocessing (e.g. [`crate::reduce::ReducedInstance`])
+    to a file any DIMACS-reading tool, including this crate's own
+    [`crate::dimacs::read_from_file`], can load back. */
+    fn write_dimacs(&self, path:&str) {
+        let edges = self.edges();
+        let mut res = format!("p edge {} {}\n", self.nb_vertices(), edges.len());
+        for (u, v) in edges {
+            res += format!("e {} {}\n", u + 1, v + 1).as_str();
+        }
+        fs::write(path, res)
+            .unwrap_or_else(|_| panic!("write_dimacs: unable to write the instance in {}", path));
+    }
+
+    /** streams this instance's edges without necessarily materializing them into the owned
+    `Vec` [`Self::edges`] caches — the default implementation is just `self.edges().iter().copied()`,
+    paying that allocation once like [`Self::edges`] always does. Backends whose conflict graph
+    is itself too large to comfortably keep a full cached edge list around (e.g.
+    [`crate::cgshop::CGSHOPInstance`]) should override this to stream their own adjacency
+    directly instead. Prefer this over [`Self::edges`] in call sites that only need to visit
+    each edge once, such as conflict counting. */
+    fn edges_iter(&self) -> Box<dyn Iterator<Item = (VertexId, VertexId)> + '_> {
+        Box::new(self.edges().iter().copied())
+    }
+
     /// iterator over vertices of the graph
     fn vertices(&self) -> Box<dyn Iterator<Item=VertexId>> { Box::new(0..self.nb_vertices()) }
 
     /// returns true if the vertex is dominated by some other
     fn is_dominated(&self, _:VertexId) -> bool { false }
 
+    /** maps `u` (an id local to this instance) back to the corresponding id in the
+    instance it was built from. Defaults to the identity, which is correct for every
+    instance that does not wrap another one. Instances produced by a preprocessing step
+    ([`preprocess::reduce`], [`crate::twins::collapse`]) override this to compose with
+    whatever they themselves wrap, so [`original_ids`] and [`check_original_id_mapping`]
+    keep working no matter how many reduction layers were stacked, and a solution can
+    always be expressed against the original vertex ids (e.g. CGSHOP segment ids)
+    regardless of which reductions ran internally. */
+    fn original_id(&self, u:VertexId) -> VertexId { u }
+
+    /// weight of vertex `u`, used by the weighted coloring objective (the cost of a color
+    /// class is the maximum weight among its vertices; see [`weighted_coloring_cost`]).
+    /// defaults to 1 for every vertex, so unweighted instances behave exactly as before
+    fn weight(&self, _:VertexId) -> usize { 1 }
+
+    /** for each of the given `classes` (bitsets of vertex ids), counts how many neighbors of
+    `u` belong to that class. The default implementation walks `neighbors(u)` and tests
+    membership in each class one vertex at a time; dense backends that maintain a full
+    adjacency-matrix bitset per vertex should override this with a word-parallel
+    (bitset-intersection) kernel instead. */
+    fn count_neighbors_in_classes(&self, u:VertexId, classes:&[BitSet]) -> Vec<usize> {
+        let nbrs = self.neighbors(u);
+        classes.iter().map(|c| nbrs.iter().filter(|v| c.contains(**v)).count()).collect()
+    }
+
+    /** returns the set of colors `u` is allowed to take, or `None` if `u` is unconstrained
+    (the default, true of every instance that does not wrap another one for precoloring or
+    list-coloring purposes). Overridden by [`crate::precoloring::PrecoloredInstance`]; solvers
+    that respect list-coloring/precoloring constraints (e.g. [`crate::solvers::coloring::greedy_dsatur::greedy_dsatur`])
+    never propose a move assigning `u` a color outside this set when it is `Some`. */
+    fn allowed_colors(&self, _u:VertexId) -> Option<&BitSet> { None }
+
     /// returns a possible coloring
     fn coloring(&self) -> Option<Vec<Vec<VertexId>>> { None }
 
@@ -67,19 +152,71 @@ pub enum CheckerResult {
     VertexNotColored(usize),
     /// conflicting edge
     ConflictingEdge(usize, usize),
+    /// equitability was required but two color classes' sizes differ by more than one
+    /// (largest class size, smallest class size)
+    NotEquitable(usize, usize),
 }
 
-/**
-returns None if the solution is infeasible
-returns the objective if the solution is feasible
-*/
-pub fn checker(inst:Rc<dyn ColoringInstance>, sol:&[Vec<VertexId>]) -> CheckerResult {
+/** full validation report built by [`validate_solution`]: every violation found, instead of
+just the first one [`checker`] stops at. An empty report ([`ValidationError::is_empty`])
+never happens in practice: [`validate_solution`] returns `Ok` instead. */
+#[derive(Clone,Debug,Default,Eq,PartialEq)]
+pub struct ValidationError {
+    /// vertices appearing in more than one color class
+    pub duplicated: Vec<VertexId>,
+    /// vertices missing from every color class
+    pub uncolored: Vec<VertexId>,
+    /// `(u, v)` pairs that are adjacent but share a color class
+    pub conflicting_edges: Vec<(VertexId, VertexId)>,
+}
+
+impl ValidationError {
+    /// true if no violation was recorded
+    pub fn is_empty(&self) -> bool {
+        self.duplicated.is_empty() && self.uncolored.is_empty() && self.conflicting_edges.is_empty()
+    }
+}
+
+/** checks that `sol` partitions every vertex of `inst` exactly once into conflict-free
+classes like [`checker`], but collects every violation instead of stopping at the first:
+useful for a `check` CLI reporting everything wrong with a solution in one pass. */
+pub fn validate_solution(inst:&dyn ColoringInstance, sol:&[Vec<VertexId>]) -> Result<usize, ValidationError> {
+    let mut err = ValidationError::default();
+    let mut seen = BitSet::with_capacity(inst.nb_vertices());
+    for c in sol {
+        for &v in c {
+            if seen.contains(v) {
+                err.duplicated.push(v);
+            }
+            seen.insert(v);
+        }
+    }
+    for v in 0..inst.nb_vertices() {
+        if !seen.contains(v) {
+            err.uncolored.push(v);
+        }
+    }
+    for c in sol {
+        for (i, &v1) in c.iter().enumerate() {
+            for &v2 in &c[(i + 1)..] {
+                if inst.are_adjacent(v1, v2) {
+                    err.conflicting_edges.push((v1, v2));
+                }
+            }
+        }
+    }
+    if err.is_empty() { Ok(sol.len()) } else { Err(err) }
+}
+
+/// checks that `sol` partitions every vertex of `inst` exactly once into conflict-free
+/// classes, returning the first violation found, if any
+fn check_validity(inst:&dyn ColoringInstance, sol:&[Vec<VertexId>]) -> Option<CheckerResult> {
     // check that all vertices are added
     let mut visited = BitSet::new();
     for c in sol {
         for v in c {
             if visited.contains(*v) {
-                return CheckerResult::VertexAddedTwice(*v);
+                return Some(CheckerResult::VertexAddedTwice(*v));
             }
             visited.insert(*v);
         }
@@ -87,7 +224,7 @@ pub fn checker(inst:Rc<dyn ColoringInstance>, sol:&[Vec<VertexId>]) -> CheckerRe
     if visited.len() != inst.nb_vertices() {
         for v in 0..inst.nb_vertices() {
             if !visited.contains(v) {
-                return CheckerResult::VertexNotColored(v);
+                return Some(CheckerResult::VertexNotColored(v));
             }
         }
         panic!("checker: internal error");
@@ -97,11 +234,266 @@ pub fn checker(inst:Rc<dyn ColoringInstance>, sol:&[Vec<VertexId>]) -> CheckerRe
         for v1 in c {
             for v2 in c {
                 if inst.are_adjacent(*v1, *v2) {
-                    return CheckerResult::ConflictingEdge(*v1,*v2);
+                    return Some(CheckerResult::ConflictingEdge(*v1,*v2));
                 }
             }
         }
     }
-    // if ok: return the number of colors
-    CheckerResult::Ok(sol.len())
+    None
+}
+
+/** a [`ColoringInstance`] built directly from an adjacency or edge list already in memory,
+for using the solvers as a library without writing a DIMACS or CGSHOP file to disk first
+(e.g. from a `petgraph` graph, or any other in-process graph representation). Same
+adjacency-list-plus-bitset representation as [`crate::dimacs::DimacsInstance`] (so
+[`ColoringInstance::are_adjacent`] and [`ColoringInstance::count_neighbors_in_classes`] stay
+O(1)/word-parallel), but kept in `color.rs` instead of `dimacs.rs` since it carries none of
+that module's file-format parsing. */
+#[derive(Debug)]
+pub struct AdjListInstance {
+    n: usize,
+    adj_list: Vec<Vec<VertexId>>,
+    adj_matrix: Vec<BitSet>,
+    edges: Vec<(VertexId, VertexId)>,
+}
+
+impl AdjListInstance {
+    /// builds an instance directly from an adjacency list (`adj_list[u]`: neighbors of `u`);
+    /// `adj_list` must already be symmetric (`v` in `adj_list[u]` implies `u` in `adj_list[v]`)
+    pub fn from_adjacency_list(adj_list:Vec<Vec<VertexId>>) -> Self {
+        let n = adj_list.len();
+        let mut adj_matrix = vec![BitSet::with_capacity(n) ; n];
+        let mut edges = Vec::new();
+        for (u, neighbors) in adj_list.iter().enumerate() {
+            for &v in neighbors {
+                adj_matrix[u].insert(v);
+                if u < v { edges.push((u, v)); }
+            }
+        }
+        Self { n, adj_list, adj_matrix, edges }
+    }
+
+    /// builds an instance with `n` vertices from a plain edge list, deriving the (symmetric)
+    /// adjacency list itself; duplicate edges are merged and self-loops dropped, not rejected
+    pub fn from_edges(n:usize, edges:&[(VertexId, VertexId)]) -> Self {
+        let mut adj_list = vec![Vec::new() ; n];
+        for &(u, v) in edges {
+            if u == v { continue; }
+            adj_list[u].push(v);
+            adj_list[v].push(u);
+        }
+        for neighbors in &mut adj_list {
+            neighbors.sort_unstable();
+            neighbors.dedup();
+        }
+        Self::from_adjacency_list(adj_list)
+    }
+}
+
+impl ColoringInstance for AdjListInstance {
+    fn nb_vertices(&self) -> usize { self.n }
+
+    fn degree(&self, u:VertexId) -> usize { self.adj_list[u].len() }
+
+    fn neighbors(&self, u:VertexId) -> Vec<VertexId> { self.adj_list[u].clone() }
+
+    fn for_each_neighbor(&self, u:VertexId, f:&mut dyn FnMut(VertexId)) {
+        self.adj_list[u].iter().copied().for_each(f);
+    }
+
+    fn are_adjacent(&self, u:VertexId, v:VertexId) -> bool { self.adj_matrix[u].contains(v) }
+
+    fn write_solution(&self, filename:&str, solution:&[Vec<usize>]) {
+        let content:String = solution.iter().enumerate()
+            .map(|(c, vs)| format!(
+                "color {}: {}\n", c, vs.iter().map(|v| v.to_string()).collect::<Vec<_>>().join(" ")
+            ))
+            .collect();
+        fs::write(filename, content)
+            .unwrap_or_else(|_| panic!("AdjListInstance::write_solution: unable to write {}", filename));
+    }
+
+    fn edges(&self) -> &[(VertexId, VertexId)] { &self.edges }
+
+    fn count_neighbors_in_classes(&self, u:VertexId, classes:&[BitSet]) -> Vec<usize> {
+        classes.iter().map(|c| self.adj_matrix[u].intersection(c).count()).collect()
+    }
+}
+
+/** smallest-last ordering: repeatedly removes a minimum-degree vertex from the remaining
+subgraph and prepends it to the order, so that by the time it is colored, as few of its
+original neighbors as possible remain uncolored. Also the ordering that exposes the graph's
+degeneracy (see [`degeneracy`]): the maximum remaining degree seen during the peeling is the
+coloring number minus one. Used by
+[`greedy_sequential`](crate::solvers::coloring::greedy_sequential::greedy_sequential)'s
+[`VertexOrder::SmallestLast`](crate::solvers::coloring::greedy_sequential::VertexOrder::SmallestLast). */
+pub fn smallest_last_order(inst:&dyn ColoringInstance) -> Vec<VertexId> {
+    let n = inst.nb_vertices();
+    let mut removed:BitSet = BitSet::with_capacity(n);
+    let mut remaining_degree:Vec<usize> = (0..n).map(|u| inst.degree(u)).collect();
+    let mut order = Vec::with_capacity(n);
+    for _ in 0..n {
+        let v = (0..n).filter(|u| !removed.contains(*u))
+            .min_by_key(|&u| remaining_degree[u])
+            .expect("smallest_last_order: fewer than nb_vertices() iterations left");
+        removed.insert(v);
+        for w in inst.neighbors(v) {
+            if !removed.contains(w) { remaining_degree[w] -= 1; }
+        }
+        order.push(v);
+    }
+    order.reverse();
+    order
+}
+
+/** the degeneracy (core number) of `inst`: the maximum, over the [`smallest_last_order`]
+peeling sequence, of a vertex's degree in the subgraph remaining when it gets removed.
+`degeneracy() + 1` colors always suffice (greedily color in smallest-last order: each vertex
+has at most `degeneracy()` already-colored neighbors when its turn comes), so this is a cheap
+upper bound on the chromatic number, useful to report alongside a solver's actual color count. */
+pub fn degeneracy(inst:&dyn ColoringInstance) -> usize {
+    let order = smallest_last_order(inst);
+    let mut colored:BitSet = BitSet::with_capacity(order.len());
+    let mut max_back_degree = 0;
+    for &v in &order {
+        let back_degree = inst.neighbors(v).iter().filter(|w| colored.contains(**w)).count();
+        max_back_degree = max_back_degree.max(back_degree);
+        colored.insert(v);
+    }
+    max_back_degree
+}
+
+/**
+returns the first violation found (as a [`CheckerResult`] variant) if the solution is infeasible
+returns `CheckerResult::Ok` with the objective (number of colors) if the solution is feasible
+*/
+pub fn checker(inst:Rc<dyn ColoringInstance>, sol:&[Vec<VertexId>]) -> CheckerResult {
+    match check_validity(inst.as_ref(), sol) {
+        Some(violation) => violation,
+        // if ok: return the number of colors
+        None => CheckerResult::Ok(sol.len()),
+    }
+}
+
+/** sum, over every color class, of the maximum [`ColoringInstance::weight`] among its
+vertices: the objective minimized by weighted vertex coloring (an empty class costs 0).
+Degenerates to `sol.len()` on unweighted instances, since [`ColoringInstance::weight`]
+defaults to 1 everywhere. */
+pub fn weighted_coloring_cost(inst:&dyn ColoringInstance, sol:&[Vec<VertexId>]) -> usize {
+    sol.iter().map(|c| c.iter().map(|v| inst.weight(*v)).max().unwrap_or(0)).sum()
+}
+
+/// checks that every (non-empty) class in `sol` has the same size, up to one, i.e. that `sol`
+/// is an equitable coloring; see [`crate::solvers::coloring::equitable`]
+fn check_equitability(sol:&[Vec<VertexId>]) -> Option<CheckerResult> {
+    let sizes:Vec<usize> = sol.iter().map(|c| c.len()).filter(|&s| s > 0).collect();
+    let max_size = sizes.iter().copied().max().unwrap_or(0);
+    let min_size = sizes.iter().copied().min().unwrap_or(0);
+    if max_size - min_size > 1 {
+        Some(CheckerResult::NotEquitable(max_size, min_size))
+    } else {
+        None
+    }
+}
+
+/** same checks as [`checker`], but additionally requires `sol` to be an equitable coloring
+(every color class's size differs from every other's by at most one), returning
+[`CheckerResult::NotEquitable`] if not. See [`crate::solvers::coloring::equitable`]. */
+pub fn checker_equitable(inst:Rc<dyn ColoringInstance>, sol:&[Vec<VertexId>]) -> CheckerResult {
+    match check_validity(inst.as_ref(), sol).or_else(|| check_equitability(sol)) {
+        Some(violation) => violation,
+        None => CheckerResult::Ok(sol.len()),
+    }
+}
+
+/** same checks as [`checker`], but returns the weighted coloring cost
+([`weighted_coloring_cost`]) instead of the number of colors when the solution is valid */
+pub fn checker_weighted(inst:Rc<dyn ColoringInstance>, sol:&[Vec<VertexId>]) -> CheckerResult {
+    match check_validity(inst.as_ref(), sol) {
+        Some(violation) => violation,
+        None => CheckerResult::Ok(weighted_coloring_cost(inst.as_ref(), sol)),
+    }
+}
+
+/// remaps every vertex id in `sol` (local to `inst`) to its [`ColoringInstance::original_id`],
+/// so a solution produced on any number of stacked reduction layers (low-degree/domination
+/// peeling, twin collapsing, ...) can be reported against the ids of the instance they all
+/// ultimately wrap
+pub fn original_ids(inst:&dyn ColoringInstance, sol:&[Vec<VertexId>]) -> Solution {
+    sol.iter().map(|c| c.iter().map(|v| inst.original_id(*v)).collect()).collect()
+}
+
+/// checks that [`ColoringInstance::original_id`] is injective over `inst`'s own vertex set,
+/// i.e. no two of its vertices claim the same original id. This should always hold for a
+/// well-formed preprocessing layer; intended as a cheap sanity check, e.g. in tests
+pub fn check_original_id_mapping(inst:&dyn ColoringInstance) -> bool {
+    let mut seen = BitSet::new();
+    for u in inst.vertices() {
+        let o = inst.original_id(u);
+        if seen.contains(o) { return false; }
+        seen.insert(o);
+    }
+    true
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_adj_list_instance_from_edges_matches_adjacency() {
+        // 4-cycle 0-1-2-3-0
+        let inst = AdjListInstance::from_edges(4, &[(0, 1), (1, 2), (2, 3), (3, 0)]);
+        assert_eq!(inst.nb_vertices(), 4);
+        assert_eq!(inst.degree(0), 2);
+        assert!(inst.are_adjacent(0, 1));
+        assert!(!inst.are_adjacent(0, 2));
+        let mut neighbors_of_0 = inst.neighbors(0);
+        neighbors_of_0.sort_unstable();
+        assert_eq!(neighbors_of_0, vec![1, 3]);
+        assert_eq!(inst.edges().len(), 4);
+    }
+
+    #[test]
+    fn test_adj_list_instance_checks_out_with_checker() {
+        // properly 2-colors a 4-cycle: {0,2} and {1,3}
+        let inst:Rc<dyn ColoringInstance> = Rc::new(
+            AdjListInstance::from_edges(4, &[(0, 1), (1, 2), (2, 3), (3, 0)])
+        );
+        let sol = vec![vec![0, 2], vec![1, 3]];
+        assert_eq!(checker(inst, &sol), CheckerResult::Ok(2));
+    }
+
+    #[test]
+    fn test_checker_equitable_accepts_balanced_and_rejects_unbalanced() {
+        // properly 2-colors a 4-cycle, classes of equal size 2: equitable
+        let cycle:Rc<dyn ColoringInstance> = Rc::new(
+            AdjListInstance::from_edges(4, &[(0, 1), (1, 2), (2, 3), (3, 0)])
+        );
+        let balanced = vec![vec![0, 2], vec![1, 3]];
+        assert_eq!(checker_equitable(cycle, &balanced), CheckerResult::Ok(2));
+
+        // single edge 0-1 among 5 vertices: {1} vs {0,2,3,4} is valid but not equitable
+        let sparse:Rc<dyn ColoringInstance> = Rc::new(AdjListInstance::from_edges(5, &[(0, 1)]));
+        let unbalanced = vec![vec![0, 2, 3, 4], vec![1]];
+        assert_eq!(checker_equitable(sparse, &unbalanced), CheckerResult::NotEquitable(4, 1));
+    }
+
+    #[test]
+    fn test_validate_solution_accepts_proper_coloring() {
+        let inst = AdjListInstance::from_edges(4, &[(0, 1), (1, 2), (2, 3), (3, 0)]);
+        let sol = vec![vec![0, 2], vec![1, 3]];
+        assert_eq!(validate_solution(&inst, &sol), Ok(2));
+    }
+
+    #[test]
+    fn test_validate_solution_reports_every_violation() {
+        // 4-cycle 0-1-2-3-0, vertex 3 missing, vertex 0 duplicated, and 0-1 and 1-2 left conflicting
+        let inst = AdjListInstance::from_edges(4, &[(0, 1), (1, 2), (2, 3), (3, 0)]);
+        let sol = vec![vec![0], vec![0, 1, 2]];
+        let err = validate_solution(&inst, &sol).unwrap_err();
+        assert_eq!(err.duplicated, vec![0]);
+        assert_eq!(err.uncolored, vec![3]);
+        assert_eq!(err.conflicting_edges, vec![(0, 1), (1, 2)]);
+    }
 }
\ No newline at end of file
@@ -0,0 +1,136 @@
+//! connected-component decomposition: splits a [`ColoringInstance`] into its connected
+//! components, so a disconnected input (common for large sparse CGSHOP conflict graphs) can be
+//! solved component-by-component instead of paying a single solver's overhead on the whole
+//! graph at once. [`solve_by_component`] drives that split/solve/merge loop end-to-end, reusing
+//! [`crate::solvers::coloring::merge`]'s cross-boundary color class merging since every
+//! component's solution starts its own coloring back from color 0.
+
+use std::{collections::VecDeque, rc::Rc};
+
+use bit_set::BitSet;
+
+use crate::color::{original_ids, ColoringInstance, Solution, VertexId};
+use crate::solvers::coloring::merge::merge_disjoint_solutions;
+
+/** a single connected component of a [`ColoringInstance`], over its own `0..k` local vertex
+ids; see [`crate::color::preprocess`]'s `ReducedInstance` for the same kept-vertices-plus-base
+pattern. */
+#[derive(Debug)]
+struct ComponentInstance {
+    base: Rc<dyn ColoringInstance>,
+    /// kept[local_id]: corresponding vertex id in `base`
+    kept: Vec<VertexId>,
+    edges: Vec<(VertexId, VertexId)>,
+}
+
+impl ColoringInstance for ComponentInstance {
+    fn nb_vertices(&self) -> usize { self.kept.len() }
+
+    fn degree(&self, u:VertexId) -> usize { self.neighbors(u).len() }
+
+    fn neighbors(&self, u:VertexId) -> Vec<VertexId> {
+        (0..self.kept.len()).filter(|v| *v != u && self.are_adjacent(u, *v)).collect()
+    }
+
+    fn are_adjacent(&self, u:VertexId, v:VertexId) -> bool {
+        self.base.are_adjacent(self.kept[u], self.kept[v])
+    }
+
+    fn display_statistics(&self) {
+        println!("\tcomponent view: {} / {} vertices", self.kept.len(), self.base.nb_vertices());
+    }
+
+    fn write_solution(&self, filename:&str, solution:&[Vec<usize>]) {
+        let mapped = original_ids(self, solution);
+        self.base.write_solution(filename, &mapped);
+    }
+
+    fn original_id(&self, u:VertexId) -> VertexId {
+        self.base.original_id(self.kept[u])
+    }
+
+    fn edges(&self) -> &[(VertexId, VertexId)] { &self.edges }
+}
+
+/// the connected components of `inst`, each as a plain list of (base) vertex ids, found by a
+/// BFS from every not-yet-visited vertex
+fn connected_components(inst:&dyn ColoringInstance) -> Vec<Vec<VertexId>> {
+    let n = inst.nb_vertices();
+    let mut visited = BitSet::with_capacity(n);
+    let mut components = Vec::new();
+    for start in 0..n {
+        if visited.contains(start) { continue; }
+        let mut component = Vec::new();
+        let mut queue = VecDeque::new();
+        queue.push_back(start);
+        visited.insert(start);
+        while let Some(u) = queue.pop_front() {
+            component.push(u);
+            for v in inst.neighbors(u) {
+                if !visited.contains(v) {
+                    visited.insert(v);
+                    queue.push_back(v);
+                }
+            }
+        }
+        components.push(component);
+    }
+    components
+}
+
+/// splits `inst` into its connected components, each as an independent [`ColoringInstance`]
+/// over its own `0..k` vertex ids
+pub fn split_components(inst:Rc<dyn ColoringInstance>) -> Vec<Rc<dyn ColoringInstance>> {
+    connected_components(inst.as_ref()).into_iter().map(|kept| {
+        let mut edges = Vec::new();
+        for i in 0..kept.len() {
+            for j in (i + 1)..kept.len() {
+                if inst.are_adjacent(kept[i], kept[j]) { edges.push((i, j)); }
+            }
+        }
+        Rc::new(ComponentInstance { base: inst.clone(), kept, edges }) as Rc<dyn ColoringInstance>
+    }).collect()
+}
+
+/** splits `inst` into its connected components (largest first, so the run is dominated by the
+hardest component rather than however the original vertex order happened to interleave small
+and large ones), runs `solve` independently on each, then merges the resulting colorings back
+onto `inst`'s full vertex set via [`merge_disjoint_solutions`] (sharing color indices across
+components, since each one was colored starting from color 0 on its own). */
+pub fn solve_by_component(
+    inst:Rc<dyn ColoringInstance>,
+    mut solve:impl FnMut(Rc<dyn ColoringInstance>) -> Solution,
+) -> Solution {
+    let mut components = split_components(inst.clone());
+    components.sort_by_key(|c| std::cmp::Reverse(c.nb_vertices()));
+    let solutions:Vec<Solution> = components.iter()
+        .map(|component| original_ids(component.as_ref(), &solve(component.clone())))
+        .collect();
+    merge_disjoint_solutions(inst, &solutions, true)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::color::AdjListInstance;
+    use crate::solvers::coloring::greedy_dsatur::greedy_dsatur;
+
+    #[test]
+    fn test_split_components_finds_two_disjoint_edges() {
+        let inst:Rc<dyn ColoringInstance> = Rc::new(AdjListInstance::from_edges(4, &[(0, 1), (2, 3)]));
+        let components = split_components(inst);
+        assert_eq!(components.len(), 2);
+        assert!(components.iter().all(|c| c.nb_vertices() == 2));
+    }
+
+    #[test]
+    fn test_solve_by_component_colors_each_component_independently() {
+        // two disjoint edges: solved together they still only need 2 colors, but a
+        // same-instance solver unaware of the split would not know they can share colors
+        let inst:Rc<dyn ColoringInstance> = Rc::new(AdjListInstance::from_edges(4, &[(0, 1), (2, 3)]));
+        let solution = solve_by_component(inst.clone(), |component| greedy_dsatur(component, false));
+        assert_eq!(crate::color::checker(inst, &solution), crate::color::CheckerResult::Ok(solution.len()));
+        assert_eq!(solution.len(), 2);
+    }
+}
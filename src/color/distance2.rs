@@ -0,0 +1,126 @@
+//! wraps a [`ColoringInstance`] to present its square (distance-2 graph), where `u` and `v`
+//! are adjacent iff they are at distance ≤ 2 in the wrapped instance: coloring it properly
+//! gives a distance-2 coloring of the original graph, useful for frequency assignment (two
+//! transmitters within two hops must use different frequencies). [`Distance2Instance`] is a
+//! [`ColoringInstance`] like any other, so every existing solver
+//! ([`crate::solvers::coloring::greedy_dsatur::greedy_dsatur`],
+//! [`crate::solvers::coloring::conflict_weighting`], ...) already runs on it unmodified.
+//!
+//! [`ColoringInstance::neighbors`]/[`ColoringInstance::are_adjacent`]/[`ColoringInstance::degree`]
+//! are computed directly from the wrapped instance's own adjacency on every call, rather than
+//! ever materializing the (generally much denser) squared adjacency list, so huge CGSHOP
+//! instances stay usable. The one exception is [`ColoringInstance::edges`], which the trait
+//! requires to return an owned slice: it is computed on first use and cached from then on.
+
+use std::cell::OnceCell;
+use std::rc::Rc;
+
+use bit_set::BitSet;
+
+use crate::color::{ColoringInstance, VertexId};
+
+/// see the [module-level documentation](self)
+#[derive(Debug)]
+pub struct Distance2Instance {
+    base: Rc<dyn ColoringInstance>,
+    edges: OnceCell<Vec<(VertexId, VertexId)>>,
+}
+
+impl Distance2Instance {
+    /// wraps `base` into its square (distance-2 graph)
+    pub fn new(base:Rc<dyn ColoringInstance>) -> Self {
+        Self { base, edges: OnceCell::new() }
+    }
+}
+
+impl ColoringInstance for Distance2Instance {
+    fn nb_vertices(&self) -> usize { self.base.nb_vertices() }
+
+    fn degree(&self, u:VertexId) -> usize { self.neighbors(u).len() }
+
+    /// vertices within distance 2 of `u` in the base instance: `u`'s own neighbors, plus
+    /// their neighbors, deduplicated and excluding `u` itself
+    fn neighbors(&self, u:VertexId) -> Vec<VertexId> {
+        let mut seen = BitSet::with_capacity(self.base.nb_vertices());
+        seen.insert(u);
+        let mut res = Vec::new();
+        for v in self.base.neighbors(u) {
+            if seen.insert(v) { res.push(v); }
+            for w in self.base.neighbors(v) {
+                if seen.insert(w) { res.push(w); }
+            }
+        }
+        res
+    }
+
+    fn are_adjacent(&self, u:VertexId, v:VertexId) -> bool {
+        u != v && (
+            self.base.are_adjacent(u, v) ||
+            self.base.neighbors(u).iter().any(|w| self.base.are_adjacent(*w, v))
+        )
+    }
+
+    fn display_statistics(&self) {
+        println!("\tdistance-2 coloring over:");
+        self.base.display_statistics();
+    }
+
+    fn write_solution(&self, filename:&str, solution:&[Vec<VertexId>]) {
+        self.base.write_solution(filename, solution)
+    }
+
+    fn edges(&self) -> &[(VertexId, VertexId)] {
+        self.edges.get_or_init(|| {
+            let mut res = Vec::new();
+            for u in 0..self.nb_vertices() {
+                for v in self.neighbors(u) {
+                    if u < v { res.push((u, v)); }
+                }
+            }
+            res
+        })
+    }
+
+    fn original_id(&self, u:VertexId) -> VertexId { self.base.original_id(u) }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::color::AdjListInstance;
+
+    #[test]
+    fn test_distance2_neighbors_of_a_path() {
+        // path 0-1-2-3-4: distance-2 neighbors of 2 are {0,1,3,4}, of 0 are {1,2}
+        let base:Rc<dyn ColoringInstance> = Rc::new(
+            AdjListInstance::from_edges(5, &[(0, 1), (1, 2), (2, 3), (3, 4)])
+        );
+        let squared = Distance2Instance::new(base);
+        let mut neighbors_of_2 = squared.neighbors(2);
+        neighbors_of_2.sort_unstable();
+        assert_eq!(neighbors_of_2, vec![0, 1, 3, 4]);
+        let mut neighbors_of_0 = squared.neighbors(0);
+        neighbors_of_0.sort_unstable();
+        assert_eq!(neighbors_of_0, vec![1, 2]);
+        assert!(!squared.are_adjacent(0, 3));
+        assert!(squared.are_adjacent(0, 2));
+    }
+
+    #[test]
+    fn test_distance2_edges_are_cached_and_consistent_with_are_adjacent() {
+        let base:Rc<dyn ColoringInstance> = Rc::new(
+            AdjListInstance::from_edges(5, &[(0, 1), (1, 2), (2, 3), (3, 4)])
+        );
+        let squared = Distance2Instance::new(base);
+        let edges = squared.edges().to_vec();
+        assert_eq!(edges, squared.edges().to_vec()); // second call hits the cache
+        for u in squared.vertices() {
+            for v in squared.vertices() {
+                if u < v {
+                    assert_eq!(squared.are_adjacent(u, v), edges.contains(&(u, v)));
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,220 @@
+//! graph reduction pre-processing: [`reduce`] builds a smaller instance by removing
+//! vertices that any solver can always color last once the rest of the graph is colored,
+//! and [`expand_solution`] lifts a solution found on it back to the original vertex set.
+//! Two kinds of vertices are removed:
+//!  - low-degree vertices, whose number of still-kept neighbors is below the instance's
+//!    clique lower bound: they always have a free color among that many colors;
+//!  - dominated vertices (`N(v) ⊆ N(by)` for some still-kept `by`), which always implies
+//!    `v` and `by` are non-adjacent, so `v` can simply reuse `by`'s eventual color.
+//!
+//! [`crate::cgshop::CGSHOPInstance`] already detects dominated segments internally but
+//! nothing used that information, and [`crate::dimacs::DimacsInstance`] had no equivalent
+//! at all; [`find_dominations`] below generalizes the computation to any [`ColoringInstance`]
+//! instead.
+//!
+//! Isolated vertices (degree 0) are covered here as a special case of low-degree peeling,
+//! since any positive clique lower bound already exceeds them; see [`crate::reduce`] for an
+//! equivalent reduction that tracks `Isolated` as its own [`crate::reduce::RemovalReason`]
+//! instead, and takes the lower bound as an explicit parameter rather than deriving it from
+//! [`ColoringInstance::clique`].
+
+use std::rc::Rc;
+
+use bit_set::BitSet;
+
+use crate::color::{ColoringInstance, Solution, VertexId};
+use crate::solvers::clique::greedy_clique::greedy_clique;
+
+/** a reduction computed by [`reduce`]: enough information for [`expand_solution`] to lift a
+solution found on the reduced instance back to `base`'s full vertex set. */
+#[derive(Clone,Debug)]
+pub struct ReductionPlan {
+    /// vertices peeled for having fewer than the clique lower bound of still-kept
+    /// neighbors, in removal order (original vertex ids); reinserted in reverse order by
+    /// [`expand_solution`], so each sees exactly the neighbor colors it was peeled against
+    peeled: Vec<VertexId>,
+    /// `(by, v)`: `v` is dominated by `by` (original vertex ids); `by` is either kept or
+    /// itself dominated by a later entry, so [`expand_solution`] walks this list in reverse
+    dominations: Vec<(VertexId, VertexId)>,
+    /// kept[reduced_id] = corresponding vertex id in the base instance
+    kept: Vec<VertexId>,
+}
+
+/// detects, within `allowed`, vertices dominated by another `allowed` vertex: `v` is
+/// dominated by `by` when `N(v) ⊆ N(by)` (restricted to `allowed`). Mirrors the domination
+/// pre-computation [`crate::cgshop::CGSHOPInstance`] already performs internally,
+/// generalized to any [`ColoringInstance`] and restricted to a vertex subset so it can be
+/// run after low-degree peeling without re-introducing peeled vertices as dominators.
+pub fn find_dominations(inst:&dyn ColoringInstance, allowed:&BitSet) -> Vec<(VertexId, VertexId)> {
+    let ids:Vec<VertexId> = allowed.iter().collect();
+    let mut local = vec![None ; inst.nb_vertices()];
+    for (i, v) in ids.iter().enumerate() { local[*v] = Some(i); }
+    let neighbor_sets:Vec<BitSet> = ids.iter().map(|u| {
+        let mut b = BitSet::with_capacity(ids.len());
+        for v in inst.neighbors(*u) {
+            if let Some(lv) = local[v] { b.insert(lv); }
+        }
+        b
+    }).collect();
+    let mut not_dominated = BitSet::with_capacity(ids.len());
+    for i in 0..ids.len() { not_dominated.insert(i); }
+    let mut dominations = Vec::new();
+    for i in 0..ids.len() {
+        if !not_dominated.contains(i) { continue; }
+        // candidates dominating i: vertices adjacent to every neighbor of i (so N(i) ⊆ N(j))
+        let mut dominating = not_dominated.clone();
+        for j in neighbor_sets[i].iter() {
+            dominating.intersect_with(&neighbor_sets[j]);
+            if dominating.is_empty() { break; }
+        }
+        if let Some(j) = dominating.iter().find(|j| *j != i) {
+            dominations.push((ids[j], ids[i]));
+            not_dominated.remove(i);
+        }
+    }
+    dominations
+}
+
+/// repeatedly removes vertices with fewer than `min_colors` still-kept neighbors, returning
+/// the peeling order (original vertex ids) and the bitset of vertices left afterwards
+fn peel_low_degree(inst:&dyn ColoringInstance, min_colors:usize) -> (BitSet, Vec<VertexId>) {
+    let n = inst.nb_vertices();
+    let mut kept = BitSet::with_capacity(n);
+    for v in 0..n { kept.insert(v); }
+    let mut peeled = Vec::new();
+    loop {
+        let next = kept.iter().find(|v|
+            inst.neighbors(*v).iter().filter(|u| kept.contains(**u)).count() < min_colors
+        );
+        match next {
+            None => break,
+            Some(v) => { kept.remove(v); peeled.push(v); }
+        }
+    }
+    (kept, peeled)
+}
+
+/// a view of `base` restricted to the kept vertices after [`reduce`], re-indexed to
+/// `0..kept.len()`. Solve on this instance, then pass the solution to [`expand_solution`]
+/// together with the [`ReductionPlan`] it was built with.
+#[derive(Debug)]
+struct ReducedInstance {
+    base: Rc<dyn ColoringInstance>,
+    kept: Vec<VertexId>,
+    edges: Vec<(VertexId, VertexId)>,
+}
+
+impl ColoringInstance for ReducedInstance {
+    fn nb_vertices(&self) -> usize { self.kept.len() }
+
+    fn degree(&self, u:VertexId) -> usize { self.neighbors(u).len() }
+
+    fn neighbors(&self, u:VertexId) -> Vec<VertexId> {
+        (0..self.kept.len()).filter(|v| *v != u && self.are_adjacent(u, *v)).collect()
+    }
+
+    fn are_adjacent(&self, u:VertexId, v:VertexId) -> bool {
+        self.base.are_adjacent(self.kept[u], self.kept[v])
+    }
+
+    fn display_statistics(&self) {
+        println!("\treduced view: {} / {} vertices kept", self.kept.len(), self.base.nb_vertices());
+    }
+
+    fn write_solution(&self, filename:&str, solution:&[Vec<usize>]) {
+        let mapped = crate::color::original_ids(self, solution);
+        self.base.write_solution(filename, &mapped);
+    }
+
+    fn original_id(&self, u:VertexId) -> VertexId {
+        self.base.original_id(self.kept[u])
+    }
+
+    fn edges(&self) -> &[(VertexId, VertexId)] { &self.edges }
+}
+
+/** reduces `inst` by peeling low-degree vertices (below the clique lower bound given by
+[`ColoringInstance::clique`], falling back to [`greedy_clique`] if none is known) and then
+removing vertices dominated by a still-kept one, returning a smaller instance any solver can
+be run on and the [`ReductionPlan`] needed to lift its solution back via
+[`expand_solution`]. */
+pub fn reduce(inst:Rc<dyn ColoringInstance>) -> (Rc<dyn ColoringInstance>, ReductionPlan) {
+    let min_colors = inst.clique().map(|c| c.len())
+        .unwrap_or_else(|| greedy_clique(inst.clone()).len())
+        .max(1);
+    let (core, peeled) = peel_low_degree(inst.as_ref(), min_colors);
+    let dominations = find_dominations(inst.as_ref(), &core);
+    let mut dominated = BitSet::with_capacity(inst.nb_vertices());
+    for (_, v) in &dominations { dominated.insert(*v); }
+    let kept:Vec<VertexId> = core.iter().filter(|v| !dominated.contains(*v)).collect();
+    let mut edges = Vec::new();
+    for i in 0..kept.len() {
+        for j in (i + 1)..kept.len() {
+            if inst.are_adjacent(kept[i], kept[j]) {
+                edges.push((i, j));
+            }
+        }
+    }
+    let reduced:Rc<dyn ColoringInstance> = Rc::new(ReducedInstance { base: inst, kept: kept.clone(), edges });
+    (reduced, ReductionPlan { peeled, dominations, kept })
+}
+
+/** lifts `solution` (found on the instance returned by [`reduce`]) back to `base`'s full
+vertex set: dominated vertices reuse their dominator's color, then peeled vertices are
+reinserted in reverse removal order, each taking the lowest color not used by its
+already-colored neighbors in `base`. */
+pub fn expand_solution(solution:&Solution, plan:&ReductionPlan, base:&dyn ColoringInstance) -> Solution {
+    let mut res:Solution = solution.iter()
+        .map(|c| c.iter().map(|v| plan.kept[*v]).collect())
+        .collect();
+    let mut color_of:Vec<Option<usize>> = vec![None ; base.nb_vertices()];
+    for (c, vertices) in res.iter().enumerate() {
+        for v in vertices { color_of[*v] = Some(c); }
+    }
+    for (by, v) in plan.dominations.iter().rev() {
+        let c = color_of[*by].expect("expand_solution: a dominator should already be colored");
+        res[c].push(*v);
+        color_of[*v] = Some(c);
+    }
+    for v in plan.peeled.iter().rev() {
+        let mut used = BitSet::with_capacity(res.len());
+        for u in base.neighbors(*v) {
+            if let Some(c) = color_of[u] { used.insert(c); }
+        }
+        let c = (0..res.len()).find(|c| !used.contains(*c)).unwrap_or_else(|| {
+            res.push(Vec::new());
+            res.len() - 1
+        });
+        res[c].push(*v);
+        color_of[*v] = Some(c);
+    }
+    res
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::color::checker;
+    use crate::color::CheckerResult;
+    use crate::dimacs::DimacsInstance;
+    use crate::solvers::coloring::greedy_dsatur::greedy_dsatur;
+
+    #[test]
+    fn test_reduce_and_expand_roundtrip() {
+        let inst:Rc<dyn ColoringInstance> = Rc::new(DimacsInstance::from_file("insts/grid-instances/grid2x2"));
+        let (reduced, plan) = reduce(inst.clone());
+        let reduced_sol = greedy_dsatur(reduced, false);
+        let expanded = expand_solution(&reduced_sol, &plan, inst.as_ref());
+        assert_eq!(checker(inst, &expanded), CheckerResult::Ok(expanded.len()));
+    }
+
+    #[test]
+    fn test_reduced_instance_original_id_mapping() {
+        let inst:Rc<dyn ColoringInstance> = Rc::new(DimacsInstance::from_file("insts/grid-instances/grid2x2"));
+        let (reduced, plan) = reduce(inst.clone());
+        assert!(crate::color::check_original_id_mapping(reduced.as_ref()));
+        for reduced_id in reduced.vertices() {
+            assert_eq!(reduced.original_id(reduced_id), plan.kept[reduced_id]);
+        }
+    }
+}
@@ -0,0 +1,77 @@
+//! transparent decompression for instance files: [`DimacsInstance::from_file`](crate::dimacs::DimacsInstance::from_file)
+//! reads through [`read_to_string`] and [`CGSHOPInstance::from_file`](crate::cgshop::CGSHOPInstance::from_file)
+//! streams through [`open`], so a `.dimacs.gz`/`.json.xz` instance is decompressed on the fly instead
+//! of needing to be unpacked to disk first. Compression is detected by file extension, falling
+//! back to the format's magic bytes when the extension doesn't settle it (e.g. a gzip file
+//! without a `.gz` suffix).
+
+use std::fs::File;
+use std::io::{Read, Seek, SeekFrom};
+
+use flate2::read::GzDecoder;
+use xz2::read::XzDecoder;
+
+/// first two bytes of every gzip stream
+const GZIP_MAGIC: [u8; 2] = [0x1f, 0x8b];
+/// first six bytes of every xz stream
+const XZ_MAGIC: [u8; 6] = [0xfd, b'7', b'z', b'X', b'Z', 0x00];
+
+/// which decompression (if any) [`read_to_string`] should apply to a file's raw bytes
+enum Compression {
+    /// read the bytes as-is
+    None,
+    /// gunzip the bytes first
+    Gzip,
+    /// unxz the bytes first
+    Xz,
+}
+
+/// picks a [`Compression`] for `filename`/`bytes` by extension first, falling back to magic
+/// bytes when the extension is inconclusive
+fn detect(filename: &str, bytes: &[u8]) -> Compression {
+    if filename.ends_with(".gz") || bytes.starts_with(&GZIP_MAGIC) {
+        Compression::Gzip
+    } else if filename.ends_with(".xz") || bytes.starts_with(&XZ_MAGIC) {
+        Compression::Xz
+    } else {
+        Compression::None
+    }
+}
+
+/** reads `filename` into a `String`, transparently decompressing it first if it is gzip- or
+xz-compressed; behaves exactly like [`std::fs::read_to_string`] on a plain, uncompressed file. */
+pub(crate) fn read_to_string(filename: &str) -> std::io::Result<String> {
+    let mut raw = Vec::new();
+    File::open(filename)?.read_to_end(&mut raw)?;
+    match detect(filename, &raw) {
+        Compression::None => String::from_utf8(raw)
+            .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e)),
+        Compression::Gzip => {
+            let mut res = String::new();
+            GzDecoder::new(raw.as_slice()).read_to_string(&mut res)?;
+            Ok(res)
+        }
+        Compression::Xz => {
+            let mut res = String::new();
+            XzDecoder::new(raw.as_slice()).read_to_string(&mut res)?;
+            Ok(res)
+        }
+    }
+}
+
+/** opens `filename` for streaming reads, transparently decompressing it first if it is gzip-
+or xz-compressed; unlike [`read_to_string`], this never buffers the whole (decompressed) file
+in memory, so a caller that can consume a `Read` incrementally (e.g. `serde_json::from_reader`)
+doesn't pay for an intermediate copy of a multi-gigabyte instance. */
+pub(crate) fn open(filename: &str) -> std::io::Result<Box<dyn Read>> {
+    let mut file = File::open(filename)?;
+    let mut header = [0u8; 6];
+    let nb_read = file.read(&mut header)?;
+    let compression = detect(filename, &header[..nb_read]);
+    file.seek(SeekFrom::Start(0))?;
+    Ok(match compression {
+        Compression::None => Box::new(file),
+        Compression::Gzip => Box::new(GzDecoder::new(file)),
+        Compression::Xz => Box::new(XzDecoder::new(file)),
+    })
+}
@@ -7,6 +7,18 @@ use nom::branch::alt;
 
 use crate::color::{ColoringInstance, VertexId};
 
+/** on-disk format for a coloring solution written/read by [`DimacsInstance::write_solution_with_format`]
+and [`DimacsInstance::read_solution_with_format`]. */
+#[derive(Clone,Copy,Debug,Eq,PartialEq)]
+pub enum SolutionFormat {
+    /// this crate's own format: one color class per line, space-separated (0-indexed) vertex
+    /// ids; see [`DimacsInstance::solution_to_string`]
+    Native,
+    /// the standard DIMACS `.sol` format downstream DIMACS tooling expects: a single
+    /// `s <nb_colors>` header line followed by one `l <vertex> <color>` line per vertex, both
+    /// 1-indexed
+    Dimacs,
+}
 
 /** models a Graph Coloring instance.  */
 #[derive(Debug)]
@@ -21,13 +33,20 @@ pub struct DimacsInstance {
     adj_list: Vec<Vec<VertexId>>,
     /// if exists: adj_matrix[i] represents a bitset of its neighbors
     adj_matrix: Option<Vec<BitSet>>,
+    /// weights[i]: weight of vertex i, read from "n" lines (weighted DIMACS); `None` when the
+    /// file had none, in which case [`ColoringInstance::weight`]'s default (1) applies
+    weights: Option<Vec<usize>>,
 }
 
 impl ColoringInstance for DimacsInstance {
     fn nb_vertices(&self) -> usize { self.n }
 
     fn neighbors(&self, u:VertexId) -> Vec<VertexId> { self.adj_list[u].clone() }
-    
+
+    fn for_each_neighbor(&self, u:VertexId, f:&mut dyn FnMut(VertexId)) {
+        self.adj_list[u].iter().copied().for_each(f);
+    }
+
     fn degree(&self, u:VertexId) -> usize { self.adj_list[u].len() }
 
     fn are_adjacent(&self, u:VertexId, v:VertexId) -> bool {
@@ -39,6 +58,26 @@ impl ColoringInstance for DimacsInstance {
 
     fn edges(&self) -> &[(VertexId, VertexId)] { &self.edges }
 
+    fn weight(&self, u:VertexId) -> usize {
+        match &self.weights {
+            None => 1,
+            Some(weights) => weights[u],
+        }
+    }
+
+    /** when the adjacency matrix is populated, computes each count via a bitset intersection
+    (word-parallel AND + popcount under the hood) rather than scanning the (possibly long)
+    adjacency list vertex by vertex; falls back to the default neighbor-scan otherwise. */
+    fn count_neighbors_in_classes(&self, u:VertexId, classes:&[BitSet]) -> Vec<usize> {
+        match &self.adj_matrix {
+            None => {
+                let nbrs = self.neighbors(u);
+                classes.iter().map(|c| nbrs.iter().filter(|v| c.contains(**v)).count()).collect()
+            }
+            Some(matrix) => classes.iter().map(|c| matrix[u].intersection(c).count()).collect(),
+        }
+    }
+
     fn display_statistics(&self) {
         println!("\t{} \t vertices", self.nb_vertices());
         println!("\t{} \t edges", self.nb_edges());
@@ -89,17 +128,33 @@ impl DimacsInstance {
         }
         m /= 2; // m = (∑ d(v)) / 2
         let edges = Self::build_edges(&adj_list);
-        let mut res = Self { n,m, edges, adj_list, adj_matrix:None };
+        let mut res = Self { n,m, edges, adj_list, adj_matrix:None, weights:None };
         res.populate_adj_matrix();
         res
     }
 
     /// creates an instance from a DIMACS file
     pub fn from_file(filename:&str) -> Self {
-        let (_,_,adj_list) = read_from_file(filename);
+        let (_,_,adj_list) = read_from_file(filename)
+            .unwrap_or_else(|e| panic!("DimacsInstance::from_file: {}: {}", filename, e));
         Self::new(adj_list)
     }
 
+    /** creates an instance from a weighted DIMACS file: same edge (`e`) lines as
+    [`DimacsInstance::from_file`], plus optional vertex weight lines (`n <vertex> <weight>`,
+    1-indexed like edges); vertices without an explicit `n` line default to weight 1. */
+    pub fn from_weighted_file(filename:&str) -> Self {
+        let (_,_,adj_list) = read_from_file(filename)
+            .unwrap_or_else(|e| panic!("DimacsInstance::from_weighted_file: {}: {}", filename, e));
+        let mut res = Self::new(adj_list);
+        let mut weights = vec![1 ; res.n];
+        for (v,w) in read_weights_from_file(filename) {
+            weights[v-1] = w;
+        }
+        res.weights = Some(weights);
+        res
+    }
+
     /// if called, populate the adj_matrix
     pub fn populate_adj_matrix(&mut self) {
         let mut res = vec![BitSet::default(); self.n];
@@ -111,6 +166,73 @@ impl DimacsInstance {
         self.adj_matrix = Some(res);
     }
 
+    /** creates an instance from a DIMACS binary (`.col.b`) graph file: an ASCII `p edge n m` /
+    `p col n m` header line (optionally preceded by `c` comment lines, exactly as in the text
+    format) followed by the graph's adjacency matrix, restricted to its strict upper triangle
+    (vertex `i` adjacent to vertex `j`, `i<j`, 0-indexed here since the binary format carries no
+    textual vertex ids), bit-packed MSB-first and continuous across byte boundaries (no per-row
+    padding), with the final byte zero-padded if the triangle doesn't end on a byte boundary. */
+    pub fn from_binary_file(filename:&str) -> Self {
+        let bytes = fs::read(filename)
+            .unwrap_or_else(|_| panic!("DimacsInstance: unable to read binary file {}", filename));
+        let (n, bits) = Self::split_binary_header(&bytes);
+        let mut adj_list = vec![Vec::new() ; n];
+        let mut bit_index = 0;
+        for i in 0..n {
+            for j in (i+1)..n {
+                let byte = bits[bit_index/8];
+                let mask = 0x80u8 >> (bit_index%8);
+                if byte & mask != 0 {
+                    adj_list[i].push(j);
+                    adj_list[j].push(i);
+                }
+                bit_index += 1;
+            }
+        }
+        Self::new(adj_list)
+    }
+
+    /// consumes the `c`/`p` ASCII header lines of a DIMACS binary file, returning `(n, bits)`
+    /// where `bits` is the remaining raw adjacency-matrix bytes
+    fn split_binary_header(bytes:&[u8]) -> (usize, &[u8]) {
+        let mut offset = 0;
+        loop {
+            let eol = bytes[offset..].iter().position(|b| *b == b'\n')
+                .expect("DIMACS binary: header is not newline-terminated");
+            let line = std::str::from_utf8(&bytes[offset..offset+eol])
+                .expect("DIMACS binary: header must be ASCII text");
+            offset += eol+1;
+            if line.starts_with('c') { continue; }
+            let (_,(n,_m)) = read_header(&format!("{}\n", line))
+                .unwrap_or_else(|_| panic!("DIMACS binary: malformed problem line {:?}", line));
+            return (n, &bytes[offset..]);
+        }
+    }
+
+    /** writes the instance's graph (not a coloring, see [`Self::write_solution`]) into a DIMACS
+    binary (`.col.b`) file, using the same upper-triangle bit-packing as
+    [`Self::from_binary_file`]. */
+    pub fn write_binary_file(&self, filename:&str) {
+        let mut bytes = format!("p edge {} {}\n", self.n, self.m).into_bytes();
+        let mut bit_index = 0;
+        let mut current_byte = 0u8;
+        for i in 0..self.n {
+            for j in (i+1)..self.n {
+                if self.are_adjacent(i,j) {
+                    current_byte |= 0x80u8 >> (bit_index%8);
+                }
+                bit_index += 1;
+                if bit_index%8 == 0 {
+                    bytes.push(current_byte);
+                    current_byte = 0;
+                }
+            }
+        }
+        if bit_index%8 != 0 { bytes.push(current_byte); }
+        fs::write(filename, bytes)
+            .unwrap_or_else(|_| panic!("write_binary_file: unable to write the instance in {}", filename));
+    }
+
     /** writes a string encoding the solution (use this to export the solution) */
     pub fn solution_to_string(&self, solution:&[Vec<usize>]) -> String {
         let mut res = String::default();
@@ -122,32 +244,199 @@ impl DimacsInstance {
         } 
         res
     }
+
+    /// reads back a solution written by [`Self::solution_to_string`]/[`Self::write_solution`]:
+    /// one color class per line, vertex ids separated by whitespace
+    pub fn read_solution_from_file(filename:&str) -> Vec<Vec<VertexId>> {
+        let content = fs::read_to_string(filename)
+            .unwrap_or_else(|_| panic!("read_solution_from_file: unable to read {}", filename));
+        content.lines()
+            .map(|line| line.split_whitespace()
+                .map(|v| v.parse().unwrap_or_else(|_| panic!("read_solution_from_file: invalid vertex id {}", v)))
+                .collect())
+            .filter(|c:&Vec<VertexId>| !c.is_empty())
+            .collect()
+    }
+
+    /** encodes `solution` in the standard DIMACS `.sol` format: an `s <nb_colors>` header
+    line followed by one `l <vertex> <color>` line per vertex (both 1-indexed), in vertex
+    order, as downstream DIMACS tooling expects. */
+    pub fn solution_to_dimacs_string(solution:&[Vec<VertexId>]) -> String {
+        let mut color_of = vec![0 ; solution.iter().map(|c| c.len()).sum()];
+        for (c, vertices) in solution.iter().enumerate() {
+            for &v in vertices { color_of[v] = c; }
+        }
+        let mut res = format!("s {}\n", solution.len());
+        for (v, c) in color_of.iter().enumerate() {
+            res += format!("l {} {}\n", v + 1, c + 1).as_str();
+        }
+        res
+    }
+
+    /** writes `solution` to `filename` using `format` (see [`SolutionFormat`]) instead of
+    always [`Self::write_solution`]'s native one-class-per-line layout. */
+    pub fn write_solution_with_format(&self, filename:&str, solution:&[Vec<VertexId>], format:SolutionFormat) {
+        let content = match format {
+            SolutionFormat::Native => self.solution_to_string(solution),
+            SolutionFormat::Dimacs => Self::solution_to_dimacs_string(solution),
+        };
+        fs::write(filename, content)
+            .unwrap_or_else(|_| panic!("write_solution_with_format: unable to write the solution in {}", filename));
+    }
+
+    /** reads back a solution written by [`Self::write_solution_with_format`] in `format` (see
+    [`SolutionFormat`]); [`SolutionFormat::Native`] delegates to [`Self::read_solution_from_file`]. */
+    pub fn read_solution_with_format(filename:&str, format:SolutionFormat) -> Vec<Vec<VertexId>> {
+        match format {
+            SolutionFormat::Native => Self::read_solution_from_file(filename),
+            SolutionFormat::Dimacs => {
+                let content = fs::read_to_string(filename)
+                    .unwrap_or_else(|_| panic!("read_solution_with_format: unable to read {}", filename));
+                let mut nb_colors = 0;
+                let mut classes:Vec<Vec<VertexId>> = Vec::new();
+                for line in content.lines() {
+                    let tokens:Vec<&str> = line.split_whitespace().collect();
+                    match tokens.as_slice() {
+                        ["s", k] => {
+                            nb_colors = k.parse()
+                                .unwrap_or_else(|_| panic!("read_solution_with_format: invalid 's' line {:?}", line));
+                            classes = vec![Vec::new() ; nb_colors];
+                        },
+                        ["l", v, c] => {
+                            let v:VertexId = v.parse()
+                                .unwrap_or_else(|_| panic!("read_solution_with_format: invalid vertex id in {:?}", line));
+                            let c:usize = c.parse()
+                                .unwrap_or_else(|_| panic!("read_solution_with_format: invalid color in {:?}", line));
+                            classes[c - 1].push(v - 1);
+                        },
+                        [] => {},
+                        _ => panic!("read_solution_with_format: malformed line {:?}", line),
+                    }
+                }
+                classes
+            },
+        }
+    }
 }
 
 
-/// reads an instance from file, returns (n,m,adj_list)
-pub fn read_from_file(filename:&str) -> (usize, usize, Vec<Vec<usize>>) {
-    let s1 = fs::read_to_string(filename)
-        .expect("Instance: unable to read file").replace("\r","");
-    let s2 = skip_comments(s1.as_str()).unwrap().0;
-    let (mut s3,(n,m)) = read_header(s2).unwrap();
+/** why [`read_from_file`] gave up on a DIMACS file, with the 1-indexed line number of the
+line that caused it, so a caller can report something more actionable than a panic. */
+#[derive(Clone,Debug,Eq,PartialEq)]
+pub enum ParseError {
+    /// the file could not be opened or read at all
+    Io(String),
+    /// no `p edge`/`p col`/`p edges` header line was found before the first non-comment line
+    MissingHeader { line:usize },
+    /// `line` is neither a comment, a header, an edge (`e u v` or `e u v w`) nor a vertex
+    /// weight (`n v w`) line
+    MalformedLine { line:usize, content:String },
+    /// an edge endpoint is outside `1..=n`
+    VertexOutOfRange { line:usize, vertex:usize, n:usize },
+}
+
+impl std::fmt::Display for ParseError {
+    fn fmt(&self, f:&mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            ParseError::Io(msg) => write!(f, "unable to read the file: {}", msg),
+            ParseError::MissingHeader { line } =>
+                write!(f, "line {}: no 'p edge'/'p col'/'p edges' header found", line),
+            ParseError::MalformedLine { line, content } =>
+                write!(f, "line {}: malformed line {:?}", line, content),
+            ParseError::VertexOutOfRange { line, vertex, n } =>
+                write!(f, "line {}: vertex {} is out of range (instance has {} vertices)", line, vertex, n),
+        }
+    }
+}
+
+/// the 1-indexed line number of `remaining` within `full`, both slices of the same string
+/// (as produced by the nom parsers in this module, which only ever trim a prefix off `full`)
+fn line_of(full:&str, remaining:&str) -> usize {
+    1 + full[..full.len() - remaining.len()].matches('\n').count()
+}
+
+/** reads an instance from a DIMACS file, returning `(n, m, adj_list)` or a [`ParseError`]
+pinpointing the first line it could not make sense of. Transparently gunzips/unxzes `filename`
+first if it is gzip/xz compressed (by extension or magic bytes). Tolerates (with a warning on stderr,
+rather than failing) duplicate edges and self-loops, and accepts `p edge`, `p col` and
+`p edges` headers as well as weighted `e u v w` edge lines (the trailing weight is read and
+discarded: this crate's weighted coloring support, see [`ColoringInstance::weight`], is
+driven by `n` vertex-weight lines, not per-edge weights). */
+pub fn read_from_file(filename:&str) -> Result<(usize, usize, Vec<Vec<usize>>), ParseError> {
+    let s1 = crate::compress::read_to_string(filename)
+        .map_err(|e| ParseError::Io(e.to_string()))?
+        .replace("\r","");
+    let s2 = skip_comments(s1.as_str()).map(|(rest,_)| rest).unwrap_or(s1.as_str());
+    let (mut s3,(n,m)) = read_header(s2)
+        .map_err(|_| ParseError::MissingHeader { line: line_of(&s1, s2) })?;
     let mut adj_list = vec![Vec::new();n];
+    let mut adj_set:Vec<BitSet> = vec![BitSet::with_capacity(n) ; n];
     let mut check_nb_edges = 0;
-    while match read_edge(s3) {
-        Ok((tmp,(a,b))) => {
-            s3 = tmp;
-            adj_list[a-1].push(b-1);
-            adj_list[b-1].push(a-1);
-            check_nb_edges += 1;
-            true
+    loop {
+        if s3.trim().is_empty() { break; }
+        match read_edge(s3) {
+            Ok((tmp,(a,b))) => {
+                let line = line_of(&s1, s3);
+                s3 = tmp;
+                check_nb_edges += 1;
+                if a == 0 || a > n { return Err(ParseError::VertexOutOfRange { line, vertex:a, n }); }
+                if b == 0 || b > n { return Err(ParseError::VertexOutOfRange { line, vertex:b, n }); }
+                if a == b {
+                    eprintln!("warning: {}: self-loop on vertex {} ignored", filename, a);
+                    continue;
+                }
+                if !adj_set[a-1].insert(b-1) {
+                    eprintln!("warning: {}: duplicate edge ({}, {}) ignored", filename, a, b);
+                    continue;
+                }
+                adj_set[b-1].insert(a-1);
+                adj_list[a-1].push(b-1);
+                adj_list[b-1].push(a-1);
+            }
+            // skip vertex weight ("n") lines interleaved with edges in weighted DIMACS files
+            Err(_) => match read_weight(s3) {
+                Ok((tmp,_)) => { s3 = tmp; }
+                Err(_) => return Err(ParseError::MalformedLine {
+                    line: line_of(&s1, s3),
+                    content: s3.lines().next().unwrap_or(s3).to_string(),
+                }),
+            }
         }
-        Err(_) => false
-    } {}
-    assert!(
-        check_nb_edges == m || 2*check_nb_edges == m,
-        "check: {}\t m: {}", check_nb_edges, m
-    );
-    (n, m, adj_list)
+    }
+    if check_nb_edges != m && 2*check_nb_edges != m {
+        eprintln!(
+            "warning: {}: header declares {} edges but {} were read", filename, m, check_nb_edges
+        );
+    }
+    Ok((n, m, adj_list))
+}
+
+/// reads the vertex weight (`n`) lines of a weighted DIMACS file, returning `(vertex, weight)`
+/// pairs (1-indexed, like [`read_edge`]'s edges)
+fn read_weights_from_file(filename:&str) -> Vec<(usize,usize)> {
+    let s1 = crate::compress::read_to_string(filename)
+        .expect("Instance: unable to read file").replace("\r","");
+    let s2 = skip_comments(s1.as_str()).unwrap().0;
+    let (mut s3,_) = read_header(s2).unwrap();
+    let mut weights = Vec::new();
+    loop {
+        match read_weight(s3) {
+            Ok((tmp,vw)) => { s3 = tmp; weights.push(vw); }
+            Err(_) => match read_edge(s3) {
+                Ok((tmp,_)) => { s3 = tmp; }
+                Err(_) => break,
+            }
+        }
+    }
+    weights
+}
+
+/// reads a vertex weight line (`n <vertex> <weight>`, WARNING: vertex indices start at 1)
+fn read_weight(s:&str) -> IResult<&str, (usize,usize)> {
+    match nom::bytes::complete::tag("n ")(s) {
+        Ok((remaining,_)) => read_two_integers(remaining),
+        Err(e) => Err(e)
+    }
 }
 
 /// skips a single comment
@@ -182,13 +471,16 @@ fn read_two_integers(s:&str) -> IResult<&str, (usize,usize)> {
                     match nom::character::complete::digit1(remaining2) {
                         Ok((remaining3, s2)) => {
                             let n2 = s2.parse::<usize>().unwrap();
-                            if nom::character::is_newline(*remaining3.as_bytes().get(0).unwrap()) {
-                                match take::<usize, &str, Error<&str>>(usize_1)(remaining3) {
-                                    Ok((remaining4,_)) => Ok((remaining4,(n1,n2))),
-                                    Err(_) => Ok((remaining3,(n1,n2))),
+                            match remaining3.as_bytes().first() {
+                                Some(b) if nom::character::is_newline(*b) => {
+                                    match take::<usize, &str, Error<&str>>(usize_1)(remaining3) {
+                                        Ok((remaining4,_)) => Ok((remaining4,(n1,n2))),
+                                        Err(_) => Ok((remaining3,(n1,n2))),
+                                    }
                                 }
-                            } else {
-                                Ok((remaining3,(n1,n2)))
+                                // no newline follows: either trailing fields on the same line
+                                // (e.g. an edge weight) or end of file with no final newline
+                                _ => Ok((remaining3,(n1,n2))),
                             }
                         },
                         Err(e) => Err(e),
@@ -201,9 +493,10 @@ fn read_two_integers(s:&str) -> IResult<&str, (usize,usize)> {
     }
 }
 
-/// reads header containing (n,m)
+/// reads header containing (n,m); accepts the `p edge`, `p col` and `p edges` spellings seen
+/// in the wild, all followed by the same `n m` pair
 pub fn read_header(s:&str) -> IResult<&str, (usize,usize)> {
-    match alt((tag("p edge "), tag("p col ")))(s) {
+    match alt((tag("p edge "), tag("p edges "), tag("p col ")))(s) {
         Ok((remaining,_)) => { // if ok, read the two numbers
             read_two_integers(remaining)
         }
@@ -211,14 +504,25 @@ pub fn read_header(s:&str) -> IResult<&str, (usize,usize)> {
     }
 }
 
-/// reads edge line (WARNING: indices start at 1 in the DIMACS format)
+/** reads an edge line (WARNING: indices start at 1 in the DIMACS format). Accepts both the
+plain `e u v` form and the weighted `e u v w` form some DIMACS-derived formats use for edge
+weights: any fields after `v` are skipped rather than rejected, since this crate has nowhere to
+put a per-edge weight (see [`read_from_file`]'s doc comment). Only whitespace and digits
+(the optional weight field) are skipped, and the record-ending newline is only consumed when it
+is the very next byte after that: some DIMACS files (e.g. `insts/other-instances/peterson.col`)
+pack several edges on one physical line with no separator at all (`e 1 4e 1 7e 1 5...`), so
+scanning ahead for the line's actual final newline would swallow every edge after the first. */
 pub fn read_edge(s:&str) -> IResult<&str, (usize,usize)> {
-    match nom::bytes::complete::tag("e ")(s) {
-        Ok((remaining,_)) => { // if ok, read the two numbers
-            read_two_integers(remaining)
-        }
-        Err(e) => Err(e)
-    }
+    let (remaining, _) = nom::bytes::complete::tag("e ")(s)?;
+    let (remaining, a) = nom::character::complete::digit1(remaining)?;
+    let (remaining, _) = take(1usize)(remaining)?;
+    let (remaining, b) = nom::character::complete::digit1(remaining)?;
+    let after_trailing_fields = remaining.trim_start_matches(|c:char| c == ' ' || c.is_ascii_digit());
+    let remaining = match after_trailing_fields.as_bytes().first() {
+        Some(byte) if nom::character::is_newline(*byte) => &after_trailing_fields[1..],
+        _ => after_trailing_fields,
+    };
+    Ok((remaining, (a.parse().unwrap(), b.parse().unwrap())))
 }
 
 
@@ -264,9 +568,19 @@ mod tests {
 
     #[test]
     fn test_read_edges_on_one_line() {
-        let (n,m,e) = read_from_file("insts/other-instances/peterson.col");
-        println!("n:{}, m:{}", n, m);
-        println!("e:{:?}", e);
+        // peterson.col packs several edges per physical line with no separator at all
+        // (`e 1 4e 1 7e 1 5...`); make sure every one of them is actually read
+        let (n,m,adj_list) = read_from_file("insts/other-instances/peterson.col").unwrap();
+        assert_eq!(n, 10);
+        assert_eq!(m, 15);
+        assert_eq!(adj_list.iter().map(|neighbors| neighbors.len()).sum::<usize>(), 2*m);
+    }
+
+    #[test]
+    fn test_read_header_edges_plural() {
+        let s = "p edges 2 1\ne 1 2";
+        assert_eq!(read_header(s).unwrap().0, "e 1 2");
+        assert_eq!(read_header(s).unwrap().1, (2,1));
     }
 
     #[test]
@@ -275,4 +589,102 @@ mod tests {
         assert_eq!(read_edge(s).unwrap().1, (1,2));
         assert_eq!(read_edge(s).unwrap().0, "");
     }
+
+    #[test]
+    fn test_read_edge_weighted() {
+        // trailing per-edge weight is tolerated and discarded
+        let s = "e 1 2 7\ne 1 3\n";
+        let (remaining,(a,b)) = read_edge(s).unwrap();
+        assert_eq!((a,b), (1,2));
+        assert_eq!(remaining, "e 1 3\n");
+    }
+
+    #[test]
+    fn test_read_from_file_missing_header() {
+        let filename = "tmp/test_missing_header.col";
+        fs::write(filename, "e 1 2\n").unwrap();
+        match read_from_file(filename) {
+            Err(ParseError::MissingHeader { line }) => assert_eq!(line, 1),
+            other => panic!("expected MissingHeader, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_read_from_file_vertex_out_of_range() {
+        let filename = "tmp/test_out_of_range.col";
+        fs::write(filename, "p edge 2 1\ne 1 3\n").unwrap();
+        match read_from_file(filename) {
+            Err(ParseError::VertexOutOfRange { line, vertex, n }) => {
+                assert_eq!((line,vertex,n), (2,3,2));
+            }
+            other => panic!("expected VertexOutOfRange, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_read_from_file_tolerates_duplicate_edges_and_self_loops() {
+        let filename = "tmp/test_dup_self_loop.col";
+        fs::write(filename, "p edge 3 4\ne 1 1\ne 1 2\ne 1 2\ne 2 3\n").unwrap();
+        let (n,_,adj_list) = read_from_file(filename).unwrap();
+        assert_eq!(n, 3);
+        assert_eq!(adj_list[0], vec![1]);
+        assert_eq!(adj_list[1], vec![0,2]);
+        assert_eq!(adj_list[2], vec![1]);
+    }
+
+    #[test]
+    fn test_read_weight() {
+        let s = "n 1 5\n";
+        assert_eq!(read_weight(s).unwrap().1, (1,5));
+        assert_eq!(read_weight(s).unwrap().0, "");
+    }
+
+    #[test]
+    fn test_read_weighted_instance() {
+        let filename = "tmp/test_weighted_grid2x2.col";
+        fs::write(filename, "p edge 4 4\nn 1 5\nn 3 2\ne 1 2\ne 1 3\ne 2 4\ne 3 4\n").unwrap();
+        let inst = DimacsInstance::from_weighted_file(filename);
+        assert_eq!(inst.nb_vertices(), 4);
+        assert_eq!(inst.nb_edges(), 4);
+        assert_eq!(inst.weight(0), 5); // vertex 1 -> index 0
+        assert_eq!(inst.weight(1), 1); // unweighted vertex defaults to 1
+        assert_eq!(inst.weight(2), 2); // vertex 3 -> index 2
+    }
+
+    #[test]
+    fn test_binary_format_roundtrip() {
+        let text_inst = DimacsInstance::from_file("insts/grid-instances/grid2x2");
+        let filename = "tmp/test_grid2x2.col.b";
+        text_inst.write_binary_file(filename);
+        let binary_inst = DimacsInstance::from_binary_file(filename);
+        assert_eq!(binary_inst.nb_vertices(), text_inst.nb_vertices());
+        assert_eq!(binary_inst.nb_edges(), text_inst.nb_edges());
+        for i in 0..text_inst.nb_vertices() {
+            for j in 0..text_inst.nb_vertices() {
+                assert_eq!(binary_inst.are_adjacent(i,j), text_inst.are_adjacent(i,j));
+            }
+        }
+    }
+
+    #[test]
+    fn test_solution_roundtrip() {
+        let inst = DimacsInstance::from_file("insts/grid-instances/grid2x2");
+        let filename = "tmp/test_grid2x2.sol";
+        let sol = vec![vec![0, 3], vec![1, 2]];
+        inst.write_solution(filename, &sol);
+        let read_back = DimacsInstance::read_solution_from_file(filename);
+        assert_eq!(read_back, sol);
+    }
+
+    #[test]
+    fn test_dimacs_format_solution_roundtrip() {
+        let inst = DimacsInstance::from_file("insts/grid-instances/grid2x2");
+        let filename = "tmp/test_grid2x2.dimacs.sol";
+        let sol = vec![vec![0, 3], vec![1, 2]];
+        inst.write_solution_with_format(filename, &sol, SolutionFormat::Dimacs);
+        let content = fs::read_to_string(filename).unwrap();
+        assert!(content.starts_with("s 2\n"));
+        let read_back = DimacsInstance::read_solution_with_format(filename, SolutionFormat::Dimacs);
+        assert_eq!(read_back, sol);
+    }
 }
\ No newline at end of file
@@ -0,0 +1,71 @@
+use std::fs;
+
+use bit_set::BitSet;
+
+use crate::color::VertexId;
+use crate::dimacs::DimacsInstance;
+
+/** reads a plain whitespace-separated edge-list graph (SNAP, Matrix Market edge lists) into a
+[`DimacsInstance`]: one edge `u v` per line, lines starting with `#` or `%` ignored as
+comments. Vertex ids may be 0- or 1-based (detected from the lowest id seen in the file,
+shifted down to 0); since SNAP graphs are directed, both `u v` and `v u` may appear for the
+same undirected edge, so duplicate and self-loop edges are silently merged away rather than
+inflating the resulting degree. */
+pub fn from_file(filename:&str) -> DimacsInstance {
+    let content = fs::read_to_string(filename)
+        .unwrap_or_else(|_| panic!("edgelist::from_file: unable to read {}", filename));
+    let raw_edges:Vec<(VertexId, VertexId)> = content.lines()
+        .map(str::trim)
+        .filter(|l| !l.is_empty() && !l.starts_with('#') && !l.starts_with('%'))
+        .map(|l| {
+            let parse = |tok:Option<&str>| tok
+                .unwrap_or_else(|| panic!("edgelist: malformed line {:?}", l))
+                .parse::<VertexId>()
+                .unwrap_or_else(|_| panic!("edgelist: malformed line {:?}", l));
+            let mut tokens = l.split_whitespace();
+            (parse(tokens.next()), parse(tokens.next()))
+        })
+        .collect();
+    let min_id = raw_edges.iter().flat_map(|&(u, v)| [u, v]).min().unwrap_or(0);
+    let max_id = raw_edges.iter().flat_map(|&(u, v)| [u, v]).max().unwrap_or(0);
+    let n = if raw_edges.is_empty() { 0 } else { max_id - min_id + 1 };
+    let mut adj_sets = vec![BitSet::with_capacity(n) ; n];
+    for (u, v) in raw_edges {
+        let (u, v) = (u - min_id, v - min_id);
+        if u != v {
+            adj_sets[u].insert(v);
+            adj_sets[v].insert(u);
+        }
+    }
+    let adj_list:Vec<Vec<VertexId>> = adj_sets.iter().map(|s| s.iter().collect()).collect();
+    DimacsInstance::new(adj_list)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::color::ColoringInstance;
+
+    #[test]
+    fn test_zero_based_edge_list() {
+        let filename = "tmp/test_edgelist_zero_based.txt";
+        fs::write(filename, "# a 4-cycle\n0 1\n1 2\n2 3\n3 0\n").unwrap();
+        let inst = from_file(filename);
+        assert_eq!(inst.nb_vertices(), 4);
+        assert_eq!(inst.nb_edges(), 4);
+        assert!(inst.are_adjacent(0, 1));
+        assert!(!inst.are_adjacent(0, 2));
+    }
+
+    #[test]
+    fn test_one_based_and_both_directions_deduplicated() {
+        let filename = "tmp/test_edgelist_one_based.txt";
+        // same 4-cycle, 1-indexed, each edge listed in both directions (directed SNAP style)
+        fs::write(filename, "1 2\n2 1\n2 3\n3 2\n3 4\n4 3\n4 1\n1 4\n").unwrap();
+        let inst = from_file(filename);
+        assert_eq!(inst.nb_vertices(), 4);
+        assert_eq!(inst.nb_edges(), 4);
+        assert!(inst.are_adjacent(0, 1)); // original vertices 1 and 2
+        assert!(!inst.are_adjacent(0, 2));
+    }
+}
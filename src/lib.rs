@@ -19,14 +19,55 @@
 /// coloring instance base trait, solutions and checker
 pub mod color;
 
+/// transparent gzip/xz decompression for instance files, used by [`dimacs`] and [`cgshop`]
+mod compress;
+
 /// read/write DIMACS instances & formats
 pub mod dimacs;
 
+/// read plain whitespace-separated edge-list graphs (SNAP, Matrix Market edge lists)
+pub mod edgelist;
+
 /// read/write CGSHOP instances & solutions (specialized for very large coloring instances)
 pub mod cgshop;
 
 /// helper and utility methods for executables
 pub mod util;
 
+/// persisted per-instance (or per-instance-family) solver tuning profiles
+pub mod profiles;
+
+/// failure-tolerant scanner that classifies and cheaply validates instance files in a directory
+pub mod scanner;
+
+/// process-wide registry of the best known bounds per instance, shared across solver invocations
+pub mod bounds_registry;
+
+/// chained solver pipelines declared as data, built in code or loaded from JSON
+pub mod pipeline;
+
+/// detection and exploitation of vertex modules (true/false twins)
+pub mod twins;
+
+/// per-vertex allowed-color-set (list-coloring) and precoloring constraints, via a
+/// [`ColoringInstance`](crate::color::ColoringInstance) wrapper
+pub mod precoloring;
+
+/// graph-reduction preprocessing: removes dominated, low-degree and isolated vertices before
+/// solving, and restores them into the solution afterwards
+pub mod reduce;
+
+/// post-processing passes (color-class merging, move-to-lower-class compaction) applied to a
+/// finished coloring before it is checked, exported or reported
+pub mod postprocess;
+
 /// solvers for the graph coloring problem and maximum clique problem
-pub mod solvers;
\ No newline at end of file
+pub mod solvers;
+
+/// named benchmark presets (solver choice + tuning profile) for known instance families
+pub mod presets;
+
+/// structured per-run results database (SQLite), for longitudinal experiment tracking;
+/// requires the `results_db` feature
+#[cfg(feature = "results_db")]
+pub mod results_db;
\ No newline at end of file
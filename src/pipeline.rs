@@ -0,0 +1,264 @@
+//! chained solver pipelines declared as data: an ordered list of solver stages with
+//! per-stage budgets and parameters, built in code or loaded from a JSON file, and
+//! executed by [`Pipeline::run`] which threads the solution (and timings) between
+//! stages. Lets experiment configurations live outside the source instead of being
+//! hard-coded into every binary.
+
+use std::rc::Rc;
+use std::time::Instant;
+
+use dogs::search_algorithm::TimeStoppingCriterion;
+use serde::{Deserialize, Serialize};
+
+use crate::color::preprocess::{expand_solution, reduce};
+use crate::color::{ColoringInstance, Solution};
+use crate::solvers::clique::greedy_clique::greedy_clique;
+use crate::solvers::clique::partial_weighting::clique_partial_weighting;
+use crate::solvers::coloring::conflict_weighting::coloring_conflict_weighting;
+use crate::solvers::coloring::greedy_dsatur::greedy_dsatur;
+use crate::solvers::coloring::greedy_rlf::greedy_rlf;
+use crate::solvers::coloring::partial_weighting::{coloring_partial_weighting, coloring_partial_weighting_with_fixed_clique};
+
+/// one stage of a solver pipeline
+#[derive(Clone,Debug,Serialize,Deserialize)]
+#[serde(tag = "kind")]
+pub enum PipelineStage {
+    /// greedy DSATUR construction heuristic (discards the incoming solution)
+    GreedyDsatur,
+    /// Recursive Largest First construction heuristic (discards the incoming solution)
+    GreedyRlf,
+    /// conflict-weighting local search, improving the incoming solution for up to
+    /// `time_budget` seconds
+    ConflictWeighting {
+        /// time budget of this stage, in seconds
+        time_budget: f32
+    },
+    /// partial-weighting local search, improving the incoming solution for up to
+    /// `time_budget` seconds
+    PartialWeighting {
+        /// time budget of this stage, in seconds
+        time_budget: f32
+    },
+    /// partial-weighting local search, improving the incoming solution for up to
+    /// `time_budget` seconds, additionally fixing `inst.clique()` (if any) to its current
+    /// colors for the duration of the stage so the search never wastes moves on vertices that
+    /// cannot be improved past the clique's lower bound (see
+    /// [`crate::solvers::coloring::partial_weighting::PartialWeightingLocalSearch::fix_clique`]);
+    /// falls back to a plain [`PipelineStage::PartialWeighting`] if the instance exposes no clique
+    PartialWeightingFixClique {
+        /// time budget of this stage, in seconds
+        time_budget: f32
+    },
+}
+
+/// one line of a pipeline execution report
+#[derive(Clone,Debug,Serialize,Deserialize)]
+pub struct PipelineStepReport {
+    /// the stage that produced this solution
+    pub stage: PipelineStage,
+    /// number of colors of the solution right after this stage
+    pub nb_colors: usize,
+    /// wall-clock time spent in this stage, in seconds
+    pub elapsed: f32,
+}
+
+/// an ordered list of pipeline stages, executed sequentially by [`Pipeline::run`]
+#[derive(Clone,Debug,Default,Serialize,Deserialize)]
+pub struct Pipeline {
+    /// the stages to run, in order
+    pub stages: Vec<PipelineStage>,
+}
+
+impl Pipeline {
+    /// loads a pipeline description from a JSON file
+    pub fn load(filename:&str) -> Self {
+        let content = std::fs::read_to_string(filename)
+            .unwrap_or_else(|why| panic!("Pipeline::load: unable to read {}: {}", filename, why));
+        serde_json::from_str(&content)
+            .unwrap_or_else(|why| panic!("Pipeline::load: unable to parse {}: {}", filename, why))
+    }
+
+    /// saves the pipeline description to a JSON file
+    pub fn save(&self, filename:&str) {
+        let content = serde_json::to_string_pretty(self).unwrap();
+        std::fs::write(filename, content)
+            .unwrap_or_else(|why| panic!("Pipeline::save: unable to write {}: {}", filename, why));
+    }
+
+    /** runs every stage in order, feeding each local-search stage the solution produced by
+    the previous one (construction stages ignore the incoming solution and build their own
+    from scratch); returns the final solution along with a report of every intermediate
+    stage's solution size and duration. */
+    pub fn run(&self, inst:Rc<dyn ColoringInstance>) -> (Solution, Vec<PipelineStepReport>) {
+        let mut solution:Solution = Vec::new();
+        let mut report = Vec::new();
+        for stage in &self.stages {
+            let start = Instant::now();
+            solution = match stage {
+                PipelineStage::GreedyDsatur => greedy_dsatur(inst.clone(), false),
+                PipelineStage::GreedyRlf => greedy_rlf(inst.clone(), false),
+                PipelineStage::ConflictWeighting { time_budget } => coloring_conflict_weighting(
+                    inst.clone(), &solution, None, None, TimeStoppingCriterion::new(*time_budget)
+                ),
+                PipelineStage::PartialWeighting { time_budget } => coloring_partial_weighting(
+                    inst.clone(), &solution, None, None, TimeStoppingCriterion::new(*time_budget)
+                ),
+                PipelineStage::PartialWeightingFixClique { time_budget } => match inst.clique() {
+                    Some(clique) => coloring_partial_weighting_with_fixed_clique(
+                        inst.clone(), &solution, &[], &clique, None, None, TimeStoppingCriterion::new(*time_budget)
+                    ),
+                    None => coloring_partial_weighting(
+                        inst.clone(), &solution, None, None, TimeStoppingCriterion::new(*time_budget)
+                    ),
+                },
+            };
+            report.push(PipelineStepReport {
+                stage: stage.clone(),
+                nb_colors: solution.len(),
+                elapsed: start.elapsed().as_secs_f32(),
+            });
+        }
+        (solution, report)
+    }
+}
+
+/// one phase of [`solve`]'s fixed clique-then-color strategy
+#[derive(Clone,Debug,Serialize,Deserialize)]
+pub enum SolvePhase {
+    /// graph-reduction preprocessing (peeling low-degree and dominated vertices)
+    Preprocess,
+    /// greedy clique search, improved by partial-weighting local search, whose size is
+    /// reported as a chromatic lower bound (the remaining phases do not use it)
+    Clique,
+    /// best of DSATUR and RLF construction on the reduced instance
+    Construction,
+    /// conflict-weighting local search
+    ConflictWeighting,
+    /// partial-weighting local search
+    PartialWeighting,
+}
+
+/// one line of a [`solve`] execution report
+#[derive(Clone,Debug,Serialize,Deserialize)]
+pub struct SolveStepReport {
+    /// the phase that produced this value
+    pub phase: SolvePhase,
+    /// number of colors after this phase ([`SolvePhase::Clique`] reports the clique size instead)
+    pub value: usize,
+    /// wall-clock time spent in this phase, in seconds
+    pub elapsed: f32,
+}
+
+/** hybrid clique-then-color pipeline: chains graph-reduction preprocessing, a greedy clique
+(improved by partial-weighting local search) whose size is reported as a chromatic lower
+bound, the better of
+DSATUR and RLF construction, conflict-weighting local search and partial-weighting local
+search, run in that order. `time_budget` is
+the overall wall-clock budget in seconds; `clique_fraction`, `conflict_weighting_fraction` and
+`partial_weighting_fraction` give each of those three phases its share of it (preprocessing
+and construction run to completion unconditionally, since both are fast and deterministic). */
+pub fn solve(
+    inst:Rc<dyn ColoringInstance>,
+    time_budget:f32,
+    clique_fraction:f32,
+    conflict_weighting_fraction:f32,
+    partial_weighting_fraction:f32,
+) -> (Solution, Vec<SolveStepReport>) {
+    let mut report = Vec::new();
+
+    let start = Instant::now();
+    let (reduced, plan) = reduce(inst.clone());
+    report.push(SolveStepReport {
+        phase: SolvePhase::Preprocess, value: reduced.nb_vertices(), elapsed: start.elapsed().as_secs_f32(),
+    });
+
+    let start = Instant::now();
+    let greedy = greedy_clique(reduced.clone());
+    let improved_clique = clique_partial_weighting(
+        reduced.clone(), &greedy, None, None, TimeStoppingCriterion::new(time_budget * clique_fraction)
+    ).remove(0);
+    report.push(SolveStepReport {
+        phase: SolvePhase::Clique, value: improved_clique.len(), elapsed: start.elapsed().as_secs_f32(),
+    });
+
+    let start = Instant::now();
+    let dsatur = greedy_dsatur(reduced.clone(), false);
+    let rlf = greedy_rlf(reduced.clone(), false);
+    let mut solution = if dsatur.len() <= rlf.len() { dsatur } else { rlf };
+    report.push(SolveStepReport {
+        phase: SolvePhase::Construction, value: solution.len(), elapsed: start.elapsed().as_secs_f32(),
+    });
+
+    let start = Instant::now();
+    solution = coloring_conflict_weighting(
+        reduced.clone(), &solution, None, None, TimeStoppingCriterion::new(time_budget * conflict_weighting_fraction)
+    );
+    report.push(SolveStepReport {
+        phase: SolvePhase::ConflictWeighting, value: solution.len(), elapsed: start.elapsed().as_secs_f32(),
+    });
+
+    let start = Instant::now();
+    solution = coloring_partial_weighting(
+        reduced.clone(), &solution, None, None, TimeStoppingCriterion::new(time_budget * partial_weighting_fraction)
+    );
+    report.push(SolveStepReport {
+        phase: SolvePhase::PartialWeighting, value: solution.len(), elapsed: start.elapsed().as_secs_f32(),
+    });
+
+    let solution = expand_solution(&solution, &plan, inst.as_ref());
+    (solution, report)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dimacs::DimacsInstance;
+
+    #[test]
+    fn test_pipeline_roundtrip_json() {
+        let pipeline = Pipeline {
+            stages: vec![
+                PipelineStage::GreedyDsatur,
+                PipelineStage::ConflictWeighting { time_budget: 1.0 },
+            ],
+        };
+        let filename = "tmp/test_pipeline.json";
+        pipeline.save(filename);
+        let reloaded = Pipeline::load(filename);
+        assert_eq!(reloaded.stages.len(), 2);
+    }
+
+    #[test]
+    fn test_pipeline_run_grid() {
+        let inst:Rc<dyn ColoringInstance> = Rc::new(DimacsInstance::from_file("insts/grid-instances/grid2x2"));
+        let pipeline = Pipeline { stages: vec![PipelineStage::GreedyDsatur] };
+        let (solution, report) = pipeline.run(inst);
+        assert_eq!(report.len(), 1);
+        assert!(!solution.is_empty());
+    }
+
+    #[test]
+    fn test_solve_reaches_a_valid_solution_on_grid() {
+        use crate::color::{checker, CheckerResult};
+        let inst:Rc<dyn ColoringInstance> = Rc::new(DimacsInstance::from_file("insts/grid-instances/grid2x2"));
+        let (solution, report) = solve(inst.clone(), 2.0, 0.2, 0.4, 0.4);
+        assert_eq!(report.len(), 5);
+        assert_eq!(checker(inst, &solution), CheckerResult::Ok(solution.len()));
+    }
+
+    #[test]
+    fn test_pipeline_run_partial_weighting_fix_clique_without_clique_falls_back() {
+        // grid2x2 exposes no clique() (DimacsInstance's default), so this stage must behave
+        // exactly like a plain PartialWeighting stage instead of failing
+        let inst:Rc<dyn ColoringInstance> = Rc::new(DimacsInstance::from_file("insts/grid-instances/grid2x2"));
+        let pipeline = Pipeline {
+            stages: vec![
+                PipelineStage::GreedyDsatur,
+                PipelineStage::PartialWeightingFixClique { time_budget: 1.0 },
+            ],
+        };
+        let (solution, report) = pipeline.run(inst);
+        assert_eq!(report.len(), 2);
+        assert!(!solution.is_empty());
+    }
+}
@@ -0,0 +1,73 @@
+//! cheap local clean-up passes applied to a finished coloring before it is checked, exported or
+//! reported, as opposed to [`crate::reduce`]'s pre-processing on the other side of a run.
+
+use std::rc::Rc;
+
+use crate::color::{ColoringInstance, Solution, VertexId};
+use crate::solvers::coloring::merge::merge_color_classes;
+
+/** cleans up `solution` once a solver is done with it: first greedily merges pairs of color
+classes that have no edge between them (see [`merge_color_classes`] — a local search that only
+ever moves one vertex at a time can leave two whole classes mergeable without ever finding the
+move itself), then greedily moves every vertex down into the lowest-index class it can join
+without conflict, and finally drops whatever classes that leaves empty, so the exported
+coloring never reports more colors, or skips a color index, than its actual class count. Pure:
+returns a new [`Solution`], never mutates `solution` or `inst`. */
+pub fn compact_coloring(inst:&Rc<dyn ColoringInstance>, solution:&[Vec<VertexId>]) -> Solution {
+    let merged = merge_color_classes(inst, solution.to_vec());
+    let moved = move_to_lowest_class(inst, merged);
+    moved.into_iter().filter(|class| !class.is_empty()).collect()
+}
+
+/// greedily moves every vertex into the lowest-index class it can join without conflict,
+/// tending to empty out the highest-index classes first (finished off by
+/// [`compact_coloring`]'s final dense relabeling)
+fn move_to_lowest_class(inst:&Rc<dyn ColoringInstance>, mut classes:Solution) -> Solution {
+    let mut colors = vec![0usize ; inst.nb_vertices()];
+    for (c, class) in classes.iter().enumerate() {
+        for &v in class { colors[v] = c; }
+    }
+    for v in inst.vertices() {
+        let current = colors[v];
+        let neighbor_colors:Vec<usize> = inst.neighbors(v).iter().map(|&w| colors[w]).collect();
+        if let Some(target) = (0..current).find(|c| !neighbor_colors.contains(c)) {
+            classes[current].retain(|&u| u != v);
+            classes[target].push(v);
+            colors[v] = target;
+        }
+    }
+    classes
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::dimacs::DimacsInstance;
+
+    #[test]
+    fn test_compact_coloring_merges_disjoint_classes_and_drops_empties() {
+        // path 0-1-2: a valid but wasteful 3-class coloring where classes 0 and 2 (both just
+        // {0} and {2}) share no edge and should merge, leaving 2 colors
+        let inst:Rc<dyn ColoringInstance> = Rc::new(DimacsInstance::new(
+            vec![vec![1], vec![0, 2], vec![1]]
+        ));
+        let solution = vec![vec![0], vec![1], vec![2]];
+        let compacted = compact_coloring(&inst, &solution);
+        assert_eq!(compacted.len(), 2);
+        assert_eq!(crate::color::checker(inst, &compacted), crate::color::CheckerResult::Ok(2));
+    }
+
+    #[test]
+    fn test_compact_coloring_moves_vertex_to_lower_class() {
+        // edge 0-1, vertex 2 isolated; vertex 2 starts in class 1 even though it has no
+        // conflict with class 0, so it should end up moved there
+        let inst:Rc<dyn ColoringInstance> = Rc::new(DimacsInstance::new(
+            vec![vec![1], vec![0], vec![]]
+        ));
+        let solution = vec![vec![0], vec![1, 2]];
+        let compacted = compact_coloring(&inst, &solution);
+        assert_eq!(crate::color::checker(inst, &compacted), crate::color::CheckerResult::Ok(compacted.len()));
+        assert!(compacted.iter().any(|c| c.contains(&0) && c.contains(&2)));
+    }
+}
@@ -0,0 +1,87 @@
+//! wraps a [`ColoringInstance`] to add per-vertex allowed-color-set (list-coloring) or fixed
+//! (precoloring) constraints via [`ColoringInstance::allowed_colors`], respected by
+//! [`crate::solvers::coloring::greedy_dsatur::greedy_dsatur`],
+//! [`crate::solvers::coloring::conflict_weighting`] and
+//! [`crate::solvers::coloring::partial_weighting`] during neighbor generation, so a solution
+//! produced over a [`PrecoloredInstance`] never assigns a vertex a color outside the list
+//! allowed for it.
+
+use std::rc::Rc;
+
+use bit_set::BitSet;
+
+use crate::color::{ColoringInstance, Solution, VertexId};
+
+/** a [`ColoringInstance`] that delegates every method to a wrapped instance, except
+[`ColoringInstance::allowed_colors`], which returns the per-vertex allowed-color set recorded
+by [`PrecoloredInstance::restrict`]/[`PrecoloredInstance::fix`] (`None` for a vertex with no
+entry, meaning unconstrained, same as the trait's default). Precoloring a vertex (pinning it
+to a single, fixed color) is just the degenerate, singleton case of restricting its list. */
+#[derive(Debug)]
+pub struct PrecoloredInstance {
+    inst: Rc<dyn ColoringInstance>,
+    allowed: Vec<Option<BitSet>>,
+}
+
+impl PrecoloredInstance {
+    /// wraps `inst` with no constraint on any vertex; add some with
+    /// [`PrecoloredInstance::restrict`] and [`PrecoloredInstance::fix`]
+    pub fn new(inst:Rc<dyn ColoringInstance>) -> Self {
+        let n = inst.nb_vertices();
+        Self { inst, allowed: vec![None ; n] }
+    }
+
+    /// restricts `u` to `colors`: it may never be assigned a color outside this set
+    pub fn restrict(&mut self, u:VertexId, colors:BitSet) {
+        self.allowed[u] = Some(colors);
+    }
+
+    /// pins `u` to exactly `c` (precoloring), the degenerate singleton case of
+    /// [`PrecoloredInstance::restrict`]
+    pub fn fix(&mut self, u:VertexId, c:usize) {
+        let mut singleton = BitSet::new();
+        singleton.insert(c);
+        self.restrict(u, singleton);
+    }
+}
+
+impl ColoringInstance for PrecoloredInstance {
+    fn nb_vertices(&self) -> usize { self.inst.nb_vertices() }
+    fn degree(&self, u:VertexId) -> usize { self.inst.degree(u) }
+    fn neighbors(&self, u:VertexId) -> Vec<VertexId> { self.inst.neighbors(u) }
+    fn are_adjacent(&self, u:VertexId, v:VertexId) -> bool { self.inst.are_adjacent(u, v) }
+    fn display_statistics(&self) { self.inst.display_statistics() }
+    fn write_solution(&self, filename:&str, solution:&[Vec<usize>]) { self.inst.write_solution(filename, solution) }
+    fn edges(&self) -> &[(VertexId, VertexId)] { self.inst.edges() }
+    fn vertices(&self) -> Box<dyn Iterator<Item=VertexId>> { self.inst.vertices() }
+    fn is_dominated(&self, u:VertexId) -> bool { self.inst.is_dominated(u) }
+    fn original_id(&self, u:VertexId) -> VertexId { self.inst.original_id(u) }
+    fn weight(&self, u:VertexId) -> usize { self.inst.weight(u) }
+    fn count_neighbors_in_classes(&self, u:VertexId, classes:&[BitSet]) -> Vec<usize> {
+        self.inst.count_neighbors_in_classes(u, classes)
+    }
+    fn coloring(&self) -> Option<Solution> { self.inst.coloring() }
+    fn clique(&self) -> Option<Vec<VertexId>> { self.inst.clique() }
+    fn complementary(&self) -> Rc<dyn ColoringInstance> { self.inst.complementary() }
+
+    fn allowed_colors(&self, u:VertexId) -> Option<&BitSet> { self.allowed[u].as_ref() }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::dimacs::DimacsInstance;
+
+    #[test]
+    fn test_precolored_instance_delegates_and_restricts() {
+        let inst = Rc::new(DimacsInstance::from_file("insts/grid-instances/grid2x2"));
+        let mut precolored = PrecoloredInstance::new(inst.clone());
+        assert_eq!(precolored.nb_vertices(), inst.nb_vertices());
+        assert!(precolored.allowed_colors(0).is_none());
+        precolored.fix(0, 2);
+        let allowed = precolored.allowed_colors(0).unwrap();
+        assert!(allowed.contains(2));
+        assert_eq!(allowed.len(), 1);
+    }
+}
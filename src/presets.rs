@@ -0,0 +1,75 @@
+//! named benchmark presets: solver choice and tuning parameters measured to work well on a
+//! given instance family (dense/sparse DIMACS, the various CGSHOP point-set families), so
+//! this institutional knowledge lives in one place instead of scattered across commented-out
+//! test file lists. Presets are [`TuningProfile`]s plus a solver choice, selectable by name
+//! and recorded alongside results so a run can always be traced back to the settings that
+//! produced it.
+
+use serde::{Deserialize, Serialize};
+
+use crate::profiles::TuningProfile;
+
+/// a named bundle of solver choice and tuning parameters for a given instance family
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct BenchmarkPreset {
+    /// preset name (e.g. "cgshop-visp"), recorded in exports alongside results
+    pub name: String,
+    /// which local search the preset was tuned for ("conflict_weighting" or "partial_weighting")
+    pub solver: String,
+    /// tuning parameters for the chosen solver
+    pub profile: TuningProfile,
+}
+
+/// returns the built-in preset registered under `name`, if any
+pub fn preset(name:&str) -> Option<BenchmarkPreset> {
+    built_in_presets().into_iter().find(|p| p.name == name)
+}
+
+/// the built-in presets, encoding current institutional knowledge about which solver and
+/// parameters work well on each instance family
+fn built_in_presets() -> Vec<BenchmarkPreset> {
+    vec![
+        BenchmarkPreset {
+            name: "dimacs-dense".to_string(),
+            solver: "conflict_weighting".to_string(),
+            profile: TuningProfile { tabu_l: 10, tabu_lambda: 0.6, guide: "weight".to_string() },
+        },
+        BenchmarkPreset {
+            name: "dimacs-sparse".to_string(),
+            solver: "partial_weighting".to_string(),
+            profile: TuningProfile { tabu_l: 6, tabu_lambda: 0.4, guide: "conflicts_first".to_string() },
+        },
+        BenchmarkPreset {
+            name: "cgshop-visp".to_string(),
+            solver: "conflict_weighting".to_string(),
+            profile: TuningProfile { tabu_l: 15, tabu_lambda: 0.8, guide: "conflicts_first".to_string() },
+        },
+        BenchmarkPreset {
+            name: "cgshop-sqrp".to_string(),
+            solver: "partial_weighting".to_string(),
+            profile: TuningProfile { tabu_l: 12, tabu_lambda: 0.7, guide: "weight".to_string() },
+        },
+        BenchmarkPreset {
+            name: "cgshop-reecn".to_string(),
+            solver: "conflict_weighting".to_string(),
+            profile: TuningProfile { tabu_l: 8, tabu_lambda: 0.5, guide: "weight".to_string() },
+        },
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_known_preset() {
+        let p = preset("cgshop-visp").expect("cgshop-visp should be a built-in preset");
+        assert_eq!(p.solver, "conflict_weighting");
+        assert_eq!(p.profile.tabu_l, 15);
+    }
+
+    #[test]
+    fn test_unknown_preset() {
+        assert!(preset("not-a-real-preset").is_none());
+    }
+}
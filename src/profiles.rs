@@ -0,0 +1,80 @@
+//! persisted per-instance (or per-instance-family) solver tuning profiles.
+//! Lets repeated experimentation on the same CGSHOP/DIMACS families converge to good
+//! settings without manual bookkeeping: a profile found to work well on an instance
+//! (or family) is saved once, and subsequent runs load it instead of using hard-coded
+//! defaults.
+
+use std::collections::HashMap;
+use std::fs;
+
+use serde::{Deserialize, Serialize};
+
+/// tunable solver parameters that can be tied to an instance (or family) and persisted
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct TuningProfile {
+    /// tabu tenure fixed size (`l` in `TabuColTenure::new`)
+    pub tabu_l: usize,
+    /// tabu tenure dynamic factor (`lambda` in `TabuColTenure::new`)
+    pub tabu_lambda: f64,
+    /// name of the guide strategy to use (e.g. "weight", "conflicts_first")
+    pub guide: String,
+}
+
+impl Default for TuningProfile {
+    fn default() -> Self {
+        Self { tabu_l: 10, tabu_lambda: 0.6, guide: "weight".to_string() }
+    }
+}
+
+/// a collection of tuning profiles, keyed by instance name or instance family name,
+/// persisted as a single JSON file
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct TuningProfiles {
+    /// profiles[key]: tuning profile for the instance or family named `key`
+    profiles: HashMap<String, TuningProfile>,
+}
+
+impl TuningProfiles {
+    /// loads a profiles file, returning an empty collection if it does not exist yet
+    pub fn load(filename:&str) -> Self {
+        match fs::read_to_string(filename) {
+            Err(_) => Self::default(),
+            Ok(content) => serde_json::from_str(&content)
+                .unwrap_or_else(|why| panic!("TuningProfiles::load: unable to parse {}: {}", filename, why)),
+        }
+    }
+
+    /// writes the profiles collection to disk as JSON
+    pub fn save(&self, filename:&str) {
+        let content = serde_json::to_string_pretty(self).unwrap();
+        fs::write(filename, content)
+            .unwrap_or_else(|why| panic!("TuningProfiles::save: unable to write {}: {}", filename, why));
+    }
+
+    /// returns the profile registered for `key` (instance name or family name),
+    /// falling back to the default profile if none is registered
+    pub fn get(&self, key:&str) -> TuningProfile {
+        self.profiles.get(key).cloned().unwrap_or_default()
+    }
+
+    /// registers (or overwrites) the profile for `key`
+    pub fn set(&mut self, key:&str, profile:TuningProfile) {
+        self.profiles.insert(key.to_string(), profile);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_roundtrip() {
+        let mut profiles = TuningProfiles::default();
+        profiles.set("cgshop-visp", TuningProfile { tabu_l: 15, tabu_lambda: 0.8, guide: "conflicts_first".to_string() });
+        let filename = "tmp/test_profiles.json";
+        profiles.save(filename);
+        let reloaded = TuningProfiles::load(filename);
+        assert_eq!(reloaded.get("cgshop-visp").tabu_l, 15);
+        assert_eq!(reloaded.get("unknown-instance").tabu_l, TuningProfile::default().tabu_l);
+    }
+}
@@ -0,0 +1,258 @@
+//! graph-reduction preprocessing: removes vertices a solver does not need to decide on at
+//! all, because a safe color for them can always be derived once the rest of the graph is
+//! colored. Three kinds of vertex qualify: dominated vertices (`u` dominates `v` when `u` is
+//! not adjacent to `v` and every neighbor of `v` is also a neighbor of `u`, so `v` can always
+//! reuse `u`'s color — the same notion [`crate::cgshop::CGSHOPInstance`] already precomputes
+//! for its own `is_dominated`/`display_statistics`, but generically here, over any
+//! [`ColoringInstance`]), low-degree vertices (fewer than the current chromatic lower bound
+//! `k`, so at least one of the `k` colors is always free for them once their neighbors are
+//! colored), and isolated vertices (degree 0, trivially colorable). [`reduce`] collapses them
+//! into a smaller [`ReducedInstance`] to solve instead, the same way
+//! [`crate::twins::collapse`] collapses twin groups; [`lift_solution`] and
+//! [`expand_solution`] together restore a solution found on it back to the original vertex set.
+//!
+//! See also [`crate::color::preprocess`], an equivalent reduction that derives its lower
+//! bound from [`ColoringInstance::clique`] (falling back to [`crate::solvers::clique::greedy_clique`])
+//! instead of taking one as a parameter, and folds isolated vertices into its low-degree case
+//! rather than tracking them as their own removal reason.
+
+use std::collections::VecDeque;
+use std::rc::Rc;
+
+use bit_set::BitSet;
+
+use crate::color::{ColoringInstance, Solution, VertexId};
+
+/// why [`reduce_vertex_set`] removed a vertex, and what its restored color should be derived
+/// from
+#[derive(Clone,Copy,Debug,PartialEq,Eq)]
+pub enum RemovalReason {
+    /// not adjacent to, and dominated by, `dominator`: safe to reuse its color
+    Dominated { dominator:VertexId },
+    /// no neighbors left among the vertices still present when it was removed
+    Isolated,
+    /// fewer neighbors than the lower bound among the vertices still present when it was
+    /// removed: some color is always free
+    LowDegree,
+}
+
+/// a vertex peeled off by [`reduce_vertex_set`], in the order it was removed (earliest first)
+#[derive(Clone,Debug)]
+pub struct RemovedVertex {
+    /// the removed vertex, in the original instance's id space
+    pub vertex:VertexId,
+    /// its neighbors that were still present at the time it was removed: by construction any
+    /// of these either survived into the kept vertex set, or were themselves removed later,
+    /// so [`expand_solution`] can always resolve their colors first (see its docs)
+    pub neighbors:Vec<VertexId>,
+    /// why it could safely be removed
+    pub reason:RemovalReason,
+}
+
+/// `w` dominates `v`: not adjacent, and every neighbor of `v` is also a neighbor of `w`
+fn dominates(w:VertexId, v:VertexId, open_neighbors:&[BitSet]) -> bool {
+    w != v && !open_neighbors[v].contains(w) && open_neighbors[v].iter().all(|u| open_neighbors[w].contains(u))
+}
+
+/** peels vertices off `inst` that a solver never needs to decide on directly: first, in one
+linear pass, every vertex with at least one neighbor that is dominated by a vertex still
+present at the time it is examined (see [`dominates`]); then, to a fixpoint via a queue (removing a vertex can push its
+neighbors below the threshold too), every vertex whose degree among the vertices still
+present is `0` or below `lower_bound`. `lower_bound` is typically a clique size found by a
+clique solver (see [`crate::solvers::clique`](crate::solvers::clique)); pass `0` to skip the
+degree-based pass and only remove dominated and isolated vertices. Returns the vertices kept
+for solving and the peeled vertices in removal order, needed to restore a solution afterwards
+(see [`expand_solution`]). */
+pub fn reduce_vertex_set(inst:&dyn ColoringInstance, lower_bound:usize) -> (Vec<VertexId>, Vec<RemovedVertex>) {
+    let n = inst.nb_vertices();
+    let open_neighbors:Vec<BitSet> = (0..n).map(|u| {
+        let mut b = BitSet::new();
+        for v in inst.neighbors(u) { b.insert(v); }
+        b
+    }).collect();
+    let mut present = BitSet::with_capacity(n);
+    for u in 0..n { present.insert(u); }
+    let mut removed = Vec::new();
+
+    // pass 1: domination, one linear sweep (a dominator found for `v` might itself later be
+    // found dominated by someone else still present; since it stays present at `v`'s removal
+    // time, it is necessarily removed later than `v`, preserving expand_solution's invariant)
+    for v in 0..n {
+        // an isolated vertex is vacuously dominated by every other vertex: leave it to pass 2,
+        // which records it as `Isolated`
+        if !present.contains(v) || open_neighbors[v].is_empty() { continue; }
+        if let Some(dominator) = (0..n).find(|&w| present.contains(w) && dominates(w, v, &open_neighbors)) {
+            let neighbors = open_neighbors[v].iter().filter(|u| present.contains(*u)).collect();
+            present.remove(v);
+            removed.push(RemovedVertex { vertex:v, neighbors, reason:RemovalReason::Dominated { dominator } });
+        }
+    }
+
+    // pass 2: low-degree and isolated vertices, peeled to a fixpoint with a queue since
+    // removing one can push its still-present neighbors below the threshold too
+    let mut remaining_degree:Vec<usize> = (0..n)
+        .map(|u| open_neighbors[u].iter().filter(|v| present.contains(*v)).count())
+        .collect();
+    let is_peelable = |degree:usize| degree == 0 || degree < lower_bound;
+    let mut queued = BitSet::with_capacity(n);
+    let mut queue:VecDeque<VertexId> = VecDeque::new();
+    for u in 0..n {
+        if present.contains(u) && is_peelable(remaining_degree[u]) {
+            queue.push_back(u);
+            queued.insert(u);
+        }
+    }
+    while let Some(u) = queue.pop_front() {
+        let reason = if remaining_degree[u] == 0 { RemovalReason::Isolated } else { RemovalReason::LowDegree };
+        let neighbors:Vec<VertexId> = open_neighbors[u].iter().filter(|v| present.contains(*v)).collect();
+        present.remove(u);
+        removed.push(RemovedVertex { vertex:u, neighbors:neighbors.clone(), reason });
+        for v in neighbors {
+            if present.contains(v) {
+                remaining_degree[v] -= 1;
+                if is_peelable(remaining_degree[v]) && !queued.contains(v) {
+                    queued.insert(v);
+                    queue.push_back(v);
+                }
+            }
+        }
+    }
+
+    let kept:Vec<VertexId> = (0..n).filter(|u| present.contains(*u)).collect();
+    (kept, removed)
+}
+
+/** a view of `base` restricted to the vertices [`reduce_vertex_set`] kept, re-indexed to
+`0..kept.len()` the same way [`crate::twins::TwinCollapsedInstance`] re-indexes collapsed twin
+groups. Solve on this instance, then lift the solution back through `kept` with
+[`lift_solution`] and restore the peeled vertices with [`expand_solution`]. */
+#[derive(Debug)]
+pub struct ReducedInstance {
+    base:Rc<dyn ColoringInstance>,
+    /// kept[reduced_id] = corresponding vertex id in `base`
+    kept:Vec<VertexId>,
+    edges:Vec<(VertexId, VertexId)>,
+}
+
+impl ColoringInstance for ReducedInstance {
+    fn nb_vertices(&self) -> usize { self.kept.len() }
+
+    fn degree(&self, u:VertexId) -> usize { self.neighbors(u).len() }
+
+    fn neighbors(&self, u:VertexId) -> Vec<VertexId> {
+        (0..self.kept.len()).filter(|v| *v != u && self.are_adjacent(u, *v)).collect()
+    }
+
+    fn are_adjacent(&self, u:VertexId, v:VertexId) -> bool {
+        self.base.are_adjacent(self.kept[u], self.kept[v])
+    }
+
+    fn display_statistics(&self) {
+        println!("\treduced view: {} / {} vertices kept", self.kept.len(), self.base.nb_vertices());
+    }
+
+    fn write_solution(&self, filename:&str, solution:&[Vec<usize>]) {
+        let mapped = crate::color::original_ids(self, solution);
+        self.base.write_solution(filename, &mapped);
+    }
+
+    fn edges(&self) -> &[(VertexId, VertexId)] { &self.edges }
+
+    fn original_id(&self, u:VertexId) -> VertexId {
+        self.base.original_id(self.kept[u])
+    }
+}
+
+/// reduces `inst` to a smaller [`ReducedInstance`], returning it alongside the vertices
+/// [`reduce_vertex_set`] peeled off, needed to expand solutions found on it back to `inst`'s
+/// full vertex set (see [`lift_solution`] and [`expand_solution`])
+pub fn reduce(inst:Rc<dyn ColoringInstance>, lower_bound:usize) -> (Rc<dyn ColoringInstance>, Vec<RemovedVertex>) {
+    let (kept, removed) = reduce_vertex_set(inst.as_ref(), lower_bound);
+    let mut edges = Vec::new();
+    for i in 0..kept.len() {
+        for j in (i + 1)..kept.len() {
+            if inst.are_adjacent(kept[i], kept[j]) {
+                edges.push((i, j));
+            }
+        }
+    }
+    (Rc::new(ReducedInstance { base:inst, kept:kept.clone(), edges }), removed)
+}
+
+/// remaps a solution found on a [`ReducedInstance`] back to the original vertex ids of the
+/// `kept` vertices it was built from (does not yet restore the removed vertices: use
+/// [`expand_solution`] for that)
+pub fn lift_solution(reduced_solution:&Solution, kept:&[VertexId]) -> Solution {
+    reduced_solution.iter().map(|c| c.iter().map(|v| kept[*v]).collect()).collect()
+}
+
+/** restores a solution already expressed in `inst`'s original vertex ids (see
+[`lift_solution`]) by walking `removed` in reverse removal order: for every removed vertex,
+everything it depends on (its recorded `neighbors`, or its dominator) already has a color by
+then, either because it was kept and solved directly, or because it was removed later and so
+is processed earlier in this reverse walk (see [`RemovedVertex::neighbors`]). Dominated
+vertices reuse their dominator's color class directly; low-degree and isolated vertices get
+the first color class not already used by their recorded neighbors, growing the solution with
+a new singleton class if every existing one is taken. */
+pub fn expand_solution(solution:&Solution, removed:&[RemovedVertex]) -> Solution {
+    let mut res = solution.to_vec();
+    for r in removed.iter().rev() {
+        match r.reason {
+            RemovalReason::Dominated { dominator } => {
+                let class = res.iter().position(|c| c.contains(&dominator))
+                    .unwrap_or_else(|| panic!("expand_solution: dominator {} of vertex {} has no color yet", dominator, r.vertex));
+                res[class].push(r.vertex);
+            }
+            RemovalReason::Isolated | RemovalReason::LowDegree => {
+                let neighbor_classes:BitSet = r.neighbors.iter()
+                    .filter_map(|v| res.iter().position(|c| c.contains(v)))
+                    .collect();
+                match (0..res.len()).find(|c| !neighbor_classes.contains(*c)) {
+                    Some(class) => res[class].push(r.vertex),
+                    None => res.push(vec![r.vertex]),
+                }
+            }
+        }
+    }
+    res
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::color::{checker, CheckerResult};
+    use crate::dimacs::DimacsInstance;
+    use crate::solvers::coloring::greedy_dsatur::greedy_dsatur;
+
+    #[test]
+    fn test_reduce_vertex_set_removes_dominated_isolated_and_low_degree_vertices() {
+        // 0 is isolated; 1-2-3 form a triangle (degree 2 each, below lower_bound 3); 4 and 5
+        // are both adjacent only to 6, so 5 is dominated by 4 (and vice versa, whichever the
+        // sweep reaches first)
+        let inst = DimacsInstance::new(vec![
+            vec![],              // 0: isolated
+            vec![2, 3],          // 1
+            vec![1, 3],          // 2
+            vec![1, 2],          // 3
+            vec![6],             // 4
+            vec![6],             // 5
+            vec![4, 5],          // 6
+        ]);
+        let (kept, removed) = reduce_vertex_set(&inst, 3);
+        assert!(!kept.contains(&0));
+        assert!(removed.iter().any(|r| r.vertex == 0 && r.reason == RemovalReason::Isolated));
+        assert!(removed.iter().any(|r| matches!(r.reason, RemovalReason::Dominated { .. }) && (r.vertex == 4 || r.vertex == 5)));
+    }
+
+    #[test]
+    fn test_reduce_and_expand_roundtrip() {
+        let inst:Rc<dyn ColoringInstance> = Rc::new(DimacsInstance::from_file("insts/grid-instances/grid2x2"));
+        let (kept, _) = reduce_vertex_set(inst.as_ref(), 2);
+        let (reduced, removed) = reduce(inst.clone(), 2);
+        let reduced_sol = greedy_dsatur(reduced.clone(), false);
+        let lifted = lift_solution(&reduced_sol, &kept);
+        let expanded = expand_solution(&lifted, &removed);
+        assert_eq!(checker(inst, &expanded), CheckerResult::Ok(expanded.len()));
+    }
+}
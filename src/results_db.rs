@@ -0,0 +1,188 @@
+//! optional SQLite-backed results store (behind the `results_db` feature): complements
+//! [`crate::util::RunSummary::append_csv`] for longitudinal experiment tracking across many
+//! runs and machines, without grepping perf JSON files scattered across directories. Each
+//! call to [`ResultsDb::record`] appends one row describing a single solver run; [`ResultsDb`]
+//! also exposes the handful of queries ("what's the best known result for this instance?",
+//! "what has been tried on it so far?") that motivated keeping the history in the first place.
+
+use rusqlite::{params, Connection, OptionalExtension, Result as SqliteResult};
+
+use crate::util::RunSummary;
+
+/// one solver run to record into a [`ResultsDb`], pairing the bookkeeping an [`RunSummary`]
+/// doesn't carry (an instance identity stable across renames/relocations, the solver name and
+/// configuration, the random seed) with the summary itself
+#[derive(Clone, Debug)]
+pub struct RunRecord {
+    /// identifies the instance independently of its file path (e.g. a content hash), so
+    /// renamed or relocated copies of the same instance still group together in queries
+    pub instance_hash: String,
+    /// name of the solver/algorithm used (e.g. "partial_weighting")
+    pub solver: String,
+    /// free-form description of the solver configuration (parameters, policies, ...)
+    pub config: String,
+    /// random seed used for this run, if the solver is randomized
+    pub seed: Option<u64>,
+    /// the run's outcome (colors, clique bound, timings), shared with
+    /// [`RunSummary::append_csv`] so both sinks describe a run the same way
+    pub summary: RunSummary,
+}
+
+/// a single row of run history, as returned by [`ResultsDb::history_for_instance`]
+#[derive(Clone, Debug)]
+pub struct RunRecordRow {
+    /// name of the solver/algorithm used
+    pub solver: String,
+    /// free-form description of the solver configuration
+    pub config: String,
+    /// random seed used for this run, if any
+    pub seed: Option<u64>,
+    /// number of colors of the best coloring found, if any
+    pub best_colors: Option<usize>,
+    /// size of the best clique found, if any
+    pub best_clique: Option<usize>,
+    /// time (seconds) at which the best solution was found
+    pub time_to_best: f32,
+    /// total time (seconds) spent solving
+    pub total_time: f32,
+    /// timestamp (SQLite `datetime('now')` format) at which the row was inserted
+    pub recorded_at: String,
+}
+
+/// a local SQLite file of [`RunRecord`] rows, for longitudinal experiment tracking
+#[derive(Debug)]
+pub struct ResultsDb {
+    conn: Connection,
+}
+
+impl ResultsDb {
+    /// opens (creating if needed) a results database at `filename`, creating the `runs` table
+    /// if it does not already exist
+    pub fn open(filename:&str) -> SqliteResult<Self> {
+        let conn = Connection::open(filename)?;
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS runs (
+                id              INTEGER PRIMARY KEY AUTOINCREMENT,
+                instance        TEXT NOT NULL,
+                instance_hash   TEXT NOT NULL,
+                solver          TEXT NOT NULL,
+                config          TEXT NOT NULL,
+                seed            INTEGER,
+                best_colors     INTEGER,
+                best_clique     INTEGER,
+                time_to_best    REAL NOT NULL,
+                total_time      REAL NOT NULL,
+                recorded_at     TEXT NOT NULL DEFAULT (datetime('now'))
+            )",
+            [],
+        )?;
+        Ok(Self { conn })
+    }
+
+    /// appends `record` as a new row
+    pub fn record(&self, record:&RunRecord) -> SqliteResult<()> {
+        self.conn.execute(
+            "INSERT INTO runs
+                (instance, instance_hash, solver, config, seed, best_colors, best_clique, time_to_best, total_time)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9)",
+            params![
+                record.summary.instance,
+                record.instance_hash,
+                record.solver,
+                record.config,
+                record.seed,
+                record.summary.best_colors.map(|c| c as i64),
+                record.summary.best_clique.map(|c| c as i64),
+                record.summary.time_to_best,
+                record.summary.total_time,
+            ],
+        )?;
+        Ok(())
+    }
+
+    /// the solver and color count of the best (fewest colors) run recorded for
+    /// `instance_hash`, if any run recorded a coloring
+    pub fn best_for_instance(&self, instance_hash:&str) -> SqliteResult<Option<(String, usize)>> {
+        self.conn.query_row(
+            "SELECT solver, best_colors FROM runs
+             WHERE instance_hash = ?1 AND best_colors IS NOT NULL
+             ORDER BY best_colors ASC LIMIT 1",
+            params![instance_hash],
+            |row| Ok((row.get(0)?, row.get::<_, i64>(1)? as usize)),
+        ).optional()
+    }
+
+    /// every run recorded for `instance_hash`, most recently recorded first
+    pub fn history_for_instance(&self, instance_hash:&str) -> SqliteResult<Vec<RunRecordRow>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT solver, config, seed, best_colors, best_clique, time_to_best, total_time, recorded_at
+             FROM runs WHERE instance_hash = ?1 ORDER BY id DESC"
+        )?;
+        stmt.query_map(params![instance_hash], |row| {
+            Ok(RunRecordRow {
+                solver: row.get(0)?,
+                config: row.get(1)?,
+                seed: row.get(2)?,
+                best_colors: row.get::<_, Option<i64>>(3)?.map(|c| c as usize),
+                best_clique: row.get::<_, Option<i64>>(4)?.map(|c| c as usize),
+                time_to_best: row.get(5)?,
+                total_time: row.get(6)?,
+                recorded_at: row.get(7)?,
+            })
+        })?.collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample_summary(instance:&str, best_colors:usize) -> RunSummary {
+        RunSummary {
+            instance: instance.to_string(),
+            preset: None,
+            best_colors: Some(best_colors),
+            best_clique: None,
+            iterations: None,
+            restarts: None,
+            time_to_best: 1.0,
+            total_time: 2.0,
+            total_cpu_time: None,
+            sol_file: None,
+            perf_file: None,
+            tag: None,
+        }
+    }
+
+    #[test]
+    fn test_record_and_query_results_db() {
+        let path = std::env::temp_dir().join("dogs_color_test_results_db.sqlite");
+        let _ = std::fs::remove_file(&path);
+        let filename = path.to_str().unwrap();
+        let db = ResultsDb::open(filename).unwrap();
+        db.record(&RunRecord {
+            instance_hash: "hash-a".to_string(),
+            solver: "partial_weighting".to_string(),
+            config: "default".to_string(),
+            seed: Some(42),
+            summary: sample_summary("grid2x2", 3),
+        }).unwrap();
+        db.record(&RunRecord {
+            instance_hash: "hash-a".to_string(),
+            solver: "greedy_dsatur".to_string(),
+            config: "default".to_string(),
+            seed: None,
+            summary: sample_summary("grid2x2", 2),
+        }).unwrap();
+
+        let (best_solver, best_colors) = db.best_for_instance("hash-a").unwrap().unwrap();
+        assert_eq!(best_solver, "greedy_dsatur");
+        assert_eq!(best_colors, 2);
+
+        let history = db.history_for_instance("hash-a").unwrap();
+        assert_eq!(history.len(), 2);
+        assert!(db.best_for_instance("hash-unknown").unwrap().is_none());
+
+        let _ = std::fs::remove_file(&path);
+    }
+}
@@ -0,0 +1,106 @@
+//! packages the common research workflow of alternating short clique (lower bound) and
+//! coloring (upper bound) bursts, watching the gap between them shrink, into one call instead
+//! of hand-wiring [`clique_partial_weighting`] and [`coloring_partial_weighting`] together
+//! every time. Stops automatically once the gap closes (lower bound == upper bound, proving
+//! both optimal) or neither side has improved for `stagnation_rounds` consecutive rounds.
+
+use std::rc::Rc;
+use std::time::Instant;
+
+use dogs::search_algorithm::TimeStoppingCriterion;
+
+use crate::color::{ColoringInstance, Solution, VertexId};
+use crate::solvers::clique::greedy_clique::greedy_clique;
+use crate::solvers::clique::partial_weighting::clique_partial_weighting;
+use crate::solvers::coloring::greedy_dsatur::greedy_dsatur;
+use crate::solvers::coloring::partial_weighting::coloring_partial_weighting;
+
+/// one round of [`tighten_bounds`]'s trace
+#[derive(Clone, Debug)]
+pub struct BoundTighteningRound {
+    /// round number, starting at 0
+    pub round: usize,
+    /// best clique size found so far (lower bound on the chromatic number)
+    pub lower_bound: usize,
+    /// best coloring size found so far (upper bound on the chromatic number)
+    pub upper_bound: usize,
+    /// wall-clock time, in seconds, since [`tighten_bounds`] started, at the end of this round
+    pub elapsed_secs: f32,
+}
+
+/// outcome of [`tighten_bounds`]
+pub struct BoundTighteningReport {
+    /// one entry per round run, in order; `rounds.last()` holds the final gap
+    pub rounds: Vec<BoundTighteningRound>,
+    /// best clique found (its size is the final lower bound)
+    pub clique: Vec<VertexId>,
+    /// best coloring found (its size is the final upper bound)
+    pub coloring: Solution,
+}
+
+/** alternates `burst_secs`-long clique and coloring local-search bursts over `inst`, starting
+both from a cheap greedy construction, and records the lower/upper bound after every round into
+a [`BoundTighteningReport`]. Stops after a round where the gap has closed (`lower_bound ==
+upper_bound`), after `stagnation_rounds` consecutive rounds in which *neither* bound improved,
+or after `max_rounds` regardless, whichever comes first (the last guards against a burst budget
+too short to ever close the gap or visibly stagnate). */
+pub fn tighten_bounds(
+    inst:Rc<dyn ColoringInstance>,
+    burst_secs:f32,
+    stagnation_rounds:usize,
+    max_rounds:usize,
+) -> BoundTighteningReport {
+    let start = Instant::now();
+    let mut clique = greedy_clique(inst.clone());
+    let mut coloring = greedy_dsatur(inst.clone(), false);
+    let mut rounds = Vec::new();
+    let mut stagnant_rounds = 0;
+    for round in 0..max_rounds.max(1) {
+        let improved_clique = clique_partial_weighting(
+            inst.clone(), &clique, None, None, TimeStoppingCriterion::new(burst_secs)
+        ).remove(0);
+        let improved_coloring = coloring_partial_weighting(
+            inst.clone(), &coloring, None, None, TimeStoppingCriterion::new(burst_secs)
+        );
+        let mut improved = false;
+        if improved_clique.len() > clique.len() {
+            clique = improved_clique;
+            improved = true;
+        }
+        if improved_coloring.len() < coloring.len() {
+            coloring = improved_coloring;
+            improved = true;
+        }
+        rounds.push(BoundTighteningRound {
+            round,
+            lower_bound: clique.len(),
+            upper_bound: coloring.len(),
+            elapsed_secs: start.elapsed().as_secs_f32(),
+        });
+        if clique.len() == coloring.len() {
+            break;
+        }
+        stagnant_rounds = if improved { 0 } else { stagnant_rounds + 1 };
+        if stagnant_rounds >= stagnation_rounds {
+            break;
+        }
+    }
+    BoundTighteningReport { rounds, clique, coloring }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::dimacs::DimacsInstance;
+
+    #[test]
+    fn test_tighten_bounds_closes_gap_on_grid2x2() {
+        // grid2x2 is a 4-cycle: clique number 2, chromatic number 2, so the gap must close
+        let inst:Rc<dyn ColoringInstance> = Rc::new(DimacsInstance::from_file("insts/grid-instances/grid2x2"));
+        let report = tighten_bounds(inst, 0.2, 3, 10);
+        let last = report.rounds.last().unwrap();
+        assert_eq!(last.lower_bound, last.upper_bound);
+        assert_eq!(report.clique.len(), report.coloring.len());
+    }
+}
@@ -0,0 +1,86 @@
+//! transfers a coloring computed on one [`CGSHOPInstance`] to a geometrically similar one, by
+//! matching segments via their endpoint coordinates within a tolerance. Useful when iterating
+//! on generated scenario variants (small point perturbations of an instance already solved):
+//! rather than re-solving from scratch, most of the previous coloring can be reused as a
+//! warm-start partial assignment, leaving only the handful of genuinely new or displaced
+//! segments for the construction heuristic to place.
+
+use std::rc::Rc;
+
+use crate::cgshop::CGSHOPInstance;
+use crate::color::VertexId;
+
+/// result of [`transfer_coloring_by_affinity`]
+pub struct AffinityTransfer {
+    /// partial coloring over `target`'s vertex ids: `partition[c]` lists the `target` segments
+    /// matched to a `source` segment that held color `c`. Classes may be shorter than in the
+    /// source coloring (or empty) whenever some of their segments went unmatched.
+    pub partition: Vec<Vec<VertexId>>,
+    /// `target` segments that could not be matched to any `source` segment within `tolerance`,
+    /// left for whatever construction heuristic seeds the rest of the warm start
+    pub unmatched: Vec<VertexId>,
+}
+
+fn point_distance(a:(i64, i64), b:(i64, i64)) -> f64 {
+    let dx = (a.0 - b.0) as f64;
+    let dy = (a.1 - b.1) as f64;
+    (dx * dx + dy * dy).sqrt()
+}
+
+/// worst-case distance between the two endpoints of `a` and `b`, trying both pairings since a
+/// segment and its reverse are the same segment
+fn endpoint_distance(a:&((i64, i64), (i64, i64)), b:&((i64, i64), (i64, i64))) -> f64 {
+    let straight = point_distance(a.0, b.0).max(point_distance(a.1, b.1));
+    let swapped = point_distance(a.0, b.1).max(point_distance(a.1, b.0));
+    straight.min(swapped)
+}
+
+/** matches every segment of `target` to the closest segment of `source` whose endpoints lie
+within `tolerance` of its own (in either orientation), then transfers `source_coloring`
+(expressed over `source`'s vertex ids) onto `target` through that matching. `O(target.m() *
+source.m())`: fine for the scenario-variant sizes this is aimed at, not for matching at
+competition scale. */
+pub fn transfer_coloring_by_affinity(
+    source:&Rc<CGSHOPInstance>,
+    source_coloring:&[Vec<VertexId>],
+    target:&Rc<CGSHOPInstance>,
+    tolerance:f64,
+) -> AffinityTransfer {
+    let mut color_of_source:Vec<Option<usize>> = vec![None ; source.m()];
+    for (c, class) in source_coloring.iter().enumerate() {
+        for &v in class {
+            color_of_source[v] = Some(c);
+        }
+    }
+    let mut partition:Vec<Vec<VertexId>> = vec![Vec::new() ; source_coloring.len()];
+    let mut unmatched = Vec::new();
+    for t in 0..target.m() {
+        let target_coord = target.coordinate(t);
+        let best = (0..source.m())
+            .map(|s| (s, endpoint_distance(target_coord, source.coordinate(s))))
+            .filter(|&(_, d)| d <= tolerance)
+            .min_by(|a, b| a.1.partial_cmp(&b.1).unwrap())
+            .map(|(s, _)| s);
+        match best.and_then(|s| color_of_source[s]) {
+            Some(c) => partition[c].push(t),
+            None => unmatched.push(t),
+        }
+    }
+    AffinityTransfer { partition, unmatched }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_transfer_coloring_by_affinity_self_match_is_exact() {
+        let inst = Rc::new(CGSHOPInstance::from_file(
+            "./insts/CGSHOP_22_original/cgshop_2022_examples_01/tiny.json"
+        ));
+        let coloring:Vec<Vec<VertexId>> = (0..inst.m()).map(|v| vec![v]).collect();
+        let transfer = transfer_coloring_by_affinity(&inst, &coloring, &inst, 0.0);
+        assert!(transfer.unmatched.is_empty());
+        assert_eq!(transfer.partition.iter().map(|c| c.len()).sum::<usize>(), inst.m());
+    }
+}
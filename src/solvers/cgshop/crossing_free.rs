@@ -0,0 +1,49 @@
+use std::rc::Rc;
+
+use bit_set::BitSet;
+
+use crate::cgshop::CGSHOPInstance;
+use crate::color::VertexId;
+
+/** greedily extracts a large crossing-free (mutually non-intersecting) subset of segments
+from a CGSHOP instance. Segments are visited shortest-first (short segments tend to cross
+fewer others), and a segment is kept whenever none of its already-kept neighbors conflict
+with it, which only requires checking its precomputed neighbor bitset rather than
+re-testing geometric intersection. The result is a stable set of the conflict graph, usable
+both as a ready-made first color class and as a seed stable set for a set-cover based
+coloring solver. */
+pub fn crossing_free_subset(inst:&Rc<CGSHOPInstance>) -> Vec<VertexId> {
+    let n = inst.nb_vertices();
+    let mut order:Vec<VertexId> = (0..n).collect();
+    order.sort_by(|a,b| inst.squared_length(*a).partial_cmp(&inst.squared_length(*b)).unwrap());
+    let mut kept:Vec<VertexId> = Vec::new();
+    let mut excluded:BitSet = BitSet::with_capacity(n);
+    for u in order {
+        if excluded.contains(u) { continue; }
+        kept.push(u);
+        for v in inst.neighbors(u) {
+            excluded.insert(v);
+        }
+    }
+    kept
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::color::ColoringInstance;
+
+    #[test]
+    fn test_crossing_free_subset_is_a_stable_set() {
+        let inst = Rc::new(CGSHOPInstance::from_file(
+            "./insts/CGSHOP_22_original/cgshop_2022_examples_01/tiny.json"
+        ));
+        let subset = crossing_free_subset(&inst);
+        for &u in &subset {
+            for &v in &subset {
+                if u != v { assert!(!inst.are_adjacent(u, v)); }
+            }
+        }
+    }
+}
@@ -1,4 +1,27 @@
 /// Admissible Orientation Greedy algorithm
-/// 
+///
 /// Selects segments with similar orientation to build a new color
 pub mod cgshop_aog;
+
+/// greedy crossing-free (stable set) subset extraction, for warm starts
+pub mod crossing_free;
+
+/// ready-made move-scoring hooks biasing move selection by geometric features
+pub mod move_bias;
+
+/// reusable "sort segments by orientation and sweep" iterator
+pub mod angular_sweep;
+
+/// safe, resumable competition submission bookkeeping (best solution + history on disk)
+pub mod submission;
+
+/// transfers a coloring to a geometrically similar instance by matching segment endpoints
+pub mod affinity;
+
+/// orientation-bucketed greedy initial solution, a faster but lower-quality alternative to
+/// [`cgshop_aog`] on enormous instances
+pub mod orientation_buckets;
+
+/// geometric clique-seeding heuristic: samples lines stabbing many segments at once, a much
+/// better starting point for [`crate::solvers::clique::partial_weighting`] than random sampling
+pub mod stabbing_clique;
@@ -0,0 +1,40 @@
+use std::rc::Rc;
+
+use crate::cgshop::CGSHOPInstance;
+use crate::color::{ColoringInstance, VertexId};
+
+/** move-scoring hook preferring to recolor short segments first: returns the segment's
+length (square root of its squared length, truncated to an integer), so it can be plugged as the `move_bias` hook of
+`coloring_conflict_weighting_with_guide_and_bias` (lower is preferred). */
+pub fn segment_length_bias(inst:Rc<CGSHOPInstance>) -> Rc<dyn Fn(VertexId) -> i64> {
+    Rc::new(move |v:VertexId| (inst.squared_length(v).sqrt()) as i64)
+}
+
+/** move-scoring hook preferring to recolor low-degree vertices first (lower is preferred). */
+pub fn degree_bias(inst:Rc<CGSHOPInstance>) -> Rc<dyn Fn(VertexId) -> i64> {
+    Rc::new(move |v:VertexId| inst.degree(v) as i64)
+}
+
+/** combines several move-scoring hooks into one, weighted by coefficients: the returned
+hook is `sum_i coefficients[i] * hooks[i](v)`. */
+pub fn combine_biases(hooks:Vec<(f64, Rc<dyn Fn(VertexId) -> i64>)>) -> Rc<dyn Fn(VertexId) -> i64> {
+    Rc::new(move |v:VertexId| {
+        hooks.iter().map(|(coef, hook)| (*coef * hook(v) as f64) as i64).sum()
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_segment_length_bias_tiny() {
+        let inst = Rc::new(CGSHOPInstance::from_file(
+            "./insts/CGSHOP_22_original/cgshop_2022_examples_01/tiny.json"
+        ));
+        let bias = segment_length_bias(inst.clone());
+        for v in 0..inst.nb_vertices() {
+            assert!(bias(v) >= 0);
+        }
+    }
+}
@@ -0,0 +1,80 @@
+use std::rc::Rc;
+
+use crate::{cgshop::CGSHOPInstance, color::{ColoringInstance, Solution}};
+
+/** geometric initial-solution heuristic for the CGSHOP challenge: segments are binned by
+orientation into `k` equal-width buckets over the `[0,180)` range (see
+[`CGSHOPInstance::segment_orientation`]), then each bucket is colored independently with the
+same greedy scheme as [`super::cgshop_aog::cgshop_aog`], checking conflicts only against other
+segments of the same bucket. Since no color class is ever shared between buckets, cross-bucket
+conflicts never need checking at all, cutting the number of adjacency tests a segment faces from
+`O(colors so far)` down to roughly `O(colors so far / k)` — at the price of using up to `k`
+times as many colors overall, since two segments that could have shared a color across a
+bucket boundary never get the chance to. Meant as a much faster, lower-quality alternative to
+[`cgshop_aog`](super::cgshop_aog::cgshop_aog) for instances too large for it to finish on. */
+pub fn orientation_buckets(inst:Rc<CGSHOPInstance>, k:usize, show_completion:bool) -> Solution {
+    assert!(k >= 1, "orientation_buckets: k must be at least 1");
+    let n = inst.nb_vertices();
+    let mut buckets:Vec<Vec<usize>> = vec![Vec::new() ; k];
+    for i in 0..n {
+        let bucket = ((inst.segment_orientation(i) / 180.) * k as f64) as usize;
+        buckets[bucket.min(k - 1)].push(i);
+    }
+    let mut res:Solution = Vec::new();
+    let mut nb_colored = 0;
+    for bucket in buckets {
+        let mut bucket_colors:Vec<Vec<usize>> = Vec::new();
+        for i in bucket {
+            nb_colored += 1;
+            if show_completion && nb_colored % 1000 == 0 { println!("colored {} / {}...", nb_colored, n); }
+            let mut current_color = 0;
+            let mut added = false;
+            while current_color < bucket_colors.len() {
+                let mut is_conflicting = false;
+                for &j in &bucket_colors[current_color] {
+                    if inst.are_adjacent(i, j) {
+                        is_conflicting = true;
+                        break;
+                    }
+                }
+                if !is_conflicting {
+                    bucket_colors[current_color].push(i);
+                    added = true;
+                    break;
+                }
+                current_color += 1;
+            }
+            if !added {
+                bucket_colors.push(vec![i]);
+            }
+        }
+        res.extend(bucket_colors);
+    }
+    res
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::color::checker;
+    use crate::color::CheckerResult;
+
+    #[test]
+    fn test_read_instance_tiny() {
+        let cg_inst = Rc::new(CGSHOPInstance::from_file(
+            "./insts/CGSHOP_22_original/cgshop_2022_examples_01/tiny.json"
+        ));
+        let solution = orientation_buckets(cg_inst.clone(), 4, false);
+        assert_eq!(checker(cg_inst, &solution), CheckerResult::Ok(solution.len()));
+    }
+
+    #[test]
+    fn test_more_buckets_stays_a_proper_coloring() {
+        let cg_inst = Rc::new(CGSHOPInstance::from_file(
+            "./insts/CGSHOP_22_original/cgshop_2022_examples_01/tiny.json"
+        ));
+        let solution = orientation_buckets(cg_inst.clone(), 8, false);
+        assert_eq!(checker(cg_inst, &solution), CheckerResult::Ok(solution.len()));
+    }
+}
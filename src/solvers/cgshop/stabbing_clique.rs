@@ -0,0 +1,57 @@
+use std::rc::Rc;
+
+use crate::cgshop::CGSHOPInstance;
+use crate::color::{ColoringInstance, VertexId};
+
+/** samples `nb_samples` vertical lines across the instance's bounding box and, for each,
+collects the segments it stabs (segments whose `x`-range spans the line) as a candidate pool:
+two segments stabbed by the same line need not actually intersect (e.g. two horizontal segments
+at different heights both crossing `x = c`), but two segments that truly do intersect are
+always stabbed by any vertical line through their intersection point, so this concentrates
+candidates where a big clique is far more likely to be found than plain random sampling would.
+Each candidate pool is then greedily filtered down to an actual clique using
+[`ColoringInstance::are_adjacent`] (the instance's exact, precomputed conflict test), and the
+largest clique found over every sample is returned, ready to hand to
+[`crate::solvers::clique::partial_weighting::clique_partial_weighting`] for further improvement. */
+pub fn stabbing_clique(inst:&Rc<CGSHOPInstance>, nb_samples:usize, rng:&mut fastrand::Rng) -> Vec<VertexId> {
+    let n = inst.nb_vertices();
+    if n == 0 { return Vec::new(); }
+    let (x_min, x_max) = (0..n)
+        .flat_map(|i| { let ((ax,_),(bx,_)) = *inst.coordinate(i); [ax,bx] })
+        .fold((i64::MAX, i64::MIN), |(lo,hi), x| (lo.min(x), hi.max(x)));
+    let mut best:Vec<VertexId> = Vec::new();
+    for _ in 0..nb_samples {
+        let x = x_min + rng.i64(0..(x_max - x_min + 1));
+        let stabbed:Vec<VertexId> = (0..n).filter(|&i| {
+            let ((ax,_),(bx,_)) = *inst.coordinate(i);
+            ax.min(bx) <= x && x <= ax.max(bx)
+        }).collect();
+        let mut clique:Vec<VertexId> = Vec::new();
+        for u in stabbed {
+            if clique.iter().all(|&v| inst.are_adjacent(u, v)) {
+                clique.push(u);
+            }
+        }
+        if clique.len() > best.len() { best = clique; }
+    }
+    best
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_stabbing_clique_is_a_clique() {
+        let inst = Rc::new(CGSHOPInstance::from_file(
+            "./insts/CGSHOP_22_original/cgshop_2022_examples_01/tiny.json"
+        ));
+        let mut rng = fastrand::Rng::new();
+        let clique = stabbing_clique(&inst, 20, &mut rng);
+        for &u in &clique {
+            for &v in &clique {
+                if u != v { assert!(inst.are_adjacent(u, v)); }
+            }
+        }
+    }
+}
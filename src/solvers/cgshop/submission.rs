@@ -0,0 +1,146 @@
+//! safe, resumable submission bookkeeping for a competition run: keeps the best-known
+//! solution for an instance on disk, only ever promotes a candidate once it has been checked
+//! against the instance and shown to strictly improve on whatever is already there, and keeps
+//! a timestamped history of every promotion, automating the hand-managed submission hygiene
+//! ("did I actually check that file before overwriting best.json?") of a competition run.
+
+use std::rc::Rc;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    cgshop::{CGSHOPInstance, CGSHOPSolution},
+    color::{checker, CheckerResult, VertexId},
+};
+
+/// one promoted improvement, recorded in [`SubmissionHistory`]
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct HistoryEntry {
+    /// unix timestamp (seconds) at which this solution was promoted
+    pub timestamp_secs: u64,
+    /// number of colors of the promoted solution
+    pub nb_colors: usize,
+}
+
+/// timestamped history of every improvement promoted through [`SubmissionWorkflow::submit`],
+/// persisted alongside the best solution so a resumed run keeps its provenance
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+struct SubmissionHistory {
+    /// one entry per promoted improvement, oldest first
+    entries: Vec<HistoryEntry>,
+}
+
+/** keeps a competition submission directory for a single instance in sync with the best
+solution found so far: [`SubmissionWorkflow::submit`] only ever overwrites `best.solution.json`
+with a candidate that (a) checks out against the instance and (b) uses strictly fewer colors
+than whatever is already there, appending a timestamped [`HistoryEntry`] to `history.json`
+each time it does. Safe to resume: [`SubmissionWorkflow::open`] reads both files back from
+`dir` instead of assuming a fresh run, so a crashed or restarted process never re-promotes a
+regression over what an earlier process already submitted. */
+pub struct SubmissionWorkflow {
+    /// directory holding `best.solution.json` and `history.json`
+    dir: String,
+    /// number of colors of the best solution currently on disk, if any
+    best_nb_colors: Option<usize>,
+    /// history loaded from (and kept in sync with) `history.json`
+    history: SubmissionHistory,
+}
+
+impl SubmissionWorkflow {
+    /// opens (or creates) a submission workflow rooted at `dir`, loading `best.solution.json`
+    /// and `history.json` if they already exist, so a resumed run picks up the promotion
+    /// threshold and history left by a previous one instead of starting from scratch
+    pub fn open(dir:&str) -> Self {
+        std::fs::create_dir_all(dir)
+            .unwrap_or_else(|why| panic!("SubmissionWorkflow::open: unable to create {}: {}", dir, why));
+        let best_path = Self::best_path(dir);
+        let best_nb_colors = if std::path::Path::new(&best_path).exists() {
+            Some(CGSHOPSolution::from_file(&best_path).to_solution().len())
+        } else {
+            None
+        };
+        let history_path = Self::history_path(dir);
+        let history = if std::path::Path::new(&history_path).exists() {
+            let content = std::fs::read_to_string(&history_path)
+                .unwrap_or_else(|why| panic!("SubmissionWorkflow::open: unable to read {}: {}", history_path, why));
+            serde_json::from_str(&content)
+                .unwrap_or_else(|why| panic!("SubmissionWorkflow::open: unable to parse {}: {}", history_path, why))
+        } else {
+            SubmissionHistory::default()
+        };
+        Self { dir: dir.to_string(), best_nb_colors, history }
+    }
+
+    /// number of colors of the solution currently promoted in `best.solution.json`, if any
+    pub fn best_nb_colors(&self) -> Option<usize> { self.best_nb_colors }
+
+    /// the timestamped history of every improvement promoted so far, oldest first
+    pub fn history(&self) -> &[HistoryEntry] { &self.history.entries }
+
+    /** verifies `candidate` against `inst` and, if it checks out and uses strictly fewer
+    colors than whatever is currently promoted (or nothing is promoted yet), writes it to
+    `best.solution.json` and appends a [`HistoryEntry`] to `history.json`. Returns whether the
+    candidate was promoted; an invalid or non-improving candidate is left untouched, exactly
+    as a human running manual submission hygiene would refuse to overwrite a good submission
+    with a worse or broken one. */
+    pub fn submit(&mut self, inst:Rc<CGSHOPInstance>, candidate:&[Vec<VertexId>]) -> bool {
+        if checker(inst.clone(), candidate) != CheckerResult::Ok(candidate.len()) {
+            return false;
+        }
+        let nb_colors = candidate.len();
+        if self.best_nb_colors.map_or(false, |best| nb_colors >= best) {
+            return false;
+        }
+        CGSHOPSolution::from_solution(inst.id(), candidate).to_file(&Self::best_path(&self.dir));
+        let timestamp_secs = SystemTime::now().duration_since(UNIX_EPOCH)
+            .map(|d| d.as_secs()).unwrap_or(0);
+        self.history.entries.push(HistoryEntry { timestamp_secs, nb_colors });
+        let content = serde_json::to_string(&self.history).unwrap();
+        std::fs::write(Self::history_path(&self.dir), content)
+            .unwrap_or_else(|why| panic!("SubmissionWorkflow::submit: unable to write history: {}", why));
+        self.best_nb_colors = Some(nb_colors);
+        true
+    }
+
+    /// path to the promoted best solution within `dir`
+    fn best_path(dir:&str) -> String { format!("{}/best.solution.json", dir) }
+
+    /// path to the promotion history within `dir`
+    fn history_path(dir:&str) -> String { format!("{}/history.json", dir) }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_submission_workflow_rejects_regression_and_persists_across_open() {
+        let inst = Rc::new(CGSHOPInstance::from_file(
+            "./insts/CGSHOP_22_original/cgshop_2022_examples_01/tiny.json"
+        ));
+        let dir = format!("{}/test_submission_workflow", std::env::temp_dir().to_str().unwrap());
+        let _ = std::fs::remove_dir_all(&dir);
+
+        let mut workflow = SubmissionWorkflow::open(&dir);
+        assert_eq!(workflow.best_nb_colors(), None);
+        let sol = crate::solvers::coloring::greedy_dsatur::greedy_dsatur(inst.clone(), false);
+        assert!(workflow.submit(inst.clone(), &sol));
+        assert_eq!(workflow.best_nb_colors(), Some(sol.len()));
+        assert_eq!(workflow.history().len(), 1);
+
+        // a solution using at least as many colors must not be promoted
+        let mut regressed = sol.clone();
+        regressed.push(vec![]);
+        assert!(!workflow.submit(inst.clone(), &regressed));
+        assert_eq!(workflow.best_nb_colors(), Some(sol.len()));
+        assert_eq!(workflow.history().len(), 1);
+
+        // a resumed workflow must see the same promotion threshold and history
+        let resumed = SubmissionWorkflow::open(&dir);
+        assert_eq!(resumed.best_nb_colors(), Some(sol.len()));
+        assert_eq!(resumed.history().len(), 1);
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+}
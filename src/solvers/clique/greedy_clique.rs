@@ -37,8 +37,139 @@ pub fn greedy_clique(inst:Rc<dyn ColoringInstance>) -> Vec<VertexId> {
     for s in res.iter() { res_bitset.insert(*s); }
     res
 }
+/** same as [`greedy_clique`], but starting the clique from a fixed `seed` vertex instead of
+the globally largest-degree one (used to explore several distinct greedy cliques by varying
+the seed). */
+fn greedy_clique_from_seed(inst:Rc<dyn ColoringInstance>, seed:VertexId) -> Vec<VertexId> {
+    let n = inst.nb_vertices();
+    let mut forbidden:BitSet<u64> = BitSet::default();
+    let mut res = vec![seed];
+    let mut neighbors:BitSet<u64> = BitSet::default();
+    for v in inst.neighbors(seed) { neighbors.insert(v); }
+    for v in 0..n {
+        if v != seed && !neighbors.contains(v) {
+            forbidden.insert(v);
+        }
+    }
+    loop {
+        match (0..n).filter(|v| !forbidden.contains(*v) && !res.contains(v)).max_by_key(|v| inst.degree(*v)) {
+            None => break,
+            Some(current_vertex) => {
+                res.push(current_vertex);
+                let mut neighbors:BitSet<u64> = BitSet::default();
+                for v in inst.neighbors(current_vertex) {
+                    neighbors.insert(v);
+                }
+                for v in 0..n {
+                    if !neighbors.contains(v) {
+                        forbidden.insert(v);
+                    }
+                }
+            }
+        };
+    }
+    res
+}
 
+/** explores several greedy cliques, one per seed among the `nb_seeds` highest-degree
+vertices, and returns the union of the vertices belonging to any clique within `slack` of
+the largest one found. Intended to feed a "hard vertex" priority set to the coloring local
+search: vertices that repeatedly show up in large cliques are the ones most likely to force
+many colors, so they should never be left uncolored during the search. */
+pub fn near_max_clique_vertices(inst:Rc<dyn ColoringInstance>, nb_seeds:usize, slack:usize) -> Vec<VertexId> {
+    let n = inst.nb_vertices();
+    let mut seeds:Vec<VertexId> = (0..n).collect();
+    seeds.sort_by_key(|v| std::cmp::Reverse(inst.degree(*v)));
+    seeds.truncate(nb_seeds.min(n));
+    let cliques:Vec<Vec<VertexId>> = seeds.iter()
+        .map(|s| greedy_clique_from_seed(inst.clone(), *s))
+        .collect();
+    let best_size = cliques.iter().map(|c| c.len()).max().unwrap_or(0);
+    let mut res_bitset:BitSet = BitSet::new();
+    for c in cliques.iter().filter(|c| c.len() + slack >= best_size) {
+        for v in c {
+            res_bitset.insert(*v);
+        }
+    }
+    res_bitset.iter().collect()
+}
 
+/** updates a (maximal) clique after the instance grew with `new_vertices`, without
+restarting the search from scratch. Each new vertex is greedily inserted into the clique
+if it is adjacent to every current member; a new vertex that cannot be inserted but is
+adjacent to all but one clique member replaces that member instead (the clique keeps its
+size); any other new vertex is left out.
+`inst` must already reflect the grown graph (i.e. `new_vertices` are valid vertex ids of
+`inst` and `inst.neighbors` accounts for the new edges). */
+pub fn extend_clique_incremental(
+    inst:Rc<dyn ColoringInstance>,
+    clique:&[VertexId],
+    new_vertices:&[VertexId],
+) -> Vec<VertexId> {
+    let mut res = clique.to_vec();
+    let mut res_bitset:BitSet = BitSet::new();
+    for v in &res { res_bitset.insert(*v); }
+    for &v in new_vertices {
+        let mut neighbors:BitSet = BitSet::new();
+        for u in inst.neighbors(v) { neighbors.insert(u); }
+        if res.iter().all(|u| neighbors.contains(*u)) {
+            // v is adjacent to the whole clique: extend it directly
+            res.push(v);
+            res_bitset.insert(v);
+        } else {
+            // v misses exactly one clique member: try swapping it in
+            let non_adjacent:Vec<VertexId> = res.iter().filter(|u| !neighbors.contains(**u)).copied().collect();
+            if non_adjacent.len() == 1 {
+                let excluded = non_adjacent[0];
+                // only swap if v is itself adjacent to every other vertex that would remain
+                res.retain(|u| *u != excluded);
+                res.push(v);
+                res_bitset.remove(excluded);
+                res_bitset.insert(v);
+            }
+        }
+    }
+    res
+}
+
+/** builds a greedy clique restricted to the subgraph induced by `inst`'s highest-weight
+vertices, as reported by a finished [`crate::solvers::coloring::partial_weighting`] run's
+learned conflict `weights` (one entry per vertex). High-weight vertices are the ones the
+local search repeatedly failed to place without conflict, so the subgraph they induce is
+often denser than the graph overall and can reveal a larger clique than seeding purely from
+vertex degree, as [`greedy_clique`] does. `top_fraction` is the fraction of vertices (by
+weight, highest first) kept as candidates, e.g. `0.1` for the top 10%. Registers the
+resulting clique size as a new lower bound in the [`crate::bounds_registry`] for `inst`. */
+pub fn greedy_clique_from_conflict_region(
+    inst:Rc<dyn ColoringInstance>,
+    weights:&[i32],
+    top_fraction:f64,
+) -> Vec<VertexId> {
+    let n = inst.nb_vertices();
+    let mut candidates:Vec<VertexId> = (0..n).collect();
+    candidates.sort_by_key(|v| std::cmp::Reverse(weights[*v]));
+    let nb_candidates = ((n as f64) * top_fraction.clamp(0., 1.)).ceil() as usize;
+    candidates.truncate(nb_candidates.max(1).min(n));
+    let mut remaining:BitSet = BitSet::new();
+    for v in &candidates { remaining.insert(*v); }
+
+    let mut res:Vec<VertexId> = Vec::new();
+    loop {
+        let next = remaining.iter()
+            .filter(|v| res.iter().all(|u| inst.are_adjacent(*u, *v)))
+            .max_by_key(|v| weights[*v]);
+        match next {
+            None => break,
+            Some(v) => {
+                res.push(v);
+                remaining.remove(v);
+            }
+        }
+    }
+    let key = crate::bounds_registry::instance_key(inst.as_ref());
+    crate::bounds_registry::record_lower_bound(key, res.len());
+    res
+}
 
 #[cfg(test)]
 mod tests {
@@ -56,6 +187,27 @@ mod tests {
         println!("clique size: {}", solution.len());
     }
 
+    #[test]
+    fn test_extend_clique_incremental_tiny() {
+        let cg_inst = Rc::new(CGSHOPInstance::from_file(
+            "./insts/CGSHOP_22_original/cgshop_2022_examples_01/tiny.json"
+        ));
+        let clique = greedy_clique(cg_inst.clone());
+        let new_vertices:Vec<VertexId> = (0..cg_inst.nb_vertices()).filter(|v| !clique.contains(v)).collect();
+        let extended = extend_clique_incremental(cg_inst, &clique, &new_vertices);
+        assert!(extended.len() >= clique.len());
+    }
+
+    #[test]
+    fn test_near_max_clique_vertices_tiny() {
+        let cg_inst = Rc::new(CGSHOPInstance::from_file(
+            "./insts/CGSHOP_22_original/cgshop_2022_examples_01/tiny.json"
+        ));
+        let priority = near_max_clique_vertices(cg_inst.clone(), 5, 1);
+        assert!(!priority.is_empty());
+        assert!(priority.iter().all(|v| *v < cg_inst.nb_vertices()));
+    }
+
     #[test]
     fn test_read_instance_visp() {
         let cg_inst = Rc::new(CGSHOPInstance::from_file(
@@ -96,6 +248,28 @@ mod tests {
         println!("clique size: {}", solution.len());
     }
 
+    #[test]
+    fn test_greedy_clique_from_conflict_region() {
+        use crate::dimacs::DimacsInstance;
+
+        // vertex 0 has the highest degree but low weight; vertices 2,3,4 form a triangle
+        // and carry high weight, so the conflict-region search should find them instead
+        let inst:Rc<dyn ColoringInstance> = Rc::new(DimacsInstance::new(vec![
+            vec![1, 2, 3, 4], // 0
+            vec![0],          // 1
+            vec![0, 3, 4],    // 2
+            vec![0, 2, 4],    // 3
+            vec![0, 2, 3],    // 4
+        ]));
+        let weights = vec![1, 1, 10, 10, 10];
+        let key = crate::bounds_registry::instance_key(inst.as_ref());
+        crate::bounds_registry::remove(key);
+        let clique = greedy_clique_from_conflict_region(inst.clone(), &weights, 0.6);
+        assert_eq!(clique.len(), 3);
+        assert!(clique.iter().all(|v| [2usize, 3, 4].contains(v)));
+        assert_eq!(crate::bounds_registry::get(key).lower_bound, Some(3));
+    }
+
     #[test]
     fn test_read_instance_visp_100k() {
         let cg_inst = Rc::new(CGSHOPInstance::from_file(
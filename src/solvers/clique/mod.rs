@@ -9,4 +9,7 @@ pub mod partial_weighting;
 pub mod greedy_clique;
 
 /// branch & bound for the CLIQUE problem
-pub mod bnb;
\ No newline at end of file
+pub mod bnb;
+
+/// exact maximum clique search with an independently verifiable lower-bound proof trace
+pub mod proof;
\ No newline at end of file
@@ -14,7 +14,7 @@ use dogs::{
     search_space::{SearchSpace, TotalNeighborGeneration, GuidedSpace, ToSolution}, tree_search::greedy::Greedy
 };
 
-use crate::{color::{ColoringInstance, VertexId}, util::{clique_vec_to_vecvec, export_results}};
+use crate::{color::{ColoringInstance, VertexId}, util::{clique_vec_to_vecvec, export_results, RunClock}};
 
 type Weight = u32;
 
@@ -105,6 +105,12 @@ struct PartialWeightingLocalSearch {
     configuration:BitSet,
     /// aspiration criterion
     aspiration:usize,
+    /// wall-clock start of the search, used to timestamp [`PartialWeightingLocalSearch::on_new_solution`] calls
+    clock:RunClock,
+    /// optional callback invoked with `(solution, wall_time_secs)` each time
+    /// [`PartialWeightingLocalSearch::add_vertex`] finds a bigger feasible clique, so an embedding
+    /// application can stream improving solutions without waiting for the run to finish
+    on_new_solution:Option<Rc<RefCell<dyn FnMut(&[Vec<VertexId>], f32)>>>,
 }
 
 impl PartialWeightingLocalSearch {
@@ -138,6 +144,8 @@ impl PartialWeightingLocalSearch {
             nb_iter: 0,
             configuration,
             aspiration: sol.len(),
+            clock: RunClock::start(),
+            on_new_solution: None,
         }
     }
 
@@ -183,6 +191,10 @@ impl PartialWeightingLocalSearch {
         // if improving the current-best-known solution, update it
         if self.inside_clique.len() > self.current_sol.len() {
             self.current_sol = self.inside_clique.iter().collect();
+            if let Some(cb) = &self.on_new_solution {
+                let sol = clique_vec_to_vecvec(&self.current_sol, self.inst.nb_vertices());
+                (*cb.borrow_mut())(&sol, self.clock.wall_secs());
+            }
         }
         self.nb_iter += 1;
     }
@@ -323,6 +335,52 @@ pub fn clique_partial_weighting<Stopping:StoppingCriterion>(
     solution
 }
 
+/** same as [`clique_partial_weighting`], invoking `on_new_solution` with
+`(solution, wall_time_secs)` each time a bigger feasible clique is found, so an embedding
+application can stream improving solutions (e.g. upload to the CGSHOP server) without waiting
+for the run to finish. */
+pub fn clique_partial_weighting_with_callback<Stopping:StoppingCriterion>(
+    inst:Rc<dyn ColoringInstance>,
+    sol:&[VertexId],
+    perf_filename:Option<String>,
+    sol_filename:Option<String>,
+    stop:Stopping,
+    on_new_solution:Rc<RefCell<dyn FnMut(&[Vec<VertexId>], f32)>>,
+) -> Vec<Vec<VertexId>> {
+    let mut solution:Vec<Vec<VertexId>> = clique_vec_to_vecvec(sol, inst.nb_vertices());
+    let logger = Rc::new(MetricLogger::default());
+    let mut search = PartialWeightingLocalSearch::initialize(inst.clone(), &solution[0]);
+    search.on_new_solution = Some(on_new_solution);
+    let space = Rc::new(RefCell::new(
+        StatTsCombinator::new(search).bind_logger(Rc::downgrade(&logger)),
+    ));
+    let mut ts = Greedy::new(space.clone());
+    logger.display_headers();
+    ts.run(stop);
+    // display the results afterwards
+    space.borrow_mut().display_statistics();
+    // check that the last solution is valid
+    match ts.get_manager().best() {
+        None => {
+            println!("\tlocal search failed improving...");
+        }
+        Some(node) => {
+            solution = space.borrow_mut().solution(&mut node.clone());
+        }
+    }
+    let mut stats = serde_json::Value::default();
+    space.borrow_mut().json_statistics(&mut stats);
+    export_results(
+        inst,
+        &solution,
+        &stats,
+        perf_filename,
+        sol_filename,
+        false,
+    );
+    solution
+}
+
 
 #[cfg(test)]
 mod tests {
@@ -656,4 +714,22 @@ mod tests {
         println!("after ls: {}", sol_ls.len());
     }
 
+    #[test]
+    fn test_pwls_with_callback_reports_new_solutions() {
+        let inst = Rc::new(CGSHOPInstance::from_file(
+            "./insts/cgshop22/vispecn5478.instance.json"
+        ));
+        let greedy_sol = greedy_clique(inst.clone());
+        let calls = Rc::new(RefCell::new(0usize));
+        let calls_handle = calls.clone();
+        let on_new_solution:Rc<RefCell<dyn FnMut(&[Vec<VertexId>], f32)>> = Rc::new(RefCell::new(
+            move |_sol:&[Vec<VertexId>], _time:f32| { *calls_handle.borrow_mut() += 1; }
+        ));
+        let stopping_criterion:TimeStoppingCriterion = TimeStoppingCriterion::new(10.);
+        clique_partial_weighting_with_callback(
+            inst, &greedy_sol, None, None, stopping_criterion, on_new_solution
+        );
+        assert!(*calls.borrow() > 0);
+    }
+
 }
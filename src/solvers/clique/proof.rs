@@ -0,0 +1,215 @@
+//! exact maximum clique search with an exportable, independently-checkable proof of the
+//! reported lower bound, complementing the heuristic search of [`crate::solvers::clique::bnb`]
+//! (whose [`crate::solvers::clique::bnb::CLIQUESpace`] descends a single greedy path and never
+//! actually proves optimality) and the plain greedy constructions of
+//! [`crate::solvers::clique::greedy_clique`]. Branches on including/excluding a candidate
+//! vertex, pruning a branch once a greedy coloring of its remaining candidates proves it
+//! cannot beat the best clique found so far (a proper coloring of the candidate pool
+//! upper-bounds how many of them can ever join one clique together); every
+//! branching decision and every pruning coloring is recorded into a [`ProofTrace`] that
+//! [`verify_proof_trace`] can replay from scratch, trusting nothing from the run that produced it.
+
+use std::rc::Rc;
+
+use bit_set::BitSet;
+use serde::{Deserialize, Serialize};
+
+use crate::color::{ColoringInstance, VertexId};
+
+/// one node of a [`ProofTrace`]'s branch-and-bound tree
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub enum ProofNode {
+    /// branches on `vertex`: `include` covers the subtree where it joins the clique, `exclude`
+    /// the subtree where it is merely removed from the candidate pool
+    Branch {
+        /// the vertex branched on
+        vertex: VertexId,
+        /// subtree where `vertex` is added to the clique
+        include: Box<ProofNode>,
+        /// subtree where `vertex` is dropped from the candidates without being added
+        exclude: Box<ProofNode>,
+    },
+    /// the branch was pruned: `coloring` is a proper coloring of every remaining candidate at
+    /// this node, so its number of classes upper-bounds how many more vertices could possibly
+    /// be added to the clique being built here, and that bound is not enough to beat the best
+    /// clique found elsewhere in the tree
+    Prune {
+        /// a proper coloring of the candidate vertices remaining at this node
+        coloring: Vec<Vec<VertexId>>,
+    },
+    /// no candidates remain: the clique built along this branch is maximal
+    Leaf {
+        /// size of the clique built along this branch
+        clique_size: usize,
+    },
+}
+
+/// a full proof that `best_size` lower-bounds the clique number of some instance, replayable
+/// from scratch by [`verify_proof_trace`] without trusting anything about how it was produced
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ProofTrace {
+    /// root of the branch-and-bound tree explored by [`exact_max_clique_with_proof`]
+    pub tree: ProofNode,
+    /// size of the largest clique found, i.e. the proven lower bound
+    pub best_size: usize,
+}
+
+impl ProofTrace {
+    /// writes the trace to `filename` as JSON
+    pub fn to_file(&self, filename:&str) {
+        let content = serde_json::to_string(self).unwrap();
+        std::fs::write(filename, content)
+            .unwrap_or_else(|why| panic!("ProofTrace::to_file: unable to write {}: {}", filename, why));
+    }
+
+    /// reads a trace previously written by [`ProofTrace::to_file`]
+    pub fn from_file(filename:&str) -> Self {
+        let content = std::fs::read_to_string(filename)
+            .unwrap_or_else(|why| panic!("ProofTrace::from_file: unable to read {}: {}", filename, why));
+        serde_json::from_str(&content)
+            .unwrap_or_else(|why| panic!("ProofTrace::from_file: unable to parse {}: {}", filename, why))
+    }
+}
+
+/// greedily (first-fit) colors the induced subgraph on `candidates`, used purely as an upper
+/// bound on how many of them could ever join one clique: each class of a proper coloring is an
+/// independent set, so a clique entirely within `candidates` has at most one vertex per class
+fn greedy_color_induced(inst:&Rc<dyn ColoringInstance>, candidates:&BitSet) -> Vec<Vec<VertexId>> {
+    let mut classes:Vec<Vec<VertexId>> = Vec::new();
+    for v in candidates.iter() {
+        match classes.iter().position(|class:&Vec<VertexId>| class.iter().all(|&u| !inst.are_adjacent(u, v))) {
+            Some(c) => classes[c].push(v),
+            None => classes.push(vec![v]),
+        }
+    }
+    classes
+}
+
+fn branch(
+    inst:&Rc<dyn ColoringInstance>,
+    candidates:BitSet,
+    clique:&mut Vec<VertexId>,
+    best_clique:&mut Vec<VertexId>,
+) -> ProofNode {
+    if clique.len() > best_clique.len() {
+        *best_clique = clique.clone();
+    }
+    if candidates.is_empty() {
+        return ProofNode::Leaf { clique_size: clique.len() };
+    }
+    let coloring = greedy_color_induced(inst, &candidates);
+    if clique.len() + coloring.len() <= best_clique.len() {
+        return ProofNode::Prune { coloring };
+    }
+    let vertex = candidates.iter().max_by_key(|&v| inst.degree(v)).unwrap();
+    let mut include_candidates = BitSet::with_capacity(inst.nb_vertices());
+    for u in candidates.iter() {
+        if u != vertex && inst.are_adjacent(vertex, u) { include_candidates.insert(u); }
+    }
+    clique.push(vertex);
+    let include = Box::new(branch(inst, include_candidates, clique, best_clique));
+    clique.pop();
+    let mut exclude_candidates = candidates;
+    exclude_candidates.remove(vertex);
+    let exclude = Box::new(branch(inst, exclude_candidates, clique, best_clique));
+    ProofNode::Branch { vertex, include, exclude }
+}
+
+/** runs an exact branch-and-bound maximum clique search over `inst`, returning the largest
+clique found together with a [`ProofTrace`] that [`verify_proof_trace`] can check
+independently. Exhaustive and therefore exponential in the worst case: meant for the small-to-
+moderate instances where an optimality certificate is wanted, not as a drop-in replacement for
+[`crate::solvers::clique::greedy_clique::greedy_clique`] or
+[`crate::solvers::clique::bnb::greedy_clique`] on large competition instances. */
+pub fn exact_max_clique_with_proof(inst:Rc<dyn ColoringInstance>) -> (Vec<VertexId>, ProofTrace) {
+    let mut candidates = BitSet::with_capacity(inst.nb_vertices());
+    for v in inst.vertices() { candidates.insert(v); }
+    let mut clique = Vec::new();
+    let mut best_clique = Vec::new();
+    let tree = branch(&inst, candidates, &mut clique, &mut best_clique);
+    let best_size = best_clique.len();
+    (best_clique, ProofTrace { tree, best_size })
+}
+
+fn verify_node(
+    inst:&Rc<dyn ColoringInstance>,
+    node:&ProofNode,
+    candidates:BitSet,
+    clique_size:usize,
+    best_size:usize,
+    max_leaf:&mut usize,
+) -> bool {
+    match node {
+        ProofNode::Leaf { clique_size: claimed } => {
+            if *claimed != clique_size { return false; }
+            *max_leaf = (*max_leaf).max(clique_size);
+            true
+        }
+        ProofNode::Prune { coloring } => {
+            let mut covered = BitSet::with_capacity(inst.nb_vertices());
+            for class in coloring {
+                for &u in class {
+                    if !candidates.contains(u) || covered.contains(u) { return false; }
+                    covered.insert(u);
+                }
+                for i in 0..class.len() {
+                    for j in (i + 1)..class.len() {
+                        if inst.are_adjacent(class[i], class[j]) { return false; }
+                    }
+                }
+            }
+            covered.len() == candidates.len() && clique_size + coloring.len() <= best_size
+        }
+        ProofNode::Branch { vertex, include, exclude } => {
+            if !candidates.contains(*vertex) { return false; }
+            let mut include_candidates = BitSet::with_capacity(inst.nb_vertices());
+            for u in candidates.iter() {
+                if u != *vertex && inst.are_adjacent(*vertex, u) { include_candidates.insert(u); }
+            }
+            let mut exclude_candidates = candidates;
+            exclude_candidates.remove(*vertex);
+            verify_node(inst, include, include_candidates, clique_size + 1, best_size, max_leaf)
+                && verify_node(inst, exclude, exclude_candidates, clique_size, best_size, max_leaf)
+        }
+    }
+}
+
+/** independently replays `trace` against `inst` from scratch (recomputing every candidate set
+from the bare branching decisions, and re-checking every pruning coloring is a genuinely proper
+coloring of exactly the candidates remaining at that node) and returns whether it actually
+proves that `trace.best_size` lower-bounds the clique number of `inst`: some branch must reach
+a leaf of exactly that size, and every other branch must be either fully exhausted or correctly
+pruned. Trusts nothing about how `trace` was produced, so it is safe to run on a trace received
+from an untrusted source (e.g. a competition submission claiming a lower bound). */
+pub fn verify_proof_trace(inst:&Rc<dyn ColoringInstance>, trace:&ProofTrace) -> bool {
+    let mut candidates = BitSet::with_capacity(inst.nb_vertices());
+    for v in inst.vertices() { candidates.insert(v); }
+    let mut max_leaf = 0;
+    verify_node(inst, &trace.tree, candidates, 0, trace.best_size, &mut max_leaf)
+        && max_leaf == trace.best_size
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::dimacs::DimacsInstance;
+
+    #[test]
+    fn test_exact_max_clique_with_proof_on_grid2x2() {
+        // grid2x2 is a 4-cycle: triangle-free, so its clique number is 2
+        let inst:Rc<dyn ColoringInstance> = Rc::new(DimacsInstance::from_file("insts/grid-instances/grid2x2"));
+        let (clique, trace) = exact_max_clique_with_proof(inst.clone());
+        assert_eq!(clique.len(), 2);
+        assert_eq!(trace.best_size, 2);
+        assert!(verify_proof_trace(&inst, &trace));
+    }
+
+    #[test]
+    fn test_verify_proof_trace_rejects_tampered_best_size() {
+        let inst:Rc<dyn ColoringInstance> = Rc::new(DimacsInstance::from_file("insts/grid-instances/grid2x2"));
+        let (_, mut trace) = exact_max_clique_with_proof(inst.clone());
+        trace.best_size += 1; // claim a bound the trace does not actually support
+        assert!(!verify_proof_trace(&inst, &trace));
+    }
+}
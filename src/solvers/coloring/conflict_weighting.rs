@@ -1,13 +1,15 @@
-use std::{cell::RefCell, rc::Rc};
+use std::{cell::RefCell, collections::{HashMap, VecDeque}, rc::Rc, thread, time::Instant};
 
 use bit_set::BitSet;
 use fastrand::Rng;
+use serde::{Deserialize, Serialize};
 
 use dogs::{
     combinators::{helper::tabu_tenure::TabuTenure, stats::StatTsCombinator},
     data_structures::sparse_set::SparseSet,
     metric_logger::MetricLogger,
     search_algorithm::StoppingCriterion,
+    search_algorithm::TimeStoppingCriterion,
     search_space::{SearchSpace, TotalNeighborGeneration, GuidedSpace, ToSolution},
     tree_search::greedy::Greedy,
     search_algorithm::SearchAlgorithm
@@ -15,11 +17,97 @@ use dogs::{
 
 use crate::{
     color::{ColoringInstance, VertexId},
-    util::export_results
+    solvers::clique::{greedy_clique::greedy_clique, partial_weighting::clique_partial_weighting},
+    util::{export_results, export_results_with_trace, log_metrics, ImprovementRecord, LogFormat, RunClock, TimeBasis}
 };
 
 type Weight = u16;
 
+/** strategy used to rank candidate moves within the local search (the "guide").
+Defaults to the raw total weight of the move, but can be tuned towards the number
+of remaining conflicts, a linear combination of both, or a fully custom closure
+taking `(weight, nb_conflicts)` and returning the guide value. */
+pub enum GuideStrategy {
+    /// guide by the total penalized weight of the move (historical default)
+    Weight,
+    /// guide by `weight + alpha * nb_conflicts`
+    WeightPlusConflicts(f64),
+    /// guide primarily by the number of remaining conflicts, weight as tie-break
+    ConflictsFirst,
+    /// user-provided closure: `(weight, nb_conflicts) -> guide`
+    Custom(Rc<dyn Fn(i64, i64) -> i64>),
+}
+
+impl Default for GuideStrategy {
+    fn default() -> Self { GuideStrategy::Weight }
+}
+
+impl std::fmt::Debug for GuideStrategy {
+    fn fmt(&self, f:&mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            GuideStrategy::Weight => write!(f, "GuideStrategy::Weight"),
+            GuideStrategy::WeightPlusConflicts(alpha) => write!(f, "GuideStrategy::WeightPlusConflicts({})", alpha),
+            GuideStrategy::ConflictsFirst => write!(f, "GuideStrategy::ConflictsFirst"),
+            GuideStrategy::Custom(_) => write!(f, "GuideStrategy::Custom(..)"),
+        }
+    }
+}
+
+impl GuideStrategy {
+    /// computes the guide value for a move given its weight and number of remaining conflicts
+    fn evaluate(&self, weight:i64, nb_conflicts:i64) -> i64 {
+        match self {
+            GuideStrategy::Weight => weight,
+            GuideStrategy::WeightPlusConflicts(alpha) => weight + (*alpha * nb_conflicts as f64) as i64,
+            GuideStrategy::ConflictsFirst => nb_conflicts * 1_000_000 + weight,
+            GuideStrategy::Custom(f) => f(weight, nb_conflicts),
+        }
+    }
+}
+
+/** policy applied when [`TotalNeighborGeneration::neighbors`] cannot find any admissible
+move at all: every candidate that would tie or improve on the incumbent is tabu and none of
+them meets the aspiration criterion, so `best_nodes` only holds the dummy sentinel (a
+`previous_color == next_color` no-op). Left at [`EscapePolicy::Stall`] (the historical
+behavior), the search commits that no-op, which leaves the state unchanged and therefore
+produces the exact same empty `best_nodes` again next call, stalling until the stopping
+criterion or an unrelated [`ConflictWeightingLocalSearch::merge_colors`] intervenes. */
+#[derive(Clone,Copy,Debug,Eq,PartialEq)]
+pub enum EscapePolicy {
+    /// commit the no-op dummy node (historical behavior): the search stalls
+    Stall,
+    /// commit the best move found, ignoring its tabu status
+    BestTabuMove,
+    /// commit a uniformly random move among every move generated this call, tabu or not
+    RandomMove,
+}
+
+impl Default for EscapePolicy {
+    fn default() -> Self { EscapePolicy::Stall }
+}
+
+/** neighborhood [`TotalNeighborGeneration::neighbors`] falls back on when no admissible
+non-tabu single-vertex recolor exists (the same stall [`EscapePolicy`] addresses). */
+#[derive(Clone,Copy,Debug,Eq,PartialEq)]
+pub enum Neighborhood {
+    /// single-vertex recolors only; stalls are handled by [`EscapePolicy`] (historical behavior)
+    SingleVertex,
+    /// on a stall, swap a Kempe chain instead: the two-color connected component containing a
+    /// random conflicting vertex, between its current color and a random other color in use.
+    /// Every neighbor of a chain vertex that lies outside the chain necessarily has neither of
+    /// the two colors involved (otherwise it would itself be part of the chain), so the swap
+    /// leaves the set of conflicting edges, hence `nb_conflicting_edges`, unchanged. The swap is
+    /// applied one vertex at a time, so every edge that conflicts at some point along the way
+    /// has its learned weight increased, and `total_weight` ends up one higher per conflicting
+    /// edge between chain vertices. A diversification move, restructuring the coloring without
+    /// adding conflicts, which can escape local optima single-vertex recolors cannot leave
+    KempeChain,
+}
+
+impl Default for Neighborhood {
+    fn default() -> Self { Neighborhood::SingleVertex }
+}
+
 /// models a decision within the local search.
 #[derive(Debug,Clone,Eq,PartialEq,Hash)]
 struct Node {
@@ -28,6 +116,9 @@ struct Node {
     pub next_color:usize, // next color of vertex v
     pub total_penalties:Weight, // total Weight associated with the decision
     pub nb_conflicts:i64, // number of conflicts
+    /// other vertices to swap between `previous_color` and `next_color` alongside `vertex`
+    /// (see [`Neighborhood::KempeChain`]); empty for a plain single-vertex recolor
+    pub chain:Vec<VertexId>,
 }
 
 
@@ -54,6 +145,9 @@ pub struct TabuColTenure {
     rng: Rng,
     /// threshold value for a given iteration
     threshold: i64,
+    /// seed the random number generator was created with (tracked so [`Checkpoint::tabu_seed`]
+    /// can reproduce it across a checkpoint/resume cycle)
+    seed: u64,
 }
 
 impl TabuTenure<Node, Node> for TabuColTenure {
@@ -75,12 +169,19 @@ impl TabuColTenure {
      - c: the maximum number of colors
     */
     pub fn new(l:usize, lambda: f64, n:usize, c:usize) -> Self {
+        Self::with_seed(l, lambda, n, c, fastrand::u64(..))
+    }
+
+    /// same as [`TabuColTenure::new`], using a caller-provided random seed instead of one
+    /// drawn from the thread-local generator, so a checkpoint can reproduce it on resume
+    pub fn with_seed(l:usize, lambda: f64, n:usize, c:usize, seed:u64) -> Self {
         Self {
             l, lambda,
             nb_iter: 0,
             decisions: vec![vec![i64::MIN ; c] ; n],
-            rng: Rng::new(),
+            rng: Rng::with_seed(seed),
             threshold: 0, // will be changed later
+            seed,
         }
     }
 
@@ -88,9 +189,248 @@ impl TabuColTenure {
     pub fn increment_iter(&mut self) { self.nb_iter += 1; }
 }
 
-/** implements a conflict weighting local search */
+/** a tabu tenure combining two granularities of memory: a short-lived forbiddance on the
+vertex alone (any color), on top of [`TabuColTenure`]'s existing per-(vertex,color) memory.
+Sparse geometric instances (e.g. CGSHOP) are reported to benefit from the coarser vertex-level
+tabu, while dense random instances favor the finer (vertex,color) one; [`TabuKind`] lets a
+solver run pick whichever fits, without forcing one choice on the other. */
 #[derive(Debug)]
-struct ConflictWeightingLocalSearch {
+pub struct TwoLevelTabuTenure {
+    /// fixed size of the short, vertex-level tabu
+    l_vertex: usize,
+    /// dynamic factor of the short, vertex-level tabu
+    lambda_vertex: f64,
+    /// last_moved[v]: last iteration in which v was moved, regardless of color
+    last_moved: Vec<i64>,
+    /// threshold value of the vertex-level tabu, for a given iteration
+    vertex_threshold: i64,
+    /// random number generator, used to draw `vertex_threshold` like [`TabuColTenure`] does
+    rng: Rng,
+    /// number of iterations since the beginning of the search
+    nb_iter: i64,
+    /// longer, per-(vertex,color) tabu memory
+    pair: TabuColTenure,
+}
+
+impl TabuTenure<Node, Node> for TwoLevelTabuTenure {
+    fn insert(&mut self, n:&Node, d:Node) {
+        self.last_moved[d.vertex] = self.nb_iter;
+        self.vertex_threshold = self.rng.i64(0..self.l_vertex as i64) + (self.lambda_vertex * (n.nb_conflicts as f64)) as i64;
+        self.pair.insert(n, d);
+    }
+
+    fn contains(&mut self, n:&Node, d:&Node) -> bool {
+        self.last_moved[d.vertex] >= self.nb_iter - self.vertex_threshold || self.pair.contains(n, d)
+    }
+}
+
+impl TwoLevelTabuTenure {
+    /** creates a two-level tabu tenure, combining a short vertex-level tabu (`l_vertex`,
+    `lambda_vertex`) with a longer (vertex,color)-level one (`l_pair`, `lambda_pair`, see
+    [`TabuColTenure::new`]). */
+    pub fn new(l_vertex:usize, lambda_vertex:f64, l_pair:usize, lambda_pair:f64, n:usize, c:usize) -> Self {
+        Self {
+            l_vertex, lambda_vertex,
+            last_moved: vec![i64::MIN ; n],
+            vertex_threshold: 0,
+            rng: Rng::with_seed(fastrand::u64(..)),
+            nb_iter: 0,
+            pair: TabuColTenure::new(l_pair, lambda_pair, n, c),
+        }
+    }
+
+    /// increases the number of iterations of both levels of the tabu tenure
+    pub fn increment_iter(&mut self) {
+        self.nb_iter += 1;
+        self.pair.increment_iter();
+    }
+}
+
+/** reactive counterpart to [`TabuColTenure`]: instead of a fixed `(l, lambda)`, maintains an
+incremental Zobrist-style hash of the full color assignment (XORing in the `(vertex, color)`
+pair touched by every move) and keeps a bounded history of recently-seen hashes. Whenever the
+current hash collides with one still held in that history the search has almost certainly
+cycled back to an assignment it already visited, so tenure grows (`l` and `lambda` are scaled
+up by `growth`) to push it out of the cycle; once `decay_after` iterations pass without a
+repeat, tenure relaxes back down towards `(l_base, lambda_base)`. This is the well-known
+reactive-tenure improvement over Battiti & Tecchiolli's fixed tabu size, reported to help on
+hard DIMACS instances where a single fixed tenure either cycles (too small) or over-restricts
+the neighborhood (too large). */
+#[derive(Debug)]
+pub struct ReactiveTenure {
+    /// current fixed tabu size, grows/decays reactively (see struct doc)
+    l: usize,
+    /// current variable tabu size, grows/decays reactively alongside `l`
+    lambda: f64,
+    /// value `l` decays back towards once no repeat has been seen for `decay_after` iterations
+    l_base: usize,
+    /// value `lambda` decays back towards once no repeat has been seen for `decay_after` iterations
+    lambda_base: f64,
+    /// multiplicative factor applied to (and undone from) `l`/`lambda` on a detected repeat
+    growth: f64,
+    /// number of repeat-free iterations after which `l`/`lambda` take one decay step
+    decay_after: i64,
+    /// number of iterations since the last detected repeat (or the beginning of the search)
+    iters_since_repeat: i64,
+    /// number of iterations since the beginning of the search
+    nb_iter: i64,
+    /// decisions[v][c]: last iteration in which the decision have been taken
+    decisions: Vec<Vec<i64>>,
+    /// random number generator
+    rng: Rng,
+    /// threshold value for a given iteration
+    threshold: i64,
+    /// zobrist[v][c]: random bitstring associated to vertex v taking color c
+    zobrist: Vec<Vec<u64>>,
+    /// incremental hash of the current color assignment
+    current_hash: u64,
+    /// bounded, oldest-first history of recently-seen assignment hashes, used to detect revisits
+    history: VecDeque<u64>,
+    /// counts[h]: number of times hash h currently appears in `history`
+    counts: HashMap<u64, usize>,
+    /// maximum number of hashes kept in `history`
+    history_capacity: usize,
+    /// seed the random number generator was created with (tracked so [`Checkpoint::tabu_seed`]
+    /// can reproduce it across a checkpoint/resume cycle)
+    seed: u64,
+}
+
+impl TabuTenure<Node, Node> for ReactiveTenure {
+    fn insert(&mut self, n:&Node, d:Node) {
+        self.decisions[d.vertex][d.previous_color] = self.nb_iter;
+        self.current_hash ^= self.zobrist[d.vertex][d.previous_color] ^ self.zobrist[d.vertex][d.next_color];
+        let repeated = self.counts.contains_key(&self.current_hash);
+        self.history.push_back(self.current_hash);
+        *self.counts.entry(self.current_hash).or_insert(0) += 1;
+        if self.history.len() > self.history_capacity {
+            if let Some(old) = self.history.pop_front() {
+                if let Some(c) = self.counts.get_mut(&old) {
+                    *c -= 1;
+                    if *c == 0 { self.counts.remove(&old); }
+                }
+            }
+        }
+        if repeated {
+            self.l = (self.l as f64 * self.growth).ceil() as usize;
+            self.lambda *= self.growth;
+            self.iters_since_repeat = 0;
+        } else {
+            self.iters_since_repeat += 1;
+            if self.iters_since_repeat >= self.decay_after {
+                self.l = self.l_base.max((self.l as f64 / self.growth) as usize);
+                self.lambda = self.lambda_base.max(self.lambda / self.growth);
+                self.iters_since_repeat = 0;
+            }
+        }
+        self.threshold = self.rng.i64(0..self.l.max(1) as i64) + (self.lambda * (n.nb_conflicts as f64)) as i64;
+    }
+
+    fn contains(&mut self, _n:&Node, d:&Node) -> bool {
+        self.decisions[d.vertex][d.next_color] >= self.nb_iter - self.threshold
+    }
+}
+
+impl ReactiveTenure {
+    /** creates a reactive tabu tenure given:
+     - l: base fixed tabu size, used while no cycling is detected
+     - λ: base variable tabu size, used while no cycling is detected
+     - n: the number of vertices in the graph
+     - c: the maximum number of colors
+    */
+    pub fn new(l:usize, lambda:f64, n:usize, c:usize) -> Self {
+        Self::with_seed(l, lambda, n, c, fastrand::u64(..))
+    }
+
+    /// same as [`ReactiveTenure::new`], using a caller-provided random seed instead of one
+    /// drawn from the thread-local generator, so a checkpoint can reproduce it on resume
+    pub fn with_seed(l:usize, lambda:f64, n:usize, c:usize, seed:u64) -> Self {
+        let mut rng = Rng::with_seed(seed);
+        let zobrist = (0..n).map(|_| (0..c).map(|_| rng.u64(..)).collect()).collect();
+        Self {
+            l, lambda,
+            l_base: l, lambda_base: lambda,
+            growth: 1.5,
+            decay_after: 20,
+            iters_since_repeat: 0,
+            nb_iter: 0,
+            decisions: vec![vec![i64::MIN ; c] ; n],
+            rng,
+            threshold: 0, // will be changed later
+            zobrist,
+            current_hash: 0,
+            history: VecDeque::new(),
+            counts: HashMap::new(),
+            history_capacity: 50,
+            seed,
+        }
+    }
+
+    /// increases the number of iterations of the tabu tenure
+    pub fn increment_iter(&mut self) { self.nb_iter += 1; }
+}
+
+/// selects which [`TabuTenure`] implementation backs a local search's tabu memory
+#[derive(Debug)]
+pub enum TabuKind {
+    /// single (vertex,color) tabu tenure (historical default)
+    Classic(TabuColTenure),
+    /// combined vertex-level and (vertex,color)-level tabu tenure (see [`TwoLevelTabuTenure`])
+    TwoLevel(TwoLevelTabuTenure),
+    /// (vertex,color) tabu tenure that reactively adapts its tenure to detected cycling (see [`ReactiveTenure`])
+    Reactive(ReactiveTenure),
+}
+
+impl TabuTenure<Node, Node> for TabuKind {
+    fn insert(&mut self, n:&Node, d:Node) {
+        match self {
+            TabuKind::Classic(t) => t.insert(n, d),
+            TabuKind::TwoLevel(t) => t.insert(n, d),
+            TabuKind::Reactive(t) => t.insert(n, d),
+        }
+    }
+
+    fn contains(&mut self, n:&Node, d:&Node) -> bool {
+        match self {
+            TabuKind::Classic(t) => t.contains(n, d),
+            TabuKind::TwoLevel(t) => t.contains(n, d),
+            TabuKind::Reactive(t) => t.contains(n, d),
+        }
+    }
+}
+
+impl TabuKind {
+    /// increases the number of iterations of the underlying tabu tenure
+    fn increment_iter(&mut self) {
+        match self {
+            TabuKind::Classic(t) => t.increment_iter(),
+            TabuKind::TwoLevel(t) => t.increment_iter(),
+            TabuKind::Reactive(t) => t.increment_iter(),
+        }
+    }
+}
+
+/** tunable parameters of a [`TabuKind::Classic`] tabu tenure (see [`TabuColTenure::new`]),
+bundled together so callers of the public API can tune tenure without forking the crate.
+[`Default`] reproduces the historical hardcoded values. */
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct TabuParams {
+    /// fixed tabu size (see [`TabuColTenure`]'s `l`)
+    pub l: usize,
+    /// variable tabu size (see [`TabuColTenure`]'s `lambda`)
+    pub lambda: f64,
+}
+
+impl Default for TabuParams {
+    fn default() -> Self { Self { l:10, lambda:0.6 } }
+}
+
+/** implements a conflict weighting local search.
+
+Besides being driven to completion by [`coloring_conflict_weighting`] and its variants, this
+state can be stepped manually through [`ConflictWeightingLocalSearch::step`], letting an
+embedding application (a GUI, a service) interleave solving with its own event loop instead
+of blocking inside `run(stop)`. */
+pub struct ConflictWeightingLocalSearch {
     /// instance object
     inst:Rc<dyn ColoringInstance>,
     /// weights[u][v]: weight learned for the edge (u,v)
@@ -105,6 +445,11 @@ struct ConflictWeightingLocalSearch {
     colors_vertex_number:Vec<usize>,
     /// weights_neigh_colors[v][c]: weights of neighbors of v that are assigned color c
     weights_neigh_colors:Vec<Vec<Weight>>,
+    /// forbidden_colors[u]: colors `u` may never take, derived once at initialization from
+    /// [`ColoringInstance::allowed_colors`] (list-coloring/precoloring, see
+    /// [`crate::precoloring::PrecoloredInstance`]); empty for every vertex on an unconstrained
+    /// instance, so this has no effect unless the instance opts in
+    forbidden_colors:Vec<BitSet>,
     /// conflicting_vertices: list of vertices that have some conflict
     conflicting_vertices:SparseSet,
     /// vertex_nb_conflicts[v]: number of conflicts for the vertex v
@@ -114,7 +459,14 @@ struct ConflictWeightingLocalSearch {
     /// total weight in the current state
     total_weight:Weight,
     /// tabu list
-    tabu:TabuColTenure,
+    tabu:TabuKind,
+    /// random number generator, used for [`EscapePolicy::RandomMove`]'s reservoir sampling
+    /// (kept separate from `tabu`'s own generator since `tabu` can be either [`TabuKind`] variant)
+    rng:Rng,
+    /// seed `rng` was created with, kept around so [`ConflictWeightingLocalSearch::checkpoint`]
+    /// can make the reservoir sampling reproducible across a resume (fastrand's `Rng` does not
+    /// expose its current internal state, only the seed it was last given)
+    rng_seed:u64,
     /// threshold on the number of conflicts to disable the tabu tenure
     aspiration_criterion:i64,
     /// number of iterations
@@ -123,12 +475,102 @@ struct ConflictWeightingLocalSearch {
     nb_colors:usize,
     /// number of colors removed since the beginning of the search (best-so-far coloring)
     best_so_far_colors:usize,
+    /// weighted coloring cost ([`crate::color::weighted_coloring_cost`]) of the best-so-far
+    /// coloring; equals `best_so_far_colors` on unweighted instances, where every vertex has
+    /// weight 1
+    best_so_far_cost:usize,
+    /// strategy used to rank candidate moves
+    guide_strategy:GuideStrategy,
+    /// optional move-scoring hook biasing move selection by domain features (e.g. segment
+    /// length, vertex degree) among moves that are otherwise tied on `guide_strategy`
+    move_bias:Option<Rc<dyn Fn(VertexId) -> i64>>,
+    /// policy applied when no admissible non-tabu move exists (see [`EscapePolicy`])
+    escape_policy:EscapePolicy,
+    /// number of times `escape_policy` had to kick in (i.e. [`EscapePolicy::Stall`] would
+    /// otherwise have committed a no-op), tracked for reporting alongside the search's stats
+    escape_activations:usize,
+    /// decision selected at the end of the last [`ConflictWeightingLocalSearch::step`] call
+    /// (or the dummy initial decision if stepping has not started yet), carried across calls
+    /// so cooperative stepping picks up exactly where the previous call left off
+    current_node:Node,
+    /// wall-clock start of the search, used to timestamp [`ConflictWeightingLocalSearch::improvement_trace`]
+    clock:RunClock,
+    /// every incumbent improvement recorded so far (see [`ImprovementRecord`]), in chronological
+    /// order. Shared behind an `Rc<RefCell<_>>` (like `logger` in [`coloring_conflict_weighting`])
+    /// so a caller can keep a handle to it (via [`ConflictWeightingLocalSearch::improvement_trace_handle`])
+    /// across the search being moved into a [`StatTsCombinator`]
+    improvement_trace:Rc<RefCell<Vec<ImprovementRecord>>>,
+    /// format [`ConflictWeightingLocalSearch::update_current_solution`] uses to emit a
+    /// per-improvement progress line on stdout (see [`LogFormat`]); [`LogFormat::Text`] (the
+    /// default) is a no-op there, since text-mode progress is already printed elsewhere (e.g.
+    /// by [`MetricLogger`])
+    log_format:LogFormat,
+    /// optional callback invoked with `(solution, wall_time_secs)` each time
+    /// [`ConflictWeightingLocalSearch::update_current_solution`] records a new incumbent, so an
+    /// embedding application can stream improving solutions (e.g. upload to a remote server)
+    /// without waiting for the run to finish
+    on_new_solution:Option<Rc<RefCell<dyn FnMut(&[Vec<VertexId>], f32)>>>,
+    /// neighborhood used to escape a stall (see [`Neighborhood`]); [`Neighborhood::SingleVertex`]
+    /// (the default) leaves that to `escape_policy` as before
+    neighborhood:Neighborhood,
+    /// number of threads used to evaluate conflicting vertices' candidate recolors in
+    /// [`TotalNeighborGeneration::neighbors`]. `1` (the default) reproduces the historical
+    /// sequential behavior exactly, including every per-vertex-color tie surviving into the
+    /// move selection; values above `1` instead keep only each vertex's own single best
+    /// color (the per-vertex evaluation is independent, so it can be split across threads,
+    /// see [`best_candidates_parallel`]), which loses some of that tie diversity in exchange
+    /// for throughput — worth it only on dense instances with thousands of conflicting
+    /// vertices, where the O(conflicting * nb_colors) evaluation dominates the iteration.
+    nb_threads:usize,
+}
+
+impl std::fmt::Debug for ConflictWeightingLocalSearch {
+    fn fmt(&self, f:&mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ConflictWeightingLocalSearch")
+            .field("nb_colors", &self.nb_colors)
+            .field("best_so_far_colors", &self.best_so_far_colors)
+            .field("best_so_far_cost", &self.best_so_far_cost)
+            .field("nb_iter", &self.nb_iter)
+            .field("guide_strategy", &self.guide_strategy)
+            .field("escape_policy", &self.escape_policy)
+            .field("escape_activations", &self.escape_activations)
+            .finish()
+    }
 }
 
 impl ConflictWeightingLocalSearch {
 
-    /// initializes the data-structure from an initial solution 
-    fn initialize(inst:Rc<dyn ColoringInstance>, sol:&[Vec<VertexId>]) -> Self {
+    /// initializes the data-structure from an initial solution
+    pub fn initialize(inst:Rc<dyn ColoringInstance>, sol:&[Vec<VertexId>]) -> Self {
+        Self::initialize_with_guide(inst, sol, GuideStrategy::default())
+    }
+
+    /// initializes the data-structure from an initial solution, using a given guide strategy
+    pub fn initialize_with_guide(inst:Rc<dyn ColoringInstance>, sol:&[Vec<VertexId>], guide_strategy:GuideStrategy) -> Self {
+        Self::initialize_with_guide_and_bias(inst, sol, guide_strategy, None)
+    }
+
+    /// initializes the data-structure from an initial solution, using a given guide strategy
+    /// and an optional move-scoring bias hook (see [`ConflictWeightingLocalSearch::move_bias`])
+    pub fn initialize_with_guide_and_bias(
+        inst:Rc<dyn ColoringInstance>,
+        sol:&[Vec<VertexId>],
+        guide_strategy:GuideStrategy,
+        move_bias:Option<Rc<dyn Fn(VertexId) -> i64>>,
+    ) -> Self {
+        Self::initialize_with_guide_bias_and_escape(inst, sol, guide_strategy, move_bias, EscapePolicy::default())
+    }
+
+    /// initializes the data-structure from an initial solution, using a given guide strategy,
+    /// an optional move-scoring bias hook and an [`EscapePolicy`] for when no admissible
+    /// non-tabu move exists
+    pub fn initialize_with_guide_bias_and_escape(
+        inst:Rc<dyn ColoringInstance>,
+        sol:&[Vec<VertexId>],
+        guide_strategy:GuideStrategy,
+        move_bias:Option<Rc<dyn Fn(VertexId) -> i64>>,
+        escape_policy:EscapePolicy,
+    ) -> Self {
         // build colors & colors_bitsets
         let n = inst.nb_vertices();
         let nb_colors = sol.len();
@@ -145,8 +587,17 @@ impl ConflictWeightingLocalSearch {
         // build weights_neigh_colors
         let mut weights_neigh_colors = vec![vec![ 0 ; nb_colors] ; n];
         for u in inst.vertices() {
-            for v in inst.neighbors(u) {
-                weights_neigh_colors[u][colors[v]] += 1;
+            inst.for_each_neighbor(u, &mut |v| weights_neigh_colors[u][colors[v]] += 1);
+        }
+        let initial_color = colors[0];
+        let rng_seed = fastrand::u64(..);
+        let initial_cost = crate::color::weighted_coloring_cost(inst.as_ref(), sol);
+        let mut forbidden_colors = vec![BitSet::new() ; n];
+        for u in 0..n {
+            if let Some(allowed) = inst.allowed_colors(u) {
+                for c in 0..nb_colors {
+                    if !allowed.contains(c) { forbidden_colors[u].insert(c); }
+                }
             }
         }
         Self {
@@ -157,18 +608,94 @@ impl ConflictWeightingLocalSearch {
             colors_bitsets,
             colors_vertex_number,
             weights_neigh_colors,
+            forbidden_colors,
             conflicting_vertices: SparseSet::new(n),
             vertex_nb_conflicts: vec![0 ; n],
             nb_conflicting_edges: 0,
             total_weight: 0,
-            tabu: TabuColTenure::new(10, 0.6, n, nb_colors),
+            tabu: TabuKind::Classic(TabuColTenure::new(10, 0.6, n, nb_colors)),
+            rng: Rng::with_seed(rng_seed),
+            rng_seed,
             aspiration_criterion: i64::MAX,
             nb_iter: 0,
             nb_colors,
             best_so_far_colors: nb_colors,
+            best_so_far_cost: initial_cost,
+            guide_strategy,
+            move_bias,
+            escape_policy,
+            escape_activations: 0,
+            current_node: Node { vertex:0, previous_color:initial_color, next_color:initial_color, total_penalties:0, nb_conflicts:0, chain:Vec::new() },
+            nb_threads: 1,
+            clock: RunClock::start(),
+            improvement_trace: Rc::new(RefCell::new(vec![ImprovementRecord { time:0., iteration:0, value:nb_colors }])),
+            log_format: LogFormat::default(),
+            on_new_solution: None,
+            neighborhood: Neighborhood::default(),
         }
     }
 
+    /// same as [`ConflictWeightingLocalSearch::initialize`], but evaluating conflicting
+    /// vertices' candidate recolors across `nb_threads` threads instead of one (see the
+    /// `nb_threads` field doc for the tradeoff this makes); `nb_threads <= 1` is equivalent
+    /// to [`ConflictWeightingLocalSearch::initialize`]
+    pub fn initialize_with_threads(inst:Rc<dyn ColoringInstance>, sol:&[Vec<VertexId>], nb_threads:usize) -> Self {
+        let mut search = Self::initialize(inst, sol);
+        search.nb_threads = nb_threads.max(1);
+        search
+    }
+
+    /// same as [`ConflictWeightingLocalSearch::initialize`], emitting a per-improvement
+    /// progress line on stdout in `log_format` (see [`LogFormat`]) instead of the default
+    /// [`LogFormat::Text`], which leaves progress reporting to the caller
+    pub fn initialize_with_log_format(inst:Rc<dyn ColoringInstance>, sol:&[Vec<VertexId>], log_format:LogFormat) -> Self {
+        let mut search = Self::initialize(inst, sol);
+        search.log_format = log_format;
+        search
+    }
+
+    /// same as [`ConflictWeightingLocalSearch::initialize`], invoking `callback` with
+    /// `(solution, wall_time_secs)` each time a new incumbent is found (see
+    /// [`ConflictWeightingLocalSearch::on_new_solution`])
+    pub fn initialize_with_on_new_solution(
+        inst:Rc<dyn ColoringInstance>,
+        sol:&[Vec<VertexId>],
+        callback:Rc<RefCell<dyn FnMut(&[Vec<VertexId>], f32)>>,
+    ) -> Self {
+        let mut search = Self::initialize(inst, sol);
+        search.on_new_solution = Some(callback);
+        search
+    }
+
+    /// same as [`ConflictWeightingLocalSearch::initialize`], using `neighborhood` to escape a
+    /// stall instead of `escape_policy` (see [`Neighborhood`])
+    pub fn initialize_with_neighborhood(inst:Rc<dyn ColoringInstance>, sol:&[Vec<VertexId>], neighborhood:Neighborhood) -> Self {
+        let mut search = Self::initialize(inst, sol);
+        search.neighborhood = neighborhood;
+        search
+    }
+
+    /// same as [`ConflictWeightingLocalSearch::initialize`], using the given [`TabuKind`]
+    /// instead of the historical single (vertex,color) tabu tenure
+    pub fn initialize_with_tabu_kind(inst:Rc<dyn ColoringInstance>, sol:&[Vec<VertexId>], tabu_kind:TabuKind) -> Self {
+        let mut search = Self::initialize(inst, sol);
+        search.tabu = tabu_kind;
+        search
+    }
+
+    /// same as [`ConflictWeightingLocalSearch::initialize`], using a [`TabuKind::Classic`]
+    /// tenure built from `tabu_params` instead of the historical hardcoded `(10, 0.6)`
+    pub fn initialize_with_tabu_params(inst:Rc<dyn ColoringInstance>, sol:&[Vec<VertexId>], tabu_params:TabuParams) -> Self {
+        let n = inst.nb_vertices();
+        let nb_colors = sol.len();
+        let tabu = TabuKind::Classic(TabuColTenure::new(tabu_params.l, tabu_params.lambda, n, nb_colors));
+        Self::initialize_with_tabu_kind(inst, sol, tabu)
+    }
+
+    /// number of times [`ConflictWeightingLocalSearch::step`] had to fall back on
+    /// `escape_policy` because no admissible non-tabu move existed (see [`EscapePolicy`])
+    pub fn escape_activations(&self) -> usize { self.escape_activations }
+
     /// merges 2 colors
     fn merge_colors(&mut self) {
         loop { // invariant: self.current_sol is feasible
@@ -205,13 +732,70 @@ impl ConflictWeightingLocalSearch {
         }
     }
 
-    /// applies a move (coloring a vertex with a color)
+    /// applies a move (coloring a vertex with a color), plus every other vertex in
+    /// `node.chain` when it is a Kempe-chain move (see [`Neighborhood::KempeChain`])
     fn commit(&mut self, node:&Node) {
         // mark the move tabu
         self.tabu.insert(node, node.clone()); // make the decision tabu
         self.tabu.increment_iter();
         self.nb_iter += 1;
         self.change_vertex_color(node.vertex, node.next_color);
+        for &u in &node.chain {
+            let target = if self.colors[u] == node.previous_color { node.next_color } else { node.previous_color };
+            self.change_vertex_color(u, target);
+        }
+    }
+
+    /// picks a uniformly random vertex among `vertices_with_conflicts` and a uniformly random
+    /// other color in use, and returns the Kempe-chain interchange move swapping the two-color
+    /// connected component containing that vertex between the two colors (see
+    /// [`Neighborhood::KempeChain`]); `None` if `vertices_with_conflicts` is empty, the
+    /// chosen vertex's color is the only one in use, or the swap would give some chain vertex
+    /// one of its [`ConflictWeightingLocalSearch::forbidden_colors`]
+    fn kempe_chain_move(&mut self, vertices_with_conflicts:&[VertexId]) -> Option<Node> {
+        if vertices_with_conflicts.is_empty() { return None; }
+        let v = vertices_with_conflicts[self.rng.usize(0..vertices_with_conflicts.len())];
+        let c1 = self.colors[v];
+        let other_colors:Vec<usize> = (0..self.nb_colors)
+            .filter(|&c| c != c1 && self.colors_vertex_number[c] > 0)
+            .collect();
+        if other_colors.is_empty() { return None; }
+        let c2 = other_colors[self.rng.usize(0..other_colors.len())];
+        let chain = self.kempe_chain_component(v, c1, c2);
+        if chain.iter().any(|&u| self.forbidden_colors[u].contains(if self.colors[u] == c1 { c2 } else { c1 })) {
+            return None;
+        }
+        Some(Node {
+            vertex: v,
+            previous_color: c1,
+            next_color: c2,
+            total_penalties: self.total_weight,
+            nb_conflicts: self.nb_conflicting_edges,
+            chain: chain.into_iter().filter(|&u| u != v).collect(),
+        })
+    }
+
+    /// connected component containing `v` in the subgraph induced by the two color classes
+    /// `c1` and `c2` (vertices colored `c1` or `c2`, connected through instance edges). Every
+    /// neighbor outside this component necessarily has a color other than `c1` and `c2`
+    /// (otherwise it would be connected to the component, hence part of it), which is why
+    /// swapping the whole component between `c1` and `c2` never changes any edge's conflict
+    /// status (see [`Neighborhood::KempeChain`])
+    fn kempe_chain_component(&self, v:VertexId, c1:usize, c2:usize) -> Vec<VertexId> {
+        let mut visited = BitSet::with_capacity(self.inst.nb_vertices());
+        let mut queue = VecDeque::new();
+        visited.insert(v);
+        queue.push_back(v);
+        while let Some(u) = queue.pop_front() {
+            self.inst.for_each_neighbor(u, &mut |w| {
+                let cw = self.colors[w];
+                if (cw == c1 || cw == c2) && !visited.contains(w) {
+                    visited.insert(w);
+                    queue.push_back(w);
+                }
+            });
+        }
+        visited.iter().collect()
     }
 
 
@@ -263,6 +847,22 @@ impl ConflictWeightingLocalSearch {
         }
         self.current_sol = new_solution;
         self.best_so_far_colors = self.current_sol.iter().filter(|e| !e.is_empty()).count();
+        self.best_so_far_cost = crate::color::weighted_coloring_cost(self.inst.as_ref(), &self.current_sol);
+        self.improvement_trace.borrow_mut().push(ImprovementRecord {
+            time: self.clock.wall_secs(),
+            iteration: self.nb_iter as u64,
+            value: self.best_so_far_colors,
+        });
+        log_metrics(self.log_format, &serde_json::json!({
+            "iteration": self.nb_iter,
+            "colors": self.best_so_far_colors,
+            "conflicts": self.nb_conflicting_edges,
+            "weight": self.best_so_far_cost,
+            "time": self.clock.wall_secs(),
+        }));
+        if let Some(cb) = &self.on_new_solution {
+            (*cb.borrow_mut())(&self.current_solution(), self.clock.wall_secs());
+        }
     }
 
     /// get the learned weight of an edge
@@ -279,11 +879,175 @@ impl ConflictWeightingLocalSearch {
 
     /// true iff state is feasible
     fn is_goal(&self) -> bool { self.total_weight == 0 }
+
+    /// sums the learned edge weights incident to each vertex: a per-vertex measure of how
+    /// often it has been involved in a conflict during the search
+    fn vertex_weight_sums(&self) -> Vec<Weight> {
+        let mut sums = vec![0 ; self.inst.nb_vertices()];
+        for u in self.inst.vertices() {
+            self.inst.for_each_neighbor(u, &mut |v| sums[u] += self.get_weight(u, v));
+        }
+        sums
+    }
+
+    /** advances the search by at most `n_iters` decisions, applying the best-guide candidate
+    move returned by [`TotalNeighborGeneration::neighbors`] at each step. This is the same
+    move-selection loop that [`dogs::tree_search::greedy::Greedy`] runs internally when driven
+    to completion by `run(stop)`, exposed one call at a time so an embedding application (a
+    GUI, a service) can interleave solving with its own event loop instead of blocking.
+    Returns true as soon as a feasible (zero-conflict) coloring is reached. */
+    pub fn step(&mut self, n_iters:usize) -> bool {
+        // neighbors() merges colors as soon as the state is feasible, so a feasible coloring
+        // reached during this call shows up as a new improvement_trace entry
+        let nb_records = self.improvement_trace.borrow().len();
+        for _ in 0..n_iters {
+            let mut node = self.current_node.clone();
+            let candidates = self.neighbors(&mut node);
+            self.current_node = candidates.into_iter()
+                .min_by_key(|c| self.guide(c))
+                .expect("step: neighbors() always returns at least the dummy sentinel node");
+            if self.is_goal() || self.improvement_trace.borrow().len() > nb_records { return true; }
+        }
+        self.is_goal()
+    }
+
+    /// returns the current best-so-far feasible coloring (same value as [`ToSolution::solution`]),
+    /// useful to poll after [`ConflictWeightingLocalSearch::step`] reaches a feasible state
+    pub fn current_solution(&self) -> Vec<Vec<VertexId>> {
+        self.current_sol.iter().filter(|e| !e.is_empty()).cloned().collect()
+    }
+
+    /// weighted coloring cost ([`crate::color::weighted_coloring_cost`]) of the best-so-far
+    /// feasible coloring; equals [`ConflictWeightingLocalSearch::current_solution`]'s length on
+    /// unweighted instances
+    pub fn best_so_far_cost(&self) -> usize { self.best_so_far_cost }
+
+    /// every incumbent improvement recorded so far, in chronological order (see [`ImprovementRecord`])
+    pub fn improvement_trace(&self) -> Vec<ImprovementRecord> { self.improvement_trace.borrow().clone() }
+
+    /// a shared handle to the same [`ImprovementRecord`] trace [`ConflictWeightingLocalSearch::improvement_trace`]
+    /// reads from, kept by callers that move `self` into a [`StatTsCombinator`] (so `self` is no
+    /// longer reachable) but still want to read the trace once the search stops
+    pub fn improvement_trace_handle(&self) -> Rc<RefCell<Vec<ImprovementRecord>>> { self.improvement_trace.clone() }
+
+    /// snapshots enough state to resume the search later via [`ConflictWeightingLocalSearch::resume`].
+    /// Panics if `tabu` is [`TabuKind::TwoLevel`] or [`TabuKind::Reactive`]: checkpointing those
+    /// variants is not yet supported.
+    pub fn checkpoint(&self) -> Checkpoint {
+        let tabu = match &self.tabu {
+            TabuKind::Classic(t) => t,
+            TabuKind::TwoLevel(_) => panic!("checkpoint: TabuKind::TwoLevel is not yet supported by save_checkpoint/resume"),
+            TabuKind::Reactive(_) => panic!("checkpoint: TabuKind::Reactive is not yet supported by save_checkpoint/resume"),
+        };
+        Checkpoint {
+            current_sol: self.current_solution(),
+            weights: self.weights.clone(),
+            tabu_l: tabu.l,
+            tabu_lambda: tabu.lambda,
+            tabu_decisions: tabu.decisions.clone(),
+            tabu_seed: tabu.seed,
+            rng_seed: self.rng_seed,
+            nb_iter: self.nb_iter,
+        }
+    }
+
+    /// writes [`ConflictWeightingLocalSearch::checkpoint`] to `filename` as JSON
+    pub fn save_checkpoint(&self, filename:&str) {
+        let content = serde_json::to_string(&self.checkpoint()).unwrap();
+        std::fs::write(filename, content)
+            .unwrap_or_else(|why| panic!("save_checkpoint: unable to write {}: {}", filename, why));
+    }
+
+    /** rebuilds a search state for `inst` from the solution, learned conflict weights and tabu
+    memory saved in `checkpoint_filename` by [`ConflictWeightingLocalSearch::save_checkpoint`],
+    continuing a run interrupted by a crash instead of restarting it from scratch. `guide_strategy`
+    and `move_bias` are not part of the checkpoint (a [`GuideStrategy::Custom`] closure isn't
+    serializable) and must be supplied again, as with [`ConflictWeightingLocalSearch::initialize_with_guide_and_bias`]. */
+    pub fn resume(
+        inst:Rc<dyn ColoringInstance>,
+        guide_strategy:GuideStrategy,
+        move_bias:Option<Rc<dyn Fn(VertexId) -> i64>>,
+        checkpoint_filename:&str,
+    ) -> Self {
+        let content = std::fs::read_to_string(checkpoint_filename)
+            .unwrap_or_else(|why| panic!("resume: unable to read {}: {}", checkpoint_filename, why));
+        let checkpoint:Checkpoint = serde_json::from_str(&content)
+            .unwrap_or_else(|why| panic!("resume: unable to parse {}: {}", checkpoint_filename, why));
+        let mut search = Self::initialize_with_guide_and_bias(
+            inst, &checkpoint.current_sol, guide_strategy, move_bias
+        );
+        search.weights = checkpoint.weights;
+        // initialize built weights_neigh_colors from unit weights: rebuild it from the restored ones
+        let mut weights_neigh_colors = vec![vec![0 ; search.nb_colors] ; search.colors.len()];
+        for u in search.inst.vertices() {
+            search.inst.for_each_neighbor(u, &mut |v| weights_neigh_colors[u][search.colors[v]] += search.get_weight(u, v));
+        }
+        search.weights_neigh_colors = weights_neigh_colors;
+        let mut tabu = TabuColTenure::with_seed(
+            checkpoint.tabu_l, checkpoint.tabu_lambda, search.colors.len(), search.nb_colors, checkpoint.tabu_seed
+        );
+        tabu.decisions = checkpoint.tabu_decisions;
+        tabu.nb_iter = checkpoint.nb_iter;
+        search.tabu = TabuKind::Classic(tabu);
+        search.rng = Rng::with_seed(crate::util::resume_rng_seed(checkpoint.rng_seed, checkpoint.nb_iter));
+        search.rng_seed = checkpoint.rng_seed;
+        search.nb_iter = checkpoint.nb_iter;
+        search
+    }
+}
+
+/** serializable subset of [`ConflictWeightingLocalSearch`]'s state: the current feasible
+solution, the learned per-edge conflict weights, the tabu memory, and both random number
+generators' seeds, written to disk periodically by
+[`coloring_conflict_weighting_with_checkpointing`] so a crash during a multi-hour run loses at
+most the interval between two checkpoints, and reloaded by
+[`ConflictWeightingLocalSearch::resume`] to continue from there. */
+#[derive(Clone,Debug,Serialize,Deserialize)]
+pub struct Checkpoint {
+    /// current best-so-far feasible coloring
+    current_sol: Vec<Vec<VertexId>>,
+    /// learned per-edge conflict weights (`weights[v][u]` for `u<v`)
+    weights: Vec<Vec<Weight>>,
+    /// tabu tenure fixed size
+    tabu_l: usize,
+    /// tabu tenure dynamic factor
+    tabu_lambda: f64,
+    /// tabu memory: `tabu_decisions[v][c]` is the last iteration the move "recolor v to c" was taken
+    tabu_decisions: Vec<Vec<i64>>,
+    /// seed of the tabu tenure's random number generator
+    tabu_seed: u64,
+    /// seed of [`ConflictWeightingLocalSearch`]'s own random number generator (used for
+    /// [`EscapePolicy::RandomMove`]'s reservoir sampling, separate from the tabu tenure's)
+    rng_seed: u64,
+    /// number of local search iterations performed so far
+    nb_iter: i64,
+}
+
+/** derives a vertex elimination ordering from per-vertex learned weights, lowest-weight
+vertices first: vertices that rarely caused conflicts during the search are eliminated
+(branched on) first, which can be fed to external treewidth/branch-and-bound tools or reused
+as the branching order for the exact DSATUR search. */
+pub fn elimination_ordering_from_weights(vertex_weights:&[Weight]) -> Vec<VertexId> {
+    let mut order:Vec<VertexId> = (0..vertex_weights.len()).collect();
+    order.sort_by_key(|v| vertex_weights[*v]);
+    order
+}
+
+/// writes an elimination ordering to a file, one vertex id per line
+pub fn export_elimination_ordering(ordering:&[VertexId], filename:&str) {
+    let content:String = ordering.iter().map(|v| format!("{}\n", v)).collect();
+    std::fs::write(filename, content)
+        .unwrap_or_else(|why| panic!("export_elimination_ordering: unable to write {}: {}", filename, why));
 }
 
 impl GuidedSpace<Node, i64> for ConflictWeightingLocalSearch {
     fn guide(&mut self, node: &Node) -> i64 {
-        node.total_penalties as i64
+        let base = self.guide_strategy.evaluate(node.total_penalties as i64, node.nb_conflicts);
+        match &self.move_bias {
+            None => base,
+            // scale the base guide up so the bias only breaks ties between otherwise-equal moves
+            Some(bias) => base * 1_000_000 + bias(node.vertex),
+        }
     }
 }
 
@@ -293,6 +1057,12 @@ impl ToSolution<Node, Vec<Vec<VertexId>>> for ConflictWeightingLocalSearch {
     }
 }
 
+impl ToSolution<Node, Vec<Weight>> for ConflictWeightingLocalSearch {
+    fn solution(&mut self, _: &mut Node) -> Vec<Weight> {
+        self.vertex_weight_sums()
+    }
+}
+
 impl SearchSpace<Node, i32> for ConflictWeightingLocalSearch {
     fn initial(&mut self) -> Node {
         Node {
@@ -301,6 +1071,7 @@ impl SearchSpace<Node, i32> for ConflictWeightingLocalSearch {
             next_color: self.colors[0],
             total_penalties: 0,
             nb_conflicts: 0,
+            chain: Vec::new(),
         }
     }
     fn bound(&mut self, _node: &Node) -> i32 { self.best_so_far_colors as i32 }
@@ -308,6 +1079,77 @@ impl SearchSpace<Node, i32> for ConflictWeightingLocalSearch {
     fn g_cost(&mut self, _n: &Node) -> i32 { 0 }
 }
 
+/// a candidate recolor of a conflicting vertex, found before any tabu filtering: on a single
+/// thread every legal recolor is one; with `nb_threads > 1` only each vertex's best one (lowest
+/// `total_penalties`, ties broken by lowest color id), produced either sequentially (by
+/// [`ConflictWeightingLocalSearch::best_candidate_for_vertex`]) or across threads (by
+/// [`best_candidates_parallel`])
+#[derive(Clone,Copy,Debug)]
+struct VertexCandidate {
+    vertex:VertexId,
+    color:usize,
+    total_penalties:Weight,
+}
+
+/** splits `best_candidate_for_vertex`'s evaluation of `vertices` across `nb_threads`
+threads: each vertex's candidate only reads already-maintained incremental state
+(`colors`, `colors_vertex_number`, `weights_neigh_colors`, `total_weight`), all plain `Vec`
+data with no vertex depending on another's result, so the chunks can run fully
+independently with no merge step beyond concatenating their outputs. Takes those fields by
+reference rather than `&ConflictWeightingLocalSearch` itself, since the struct also holds an
+`Rc<dyn ColoringInstance>` (not `Sync`) that none of this computation needs. */
+fn best_candidates_parallel(
+    vertices:&[VertexId],
+    nb_threads:usize,
+    nb_colors:usize,
+    colors:&[usize],
+    colors_vertex_number:&[usize],
+    weights_neigh_colors:&[Vec<Weight>],
+    total_weight:Weight,
+    forbidden_colors:&[BitSet],
+) -> Vec<VertexCandidate> {
+    let nb_threads = nb_threads.max(1);
+    let chunk_size = vertices.len().div_ceil(nb_threads).max(1);
+    let mut results = Vec::new();
+    thread::scope(|scope| {
+        let handles:Vec<_> = vertices.chunks(chunk_size)
+            .map(|chunk| scope.spawn(move || {
+                chunk.iter().filter_map(|&u| {
+                    (0..nb_colors)
+                        .filter(|&c| c != colors[u] && colors_vertex_number[c] > 0 && !forbidden_colors[u].contains(c))
+                        .map(|c| VertexCandidate {
+                            vertex: u,
+                            color: c,
+                            total_penalties: total_weight + weights_neigh_colors[u][c] - weights_neigh_colors[u][colors[u]],
+                        })
+                        .min_by_key(|cand| cand.total_penalties)
+                }).collect::<Vec<_>>()
+            }))
+            .collect();
+        for handle in handles {
+            results.extend(handle.join().unwrap());
+        }
+    });
+    results
+}
+
+impl ConflictWeightingLocalSearch {
+    /// best (lowest `total_penalties`) recolor of `u` among colors currently in use other
+    /// than its own and allowed by [`ConflictWeightingLocalSearch::forbidden_colors`], or
+    /// `None` if none exists; the sequential counterpart of [`best_candidates_parallel`], used
+    /// when `self.nb_threads > 1` but fewer vertices conflict than there are threads
+    fn best_candidate_for_vertex(&self, u:VertexId) -> Option<VertexCandidate> {
+        (0..self.nb_colors)
+            .filter(|&c| c != self.colors[u] && self.colors_vertex_number[c] > 0 && !self.forbidden_colors[u].contains(c))
+            .map(|c| VertexCandidate {
+                vertex: u,
+                color: c,
+                total_penalties: self.total_weight + self.weights_neigh_colors[u][c] - self.weights_neigh_colors[u][self.colors[u]],
+            })
+            .min_by_key(|cand| cand.total_penalties)
+    }
+}
+
 impl TotalNeighborGeneration<Node> for ConflictWeightingLocalSearch {
     fn neighbors(&mut self, node: &mut Node) -> Vec<Node> {
         if node.previous_color != node.next_color { // if not a dummy decision, commit it
@@ -317,39 +1159,95 @@ impl TotalNeighborGeneration<Node> for ConflictWeightingLocalSearch {
             assert!(self.is_goal()); // the search state should be a goal here
             self.merge_colors();
         }
-        let mut best_nodes = vec![
-            Node {vertex:0, previous_color:0, next_color:0, total_penalties:Weight::MAX, nb_conflicts:0}
-        ];
+        // drop conflicting_vertices entries that turned out not to conflict any more, and
+        // collect the ones that still do (cheap pointer-chasing over the sparse set, kept
+        // sequential; the expensive part is the per-vertex move evaluation below)
+        let mut vertices_with_conflicts = Vec::new();
         let mut i = 0;
-        while i < self.conflicting_vertices.len() { // iterate over conflicting vertices
+        while i < self.conflicting_vertices.len() {
             let u = self.conflicting_vertices.nth(i);
-            if self.vertex_nb_conflicts[u] > 0 { // u has indeed some conflicts
-                // for each vertex, try changing its color by an existing other color
-                for c in 0..self.nb_colors {
-                    if c != self.colors[u] && self.colors_vertex_number[c] > 0 {
-                        let current_penalties:Weight = self.total_weight +
-                            self.weights_neigh_colors[u][c] - self.weights_neigh_colors[u][self.colors[u]];
-                        if current_penalties <= best_nodes[0].total_penalties {
-                            let current_node = Node {
-                                vertex:u,
-                                previous_color:self.colors[u],
-                                next_color:c,
-                                total_penalties:current_penalties,
-                                nb_conflicts: self.nb_conflicting_edges
-                            };
-                            let is_tabu = self.tabu.contains(&current_node, &current_node);
-                            if !is_tabu || self.nb_conflicting_edges < self.aspiration_criterion {
-                                if current_penalties < best_nodes[0].total_penalties {
-                                    best_nodes.clear();
-                                }
-                                best_nodes.push(current_node); 
-                            }
-                        }
+            if self.vertex_nb_conflicts[u] > 0 {
+                vertices_with_conflicts.push(u);
+                i += 1;
+            } else {
+                self.conflicting_vertices.remove(u);
+            }
+        }
+        // the O(|conflicting| * nb_colors) move evaluation itself, optionally split across
+        // self.nb_threads threads (see best_candidates_parallel's doc for what this trades off)
+        let candidates = if self.nb_threads > 1 && vertices_with_conflicts.len() >= self.nb_threads {
+            best_candidates_parallel(
+                &vertices_with_conflicts, self.nb_threads, self.nb_colors,
+                &self.colors, &self.colors_vertex_number, &self.weights_neigh_colors, self.total_weight,
+                &self.forbidden_colors,
+            )
+        } else if self.nb_threads > 1 {
+            vertices_with_conflicts.iter().filter_map(|&u| self.best_candidate_for_vertex(u)).collect()
+        } else {
+            // single thread: every legal recolor of every conflicting vertex, so ties between
+            // colors of the same vertex all reach the move selection below
+            let mut all = Vec::new();
+            for &u in &vertices_with_conflicts {
+                for c in (0..self.nb_colors).filter(|&c| c != self.colors[u] && self.colors_vertex_number[c] > 0 && !self.forbidden_colors[u].contains(c)) {
+                    all.push(VertexCandidate {
+                        vertex: u,
+                        color: c,
+                        total_penalties: self.total_weight + self.weights_neigh_colors[u][c] - self.weights_neigh_colors[u][self.colors[u]],
+                    });
+                }
+            }
+            all
+        };
+        let mut best_nodes = vec![
+            Node {vertex:0, previous_color:0, next_color:0, total_penalties:Weight::MAX, nb_conflicts:self.nb_conflicting_edges, chain:Vec::new()}
+        ];
+        // tracked alongside best_nodes so EscapePolicy can fall back to them when no
+        // admissible non-tabu move was found (best_nodes still only holds the dummy above)
+        let mut best_ignoring_tabu:Option<Node> = None;
+        let mut random_candidate:Option<Node> = None;
+        let mut nb_candidates:u64 = 0;
+        for candidate in candidates {
+            let u = candidate.vertex;
+            if candidate.total_penalties <= best_nodes[0].total_penalties {
+                let current_node = Node {
+                    vertex:u,
+                    previous_color:self.colors[u],
+                    next_color:candidate.color,
+                    total_penalties:candidate.total_penalties,
+                    nb_conflicts: self.nb_conflicting_edges,
+                    chain: Vec::new(),
+                };
+                if best_ignoring_tabu.as_ref().map_or(true, |b| current_node.total_penalties < b.total_penalties) {
+                    best_ignoring_tabu = Some(current_node.clone());
+                }
+                nb_candidates += 1; // reservoir sampling, for EscapePolicy::RandomMove
+                if self.rng.u64(0..nb_candidates) == 0 {
+                    random_candidate = Some(current_node.clone());
+                }
+                let is_tabu = self.tabu.contains(&current_node, &current_node);
+                if !is_tabu || self.nb_conflicting_edges < self.aspiration_criterion {
+                    if current_node.total_penalties < best_nodes[0].total_penalties {
+                        best_nodes.clear();
                     }
+                    best_nodes.push(current_node);
                 }
-                i += 1;
+            }
+        }
+        // no admissible non-tabu move: escape per self.neighborhood/self.escape_policy instead
+        // of letting the dummy no-op stall the search (see Neighborhood, EscapePolicy)
+        if best_nodes.len() == 1 && best_nodes[0].previous_color == best_nodes[0].next_color && self.nb_conflicting_edges > 0 {
+            let escaped = if self.neighborhood == Neighborhood::KempeChain {
+                self.kempe_chain_move(&vertices_with_conflicts)
             } else {
-                self.conflicting_vertices.remove(u); // update conflicting_vertices if it has no conflict
+                match self.escape_policy {
+                    EscapePolicy::Stall => None,
+                    EscapePolicy::BestTabuMove => best_ignoring_tabu,
+                    EscapePolicy::RandomMove => random_candidate,
+                }
+            };
+            if let Some(node) = escaped {
+                self.escape_activations += 1;
+                best_nodes = vec![node];
             }
         }
         best_nodes
@@ -364,13 +1262,58 @@ sol:&[Vec<VertexId>],
 perf_filename:Option<String>,
 sol_filename:Option<String>,
 stop:Stopping
+) -> Vec<Vec<VertexId>> {
+    coloring_conflict_weighting_with_guide(inst, sol, perf_filename, sol_filename, stop, GuideStrategy::default())
+}
+
+/** performs a conflict weighting local search, ranking candidate moves using the given guide strategy. */
+pub fn coloring_conflict_weighting_with_guide<Stopping:StoppingCriterion>(
+inst:Rc<dyn ColoringInstance>,
+sol:&[Vec<VertexId>],
+perf_filename:Option<String>,
+sol_filename:Option<String>,
+stop:Stopping,
+guide_strategy:GuideStrategy,
+) -> Vec<Vec<VertexId>> {
+    coloring_conflict_weighting_with_guide_and_bias(inst, sol, perf_filename, sol_filename, stop, guide_strategy, None)
+}
+
+/** performs a conflict weighting local search, ranking candidate moves using the given guide
+strategy and breaking ties between otherwise-equal moves using a domain-feature move-scoring
+hook (e.g. prefer recoloring short segments or low-degree vertices first). */
+pub fn coloring_conflict_weighting_with_guide_and_bias<Stopping:StoppingCriterion>(
+inst:Rc<dyn ColoringInstance>,
+sol:&[Vec<VertexId>],
+perf_filename:Option<String>,
+sol_filename:Option<String>,
+stop:Stopping,
+guide_strategy:GuideStrategy,
+move_bias:Option<Rc<dyn Fn(VertexId) -> i64>>,
+) -> Vec<Vec<VertexId>> {
+    coloring_conflict_weighting_with_guide_bias_and_ordering_export(
+        inst, sol, perf_filename, sol_filename, stop, guide_strategy, move_bias, None
+    )
+}
+
+/** same as [`coloring_conflict_weighting_with_guide_and_bias`], additionally exporting the
+weight-guided elimination ordering (see [`elimination_ordering_from_weights`]) of the final
+search state to `ordering_filename`, if given. */
+pub fn coloring_conflict_weighting_with_guide_bias_and_ordering_export<Stopping:StoppingCriterion>(
+inst:Rc<dyn ColoringInstance>,
+sol:&[Vec<VertexId>],
+perf_filename:Option<String>,
+sol_filename:Option<String>,
+stop:Stopping,
+guide_strategy:GuideStrategy,
+move_bias:Option<Rc<dyn Fn(VertexId) -> i64>>,
+ordering_filename:Option<String>,
 ) -> Vec<Vec<VertexId>> {
     let mut solution:Vec<Vec<VertexId>> = sol.to_vec();
     let logger = Rc::new(MetricLogger::default());
+    let search = ConflictWeightingLocalSearch::initialize_with_guide_and_bias(inst.clone(), &solution, guide_strategy, move_bias);
+    let trace_handle = search.improvement_trace_handle();
     let space = Rc::new(RefCell::new(
-        StatTsCombinator::new(
-            ConflictWeightingLocalSearch::initialize(inst.clone(), &solution),
-        ).bind_logger(Rc::downgrade(&logger)),
+        StatTsCombinator::new(search).bind_logger(Rc::downgrade(&logger)),
     ));
     let mut ts = Greedy::new(space.clone());
     logger.display_headers();
@@ -385,29 +1328,221 @@ stop:Stopping
         Some(node) => {
             assert_eq!(node.nb_conflicts, 0);
             solution = space.borrow_mut().solution(&mut node.clone());
-        }  
+            if let Some(filename) = &ordering_filename {
+                let vertex_weights:Vec<Weight> = space.borrow_mut().solution(&mut node.clone());
+                let ordering = elimination_ordering_from_weights(&vertex_weights);
+                export_elimination_ordering(&ordering, filename);
+            }
+        }
+    }
+    let mut stats = serde_json::Value::default();
+    space.borrow_mut().json_statistics(&mut stats);
+    export_results_with_trace(
+        inst,
+        &solution,
+        &stats,
+        perf_filename,
+        sol_filename,
+        true,
+        &trace_handle.borrow(),
+    );
+    solution
+}
+
+/** same as [`coloring_conflict_weighting`], but using a [`TabuKind::Classic`] tenure built
+from `tabu_params` instead of the historical hardcoded `(10, 0.6)`, so callers can tune tenure
+without forking the crate. */
+pub fn coloring_conflict_weighting_with_tabu_params<Stopping:StoppingCriterion>(
+inst:Rc<dyn ColoringInstance>,
+sol:&[Vec<VertexId>],
+perf_filename:Option<String>,
+sol_filename:Option<String>,
+stop:Stopping,
+tabu_params:TabuParams,
+) -> Vec<Vec<VertexId>> {
+    let mut solution:Vec<Vec<VertexId>> = sol.to_vec();
+    let logger = Rc::new(MetricLogger::default());
+    let search = ConflictWeightingLocalSearch::initialize_with_tabu_params(inst.clone(), &solution, tabu_params);
+    let trace_handle = search.improvement_trace_handle();
+    let space = Rc::new(RefCell::new(
+        StatTsCombinator::new(search).bind_logger(Rc::downgrade(&logger)),
+    ));
+    let mut ts = Greedy::new(space.clone());
+    logger.display_headers();
+    ts.run(stop);
+    // display the results afterwards
+    space.borrow_mut().display_statistics();
+    // check that the last solution is valid
+    match ts.get_manager().best() {
+        None => {
+            println!("\tlocal search failed improving...");
+        }
+        Some(node) => {
+            assert_eq!(node.nb_conflicts, 0);
+            solution = space.borrow_mut().solution(&mut node.clone());
+        }
+    }
+    let mut stats = serde_json::Value::default();
+    space.borrow_mut().json_statistics(&mut stats);
+    export_results_with_trace(
+        inst,
+        &solution,
+        &stats,
+        perf_filename,
+        sol_filename,
+        true,
+        &trace_handle.borrow(),
+    );
+    solution
+}
+
+/** same as [`coloring_conflict_weighting`], but emitting one JSON object per improvement on
+stdout instead of [`MetricLogger`]'s tabular output, so downstream tooling can monitor a run's
+progress (iteration, colors, conflicts, weight, time) without screen-scraping. Under
+[`LogFormat::Json`], [`MetricLogger`]'s own header/table is skipped so stdout stays a clean
+stream of JSON objects; [`LogFormat::Text`] reproduces [`coloring_conflict_weighting`]'s
+historical output exactly. */
+pub fn coloring_conflict_weighting_with_log_format<Stopping:StoppingCriterion>(
+inst:Rc<dyn ColoringInstance>,
+sol:&[Vec<VertexId>],
+perf_filename:Option<String>,
+sol_filename:Option<String>,
+stop:Stopping,
+log_format:LogFormat,
+) -> Vec<Vec<VertexId>> {
+    let mut solution:Vec<Vec<VertexId>> = sol.to_vec();
+    let logger = Rc::new(MetricLogger::default());
+    let search = ConflictWeightingLocalSearch::initialize_with_log_format(inst.clone(), &solution, log_format);
+    let trace_handle = search.improvement_trace_handle();
+    let space = Rc::new(RefCell::new(
+        StatTsCombinator::new(search).bind_logger(Rc::downgrade(&logger)),
+    ));
+    let mut ts = Greedy::new(space.clone());
+    if log_format == LogFormat::Text { logger.display_headers(); }
+    ts.run(stop);
+    // display the results afterwards
+    if log_format == LogFormat::Text { space.borrow_mut().display_statistics(); }
+    // check that the last solution is valid
+    match ts.get_manager().best() {
+        None => {
+            println!("\tlocal search failed improving...");
+        }
+        Some(node) => {
+            assert_eq!(node.nb_conflicts, 0);
+            solution = space.borrow_mut().solution(&mut node.clone());
+        }
     }
     let mut stats = serde_json::Value::default();
     space.borrow_mut().json_statistics(&mut stats);
-    export_results(
+    export_results_with_trace(
         inst,
         &solution,
         &stats,
         perf_filename,
         sol_filename,
-        true
+        true,
+        &trace_handle.borrow(),
     );
     solution
 }
 
+/** same objective as [`coloring_conflict_weighting`], but drives the search through repeated
+[`ConflictWeightingLocalSearch::step`] calls instead of [`dogs::tree_search::greedy::Greedy`],
+writing a [`ConflictWeightingLocalSearch::checkpoint`] to `checkpoint_filename` every
+`checkpoint_interval_secs` seconds of wall-clock time so a crash during a multi-hour
+competition run loses at most that much progress. `time_limit_secs` is measured against
+`time_basis` (wall-clock or process CPU time, see [`TimeBasis`]), so single- and
+multi-threaded variants can be budgeted fairly; both clocks are reported in the exported
+stats regardless of which one gated the stop. If `resume` is true, `checkpoint_filename` is
+loaded as the starting state (via [`ConflictWeightingLocalSearch::resume`]) instead of
+initializing fresh from `sol`/`guide_strategy`. */
+pub fn coloring_conflict_weighting_with_checkpointing(
+inst:Rc<dyn ColoringInstance>,
+sol:&[Vec<VertexId>],
+guide_strategy:GuideStrategy,
+time_limit_secs:f32,
+time_basis:TimeBasis,
+checkpoint_interval_secs:f32,
+checkpoint_filename:&str,
+resume:bool,
+perf_filename:Option<String>,
+sol_filename:Option<String>,
+) -> Vec<Vec<VertexId>> {
+    let mut search = if resume {
+        ConflictWeightingLocalSearch::resume(inst.clone(), guide_strategy, None, checkpoint_filename)
+    } else {
+        ConflictWeightingLocalSearch::initialize_with_guide(inst.clone(), sol, guide_strategy)
+    };
+    let clock = RunClock::start();
+    let mut last_checkpoint = Instant::now();
+    loop {
+        let is_over = clock.elapsed_secs(time_basis) >= time_limit_secs;
+        search.step(1_000);
+        if is_over || last_checkpoint.elapsed().as_secs_f32() >= checkpoint_interval_secs {
+            search.save_checkpoint(checkpoint_filename);
+            last_checkpoint = Instant::now();
+        }
+        if is_over { break; }
+    }
+    let solution = search.current_solution();
+    let stats = serde_json::json!({
+        "wall_time_secs": clock.wall_secs(),
+        "cpu_time_secs": clock.cpu_secs(),
+        "peak_rss_growth_bytes": clock.peak_rss_growth_bytes(),
+    });
+    export_results_with_trace(inst, &solution, &stats, perf_filename, sol_filename, true, &search.improvement_trace());
+    solution
+}
+
+/** same objective as [`coloring_conflict_weighting`], but first spends `clique_time_secs`
+improving a [`greedy_clique`] with
+[`clique_partial_weighting`](crate::solvers::clique::partial_weighting::clique_partial_weighting)
+to get a chromatic lower bound, records it as `"lower_bound"` in the exported stats
+(`"gap"` alongside it, the difference to the current best coloring), and stops the local
+search as soon as its current solution matches it: no coloring can use fewer colors than a
+clique already found in the graph, so the search can declare victory rather than running out
+the rest of `time_limit_secs`. `time_limit_secs` is measured against `time_basis` (see
+[`TimeBasis`]) and does not include `clique_time_secs`, spent up front. */
+pub fn coloring_conflict_weighting_with_lower_bound(
+inst:Rc<dyn ColoringInstance>,
+sol:&[Vec<VertexId>],
+time_limit_secs:f32,
+time_basis:TimeBasis,
+clique_time_secs:f32,
+perf_filename:Option<String>,
+sol_filename:Option<String>,
+) -> Vec<Vec<VertexId>> {
+    let greedy = greedy_clique(inst.clone());
+    let improved_clique = clique_partial_weighting(
+        inst.clone(), &greedy, None, None, TimeStoppingCriterion::new(clique_time_secs)
+    ).remove(0);
+    let lower_bound = improved_clique.len();
+    let mut search = ConflictWeightingLocalSearch::initialize(inst.clone(), sol);
+    let clock = RunClock::start();
+    while search.current_solution().len() > lower_bound
+    && clock.elapsed_secs(time_basis) < time_limit_secs {
+        search.step(1_000);
+    }
+    let solution = search.current_solution();
+    let stats = serde_json::json!({
+        "wall_time_secs": clock.wall_secs(),
+        "cpu_time_secs": clock.cpu_secs(),
+        "lower_bound": lower_bound,
+        "gap": solution.len() as i64 - lower_bound as i64,
+        "peak_rss_growth_bytes": clock.peak_rss_growth_bytes(),
+    });
+    export_results_with_trace(inst, &solution, &stats, perf_filename, sol_filename, true, &search.improvement_trace());
+    solution
+}
+
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
     use dogs::search_algorithm::TimeStoppingCriterion;
-    
-    use crate::{cgshop::CGSHOPInstance, solvers::coloring::greedy_dsatur::greedy_dsatur};
+
+    use crate::{cgshop::CGSHOPInstance, color::{checker, CheckerResult}, solvers::coloring::greedy_dsatur::greedy_dsatur};
 
     #[test]
     fn test_cwls() {
@@ -437,5 +1572,179 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_cwls_with_threads_reaches_goal() {
+        use crate::dimacs::DimacsInstance;
+        let inst:Rc<dyn ColoringInstance> = Rc::new(DimacsInstance::from_file("insts/grid-instances/grid2x2"));
+        let greedy_sol = greedy_dsatur(inst.clone(), false);
+        let mut search = ConflictWeightingLocalSearch::initialize_with_threads(inst, &greedy_sol, 4);
+        assert!(search.step(1_000));
+    }
+
+    #[test]
+    fn test_cwls_with_tabu_params_reaches_goal() {
+        use crate::dimacs::DimacsInstance;
+        let inst:Rc<dyn ColoringInstance> = Rc::new(DimacsInstance::from_file("insts/grid-instances/grid2x2"));
+        let greedy_sol = greedy_dsatur(inst.clone(), false);
+        let mut search = ConflictWeightingLocalSearch::initialize_with_tabu_params(
+            inst, &greedy_sol, TabuParams { l:5, lambda:0.3 }
+        );
+        assert!(search.step(1_000));
+    }
+
+    #[test]
+    fn test_cwls_with_log_format_json_reaches_goal() {
+        use crate::dimacs::DimacsInstance;
+        let inst:Rc<dyn ColoringInstance> = Rc::new(DimacsInstance::from_file("insts/grid-instances/grid2x2"));
+        let greedy_sol = greedy_dsatur(inst.clone(), false);
+        let mut search = ConflictWeightingLocalSearch::initialize_with_log_format(inst, &greedy_sol, LogFormat::Json);
+        assert!(search.step(1_000));
+    }
+
+    #[test]
+    fn test_cwls_with_on_new_solution_reports_incumbents() {
+        use crate::dimacs::DimacsInstance;
+        let inst:Rc<dyn ColoringInstance> = Rc::new(DimacsInstance::from_file("insts/grid-instances/grid2x2"));
+        let greedy_sol = greedy_dsatur(inst.clone(), false);
+        let calls = Rc::new(RefCell::new(0usize));
+        let calls_handle = calls.clone();
+        let callback:Rc<RefCell<dyn FnMut(&[Vec<VertexId>], f32)>> = Rc::new(RefCell::new(
+            move |_sol:&[Vec<VertexId>], _time:f32| { *calls_handle.borrow_mut() += 1; }
+        ));
+        let mut search = ConflictWeightingLocalSearch::initialize_with_on_new_solution(inst, &greedy_sol, callback);
+        assert!(search.step(1_000));
+        assert!(*calls.borrow() > 0);
+    }
+
+    #[test]
+    fn test_cwls_with_kempe_chain_neighborhood_reaches_goal() {
+        use crate::dimacs::DimacsInstance;
+        let inst:Rc<dyn ColoringInstance> = Rc::new(DimacsInstance::from_file("insts/grid-instances/grid2x2"));
+        let greedy_sol = greedy_dsatur(inst.clone(), false);
+        let mut search = ConflictWeightingLocalSearch::initialize_with_neighborhood(
+            inst, &greedy_sol, Neighborhood::KempeChain
+        );
+        assert!(search.step(1_000));
+    }
+
+    #[test]
+    fn test_cwls_with_reactive_tenure_reaches_goal() {
+        use crate::dimacs::DimacsInstance;
+        let inst:Rc<dyn ColoringInstance> = Rc::new(DimacsInstance::from_file("insts/grid-instances/grid2x2"));
+        let greedy_sol = greedy_dsatur(inst.clone(), false);
+        let n = inst.nb_vertices();
+        let nb_colors = greedy_sol.len();
+        let tabu = TabuKind::Reactive(ReactiveTenure::new(5, 0.3, n, nb_colors));
+        let mut search = ConflictWeightingLocalSearch::initialize_with_tabu_kind(inst, &greedy_sol, tabu);
+        assert!(search.step(1_000));
+    }
+
+    #[test]
+    fn test_cwls_improvement_trace_is_monotonically_decreasing() {
+        use crate::dimacs::DimacsInstance;
+        let inst:Rc<dyn ColoringInstance> = Rc::new(DimacsInstance::from_file("insts/grid-instances/grid2x2"));
+        let greedy_sol = greedy_dsatur(inst.clone(), false);
+        let mut search = ConflictWeightingLocalSearch::initialize(inst, &greedy_sol);
+        assert!(search.step(1_000));
+        let trace = search.improvement_trace();
+        assert!(trace.len() > 1);
+        for (a, b) in trace.iter().zip(trace.iter().skip(1)) {
+            assert!(b.value <= a.value);
+            assert!(b.iteration >= a.iteration);
+        }
+    }
+
+    #[test]
+    fn test_cwls_never_proposes_a_forbidden_color() {
+        use crate::dimacs::DimacsInstance;
+        use crate::precoloring::PrecoloredInstance;
+        let mut precolored = PrecoloredInstance::new(
+            Rc::new(DimacsInstance::from_file("insts/grid-instances/grid2x2"))
+        );
+        precolored.fix(0, 3);
+        let inst:Rc<dyn ColoringInstance> = Rc::new(precolored);
+        let greedy_sol = greedy_dsatur(inst.clone(), false);
+        let mut search = ConflictWeightingLocalSearch::initialize(inst, &greedy_sol);
+        assert!(search.step(1_000));
+        assert_eq!(search.colors[0], 3);
+    }
+
+    #[test]
+    fn test_with_lower_bound_stops_immediately_when_already_optimal() {
+        use crate::dimacs::DimacsInstance;
+        let inst:Rc<dyn ColoringInstance> = Rc::new(DimacsInstance::from_file("insts/grid-instances/grid2x2"));
+        let greedy_sol = greedy_dsatur(inst.clone(), false);
+        // grid2x2 is bipartite: the greedy solution is already a maximum clique's worth of colors
+        let solution = coloring_conflict_weighting_with_lower_bound(
+            inst.clone(), &greedy_sol, 3600., TimeBasis::Wall, 1., None, None
+        );
+        assert_eq!(solution.len(), greedy_sol.len());
+    }
+
+    #[test]
+    fn test_checkpoint_resume_restores_rng_seed() {
+        use crate::dimacs::DimacsInstance;
+        let inst:Rc<dyn ColoringInstance> = Rc::new(DimacsInstance::from_file("insts/grid-instances/grid2x2"));
+        let greedy_sol = greedy_dsatur(inst.clone(), false);
+        let mut search = ConflictWeightingLocalSearch::initialize(inst.clone(), &greedy_sol);
+        search.step(10);
+        let filename = std::env::temp_dir().join("dogs_color_test_cwls_checkpoint.json");
+        let filename = filename.to_str().unwrap();
+        search.save_checkpoint(filename);
+        let resumed = ConflictWeightingLocalSearch::resume(inst, GuideStrategy::Weight, None, filename);
+        assert_eq!(resumed.rng_seed, search.rng_seed);
+        assert_eq!(resumed.current_solution().len(), search.current_solution().len());
+    }
+
+    #[test]
+    fn test_checkpoint_resume_does_not_replay_the_original_draw_stream() {
+        use crate::dimacs::DimacsInstance;
+        let inst:Rc<dyn ColoringInstance> = Rc::new(DimacsInstance::from_file("insts/grid-instances/grid2x2"));
+        let greedy_sol = greedy_dsatur(inst.clone(), false);
+        let mut search = ConflictWeightingLocalSearch::initialize(inst.clone(), &greedy_sol);
+        search.step(10);
+        let filename = std::env::temp_dir().join("dogs_color_test_cwls_checkpoint_rng.json");
+        let filename = filename.to_str().unwrap();
+        search.save_checkpoint(filename);
+        let mut resumed = ConflictWeightingLocalSearch::resume(inst, GuideStrategy::Weight, None, filename);
+        // rewinding to the raw original seed would replay the exact draws already consumed
+        // near the start of the original run; resuming must draw a different sequence instead
+        let mut rewound = Rng::with_seed(search.rng_seed);
+        let draws_if_rewound:Vec<u64> = (0..10).map(|_| rewound.u64(..)).collect();
+        let draws_from_resumed:Vec<u64> = (0..10).map(|_| resumed.rng.u64(..)).collect();
+        assert_ne!(draws_if_rewound, draws_from_resumed);
+    }
+
+    #[test]
+    fn test_checkpoint_resume_on_cgshop_instance() {
+        // checkpointing is exercised above on a DIMACS instance; CGSHOP runs are the ones that
+        // actually last hours and need it, so make sure the same round-trip works there too
+        let inst:Rc<dyn ColoringInstance> = Rc::new(CGSHOPInstance::from_file(
+            "./insts/cgshop_22_examples/tiny10.instance.json"
+        ));
+        let greedy_sol = greedy_dsatur(inst.clone(), false);
+        let mut search = ConflictWeightingLocalSearch::initialize(inst.clone(), &greedy_sol);
+        search.step(10);
+        let filename = std::env::temp_dir().join("dogs_color_test_cwls_cgshop_checkpoint.json");
+        let filename = filename.to_str().unwrap();
+        search.save_checkpoint(filename);
+        let resumed = ConflictWeightingLocalSearch::resume(inst, GuideStrategy::Weight, None, filename);
+        assert_eq!(resumed.rng_seed, search.rng_seed);
+        assert_eq!(resumed.current_solution().len(), search.current_solution().len());
+    }
+
+    #[test]
+    fn test_coloring_conflict_weighting_with_lower_bound_reaches_clique_size_on_grid2x2() {
+        // grid2x2 is a 4-cycle: clique number 2, chromatic number 2, so the search should stop
+        // as soon as it reaches the lower bound instead of running out its full time budget
+        use crate::dimacs::DimacsInstance;
+        let inst:Rc<dyn ColoringInstance> = Rc::new(DimacsInstance::from_file("insts/grid-instances/grid2x2"));
+        let greedy_sol = greedy_dsatur(inst.clone(), false);
+        let solution = coloring_conflict_weighting_with_lower_bound(
+            inst.clone(), &greedy_sol, 60., TimeBasis::Wall, 0.2, None, None
+        );
+        assert_eq!(solution.len(), 2);
+        assert_eq!(checker(inst, &solution), CheckerResult::Ok(solution.len()));
+    }
 }
 
@@ -0,0 +1,413 @@
+use std::{cell::RefCell, collections::{HashMap, HashSet, VecDeque}, rc::Rc};
+
+use bit_set::BitSet;
+use fastrand::Rng;
+
+use dogs::{
+    combinators::{helper::tabu_tenure::TabuTenure, stats::StatTsCombinator},
+    metric_logger::MetricLogger,
+    search_algorithm::{SearchAlgorithm, StoppingCriterion},
+    search_space::{GuidedSpace, SearchSpace, ToSolution, TotalNeighborGeneration},
+    tree_search::greedy::Greedy,
+};
+
+use crate::{
+    color::{ColoringInstance, VertexId},
+    solvers::coloring::state::ColoringState,
+    util::export_results,
+};
+
+/** a single ejection-chain move: `vertex` takes `next_color`, displacing any neighbor
+already wearing it to its own best alternate color, which may in turn displace someone else,
+cascading up to [`EjectionChainLocalSearch`]'s chain-length cap. `chain` holds every vertex
+displaced this way (in the order it was displaced), excluding `vertex` itself. */
+#[derive(Debug,Clone,Eq,PartialEq,Hash)]
+struct Node {
+    /// vertex the chain is rooted at
+    vertex:VertexId,
+    /// color `vertex` wore before the move
+    previous_color:usize,
+    /// color `vertex` is moved to
+    next_color:usize,
+    /// every vertex displaced along the chain, as `(vertex, previous_color, next_color)`, in
+    /// the order the chain reached them
+    chain:Vec<(VertexId, usize, usize)>,
+    /// number of conflicting edges the whole chain would leave behind, were it applied
+    nb_conflicts:i64,
+}
+
+/** tabu tenure for the ejection-chain search: forbids moving a chain's root vertex back to
+the color it just left, for a number of iterations drawn uniformly from `[0, l)` plus
+`lambda` times the number of conflicts the move left behind. Only the root is made tabu; the
+vertices it displaces along the way stay free to move again immediately, since they weren't
+chosen, just squeezed out. This is the same `(l, lambda)` scheme
+[`crate::solvers::coloring::conflict_weighting::TabuColTenure`] uses, reimplemented here since
+that one is tied to `conflict_weighting`'s own `Node` type. */
+#[derive(Debug)]
+struct EjectionTabuTenure {
+    l:usize,
+    lambda:f64,
+    nb_iter:i64,
+    /// decisions[v][c]: last iteration the move "recolor v to c" was taken
+    decisions:Vec<Vec<i64>>,
+    rng:Rng,
+    threshold:i64,
+}
+
+impl TabuTenure<Node, Node> for EjectionTabuTenure {
+    fn insert(&mut self, n:&Node, d:Node) {
+        self.decisions[d.vertex][d.previous_color] = self.nb_iter;
+        self.threshold = self.rng.i64(0..self.l as i64) + (self.lambda * (n.nb_conflicts as f64)) as i64;
+    }
+
+    fn contains(&mut self, _n:&Node, d:&Node) -> bool {
+        self.decisions[d.vertex][d.next_color] >= self.nb_iter - self.threshold
+    }
+}
+
+impl EjectionTabuTenure {
+    fn new(l:usize, lambda:f64, n:usize, c:usize) -> Self {
+        Self {
+            l, lambda,
+            nb_iter: 0,
+            decisions: vec![vec![i64::MIN ; c] ; n],
+            rng: Rng::with_seed(fastrand::u64(..)),
+            threshold: 0,
+        }
+    }
+
+    fn increment_iter(&mut self) { self.nb_iter += 1; }
+}
+
+/** ejection-chain local search for the vertex coloring problem: instead of recoloring a
+single conflicting vertex at a time, tries to make room for it by displacing whichever
+same-colored neighbors stand in the way, each displaced vertex in turn taking its own best
+available color, cascading until the chain either resolves cleanly or hits
+[`EjectionChainLocalSearch::max_chain_length`]. Built on top of [`ColoringState`] for the
+incremental color/conflict bookkeeping, conflicting vertices are visited round-robin and the
+target color tried for a given vertex is cycled after every attempt (rather than always
+picked by weight, as [`crate::solvers::coloring::conflict_weighting`] does), so repeated
+chains rooted at the same vertex explore every color in turn instead of converging on one. */
+pub struct EjectionChainLocalSearch {
+    /// instance object
+    inst:Rc<dyn ColoringInstance>,
+    /// incremental color/conflict bookkeeping
+    state:ColoringState,
+    /// current best feasible solution
+    current_sol:Vec<Vec<VertexId>>,
+    /// number of colors at the beginning of the search
+    nb_colors:usize,
+    /// number of colors in use in the best-so-far feasible coloring
+    best_so_far_colors:usize,
+    /// tabu list
+    tabu:EjectionTabuTenure,
+    /// number of iterations
+    nb_iter:i64,
+    /// maximum number of vertices an ejection chain may displace, the root included; bounds
+    /// the cost of building one and how far a single move can ripple through the coloring
+    max_chain_length:usize,
+    /// next target color to try for each vertex, cycled after every attempt rooted there
+    target_color_cursor:Vec<usize>,
+    /// index into the current conflicting-vertex list of the next vertex to root a chain at
+    /// (round robin, so every conflicting vertex eventually gets a turn)
+    cursor:usize,
+}
+
+impl std::fmt::Debug for EjectionChainLocalSearch {
+    fn fmt(&self, f:&mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("EjectionChainLocalSearch")
+            .field("nb_colors", &self.nb_colors)
+            .field("best_so_far_colors", &self.best_so_far_colors)
+            .field("nb_iter", &self.nb_iter)
+            .field("max_chain_length", &self.max_chain_length)
+            .finish()
+    }
+}
+
+impl EjectionChainLocalSearch {
+
+    /// initializes the data-structure from an initial solution
+    pub fn initialize(inst:Rc<dyn ColoringInstance>, sol:&[Vec<VertexId>]) -> Self {
+        Self::initialize_with_chain_length(inst, sol, 5)
+    }
+
+    /// same as [`EjectionChainLocalSearch::initialize`], bounding chains to `max_chain_length`
+    /// displaced vertices (the root included) instead of the historical default of 5
+    pub fn initialize_with_chain_length(inst:Rc<dyn ColoringInstance>, sol:&[Vec<VertexId>], max_chain_length:usize) -> Self {
+        let n = inst.nb_vertices();
+        let nb_colors = sol.len();
+        let mut colors = vec![None ; n];
+        for (c, class) in sol.iter().enumerate() {
+            for &v in class { colors[v] = Some(c); }
+        }
+        let neighbors:Vec<Vec<VertexId>> = inst.vertices().map(|v| inst.neighbors(v)).collect();
+        let state = ColoringState::new(neighbors, colors, nb_colors);
+        Self {
+            inst,
+            state,
+            current_sol: sol.to_vec(),
+            nb_colors,
+            best_so_far_colors: sol.iter().filter(|e| !e.is_empty()).count(),
+            tabu: EjectionTabuTenure::new(10, 0.6, n, nb_colors),
+            nb_iter: 0,
+            max_chain_length,
+            target_color_cursor: vec![0 ; n],
+            cursor: 0,
+        }
+    }
+
+    /// true iff the current state has no conflicting edge
+    fn is_goal(&self) -> bool { self.state.nb_conflicting_edges() == 0 }
+
+    /// update the current solution from the (feasible) search state
+    fn update_current_solution(&mut self) {
+        assert!(self.is_goal());
+        let mut new_solution = vec![vec![] ; self.nb_colors];
+        for v in self.inst.vertices() {
+            if let Some(c) = self.state.color_of(v) { new_solution[c].push(v); }
+        }
+        self.current_sol = new_solution;
+        self.best_so_far_colors = self.current_sol.iter().filter(|e| !e.is_empty()).count();
+    }
+
+    /// merges the two color classes whose combination would create the fewest conflicts,
+    /// repeating while the merge stays feasible; called every time the search reaches a
+    /// zero-conflict state, the same progressive color-count reduction
+    /// [`crate::solvers::coloring::conflict_weighting::ConflictWeightingLocalSearch::merge_colors`]
+    /// uses, just driven by raw conflict counts instead of learned edge weights
+    fn merge_colors(&mut self) {
+        loop { // invariant: the state is feasible
+            let mut best_conflicts = i64::MAX;
+            let mut best_pair:Option<(usize,usize)> = None;
+            for c1 in 0..self.nb_colors {
+                if self.state.class(c1).is_empty() { continue; }
+                for c2 in 0..c1 {
+                    if self.state.class(c2).is_empty() { continue; }
+                    let conflicts:i64 = self.state.class(c1).iter()
+                        .map(|v| self.state.nb_conflicts_if_colored(v, c2)).sum();
+                    if best_pair.is_none() || conflicts < best_conflicts {
+                        best_conflicts = conflicts;
+                        best_pair = Some((c1, c2));
+                    }
+                }
+            }
+            let (c1, c2) = match best_pair {
+                Some(pair) => pair,
+                None => break, // at most one color class in use: nothing left to merge
+            };
+            let (c_min, c_max) = if self.state.class(c1).len() < self.state.class(c2).len() { (c1, c2) } else { (c2, c1) };
+            let vertices:Vec<VertexId> = self.state.class(c_min).iter().collect();
+            for v in vertices {
+                self.state.recolor(v, c_max);
+            }
+            if self.is_goal() {
+                self.update_current_solution();
+            } else {
+                break;
+            }
+        }
+    }
+
+    /// best (fewest resulting conflicts) color for `u` other than `avoid`, used to place a
+    /// vertex displaced mid-chain
+    fn best_alternate_color(&self, u:VertexId, avoid:usize) -> usize {
+        (0..self.nb_colors)
+            .filter(|&c| c != avoid)
+            .min_by_key(|&c| self.state.nb_conflicts_if_colored(u, c))
+            .unwrap_or(avoid)
+    }
+
+    /** builds the ejection chain that would result from moving `vertex` to `target_color`:
+    every neighbor of `vertex` already wearing `target_color` must itself move, taking its own
+    best alternate color, cascading further if that in turn displaces someone else, until no
+    one is left to displace or `max_chain_length` vertices have been moved. Does not mutate
+    `self.state`; the chain is only realized by [`EjectionChainLocalSearch::commit`] once
+    selected. The returned vector's first entry is always `vertex` itself. */
+    fn build_chain(&self, vertex:VertexId, target_color:usize) -> Vec<(VertexId, usize, usize)> {
+        let mut chain = Vec::new();
+        let mut visited = BitSet::with_capacity(self.inst.nb_vertices());
+        let mut queue = VecDeque::new();
+        visited.insert(vertex);
+        queue.push_back((vertex, target_color));
+        while let Some((u, c)) = queue.pop_front() {
+            if chain.len() >= self.max_chain_length { break; }
+            let previous = self.state.color_of(u).expect("build_chain: vertex must already be colored");
+            chain.push((u, previous, c));
+            for w in self.inst.neighbors(u) {
+                if !visited.contains(w) && self.state.color_of(w) == Some(c) {
+                    visited.insert(w);
+                    let next_for_w = self.best_alternate_color(w, c);
+                    queue.push_back((w, next_for_w));
+                }
+            }
+        }
+        chain
+    }
+
+    /// number of conflicting edges that would remain were `chain` applied, computed by
+    /// walking only the edges incident to a chain vertex (each exactly once) instead of
+    /// rescanning the whole instance
+    fn projected_conflicts(&self, chain:&[(VertexId, usize, usize)]) -> i64 {
+        let new_color:HashMap<VertexId, usize> = chain.iter().map(|&(v, _, c)| (v, c)).collect();
+        let mut seen_edges = HashSet::new();
+        let mut delta = 0i64;
+        for &(v, _, _) in chain {
+            for w in self.inst.neighbors(v) {
+                let edge = if v < w { (v, w) } else { (w, v) };
+                if !seen_edges.insert(edge) { continue; }
+                let color_v = self.state.color_of(v).unwrap();
+                let color_w = self.state.color_of(w).unwrap();
+                let before = color_v == color_w;
+                let after = *new_color.get(&v).unwrap_or(&color_v) == *new_color.get(&w).unwrap_or(&color_w);
+                if before && !after { delta -= 1; }
+                if !before && after { delta += 1; }
+            }
+        }
+        self.state.nb_conflicting_edges() + delta
+    }
+
+    /// applies a move (recoloring `node.vertex`, then every vertex in `node.chain` in order),
+    /// marking the chain's root tabu
+    fn commit(&mut self, node:&Node) {
+        self.tabu.insert(node, node.clone());
+        self.tabu.increment_iter();
+        self.nb_iter += 1;
+        self.state.recolor(node.vertex, node.next_color);
+        for &(v, _, next_color) in &node.chain {
+            self.state.recolor(v, next_color);
+        }
+    }
+
+    /// the dummy no-op node committed when this iteration found nothing worth taking
+    fn dummy_node(&self) -> Node {
+        Node { vertex: 0, previous_color: 0, next_color: 0, chain: Vec::new(), nb_conflicts: self.state.nb_conflicting_edges() }
+    }
+}
+
+impl GuidedSpace<Node, i64> for EjectionChainLocalSearch {
+    fn guide(&mut self, node: &Node) -> i64 { node.nb_conflicts }
+}
+
+impl ToSolution<Node, Vec<Vec<VertexId>>> for EjectionChainLocalSearch {
+    fn solution(&mut self, _: &mut Node) -> Vec<Vec<VertexId>> {
+        self.current_sol.iter().filter(|e| !e.is_empty()).cloned().collect()
+    }
+}
+
+impl SearchSpace<Node, i32> for EjectionChainLocalSearch {
+    fn initial(&mut self) -> Node {
+        let c = self.state.color_of(0).unwrap_or(0);
+        Node { vertex: 0, previous_color: c, next_color: c, chain: Vec::new(), nb_conflicts: self.state.nb_conflicting_edges() }
+    }
+    fn bound(&mut self, _node: &Node) -> i32 { self.best_so_far_colors as i32 }
+    fn goal(&mut self, n: &Node) -> bool { n.nb_conflicts == 0 }
+    fn g_cost(&mut self, _n: &Node) -> i32 { 0 }
+}
+
+impl TotalNeighborGeneration<Node> for EjectionChainLocalSearch {
+    fn neighbors(&mut self, node: &mut Node) -> Vec<Node> {
+        if node.previous_color != node.next_color || !node.chain.is_empty() { // if not a dummy decision, commit it
+            self.commit(node);
+        }
+        if self.is_goal() { // if no conflict, merge some colors
+            self.update_current_solution();
+            self.merge_colors();
+        }
+        let conflicting = self.state.conflicting_vertices();
+        if conflicting.is_empty() {
+            return vec![self.dummy_node()];
+        }
+        self.cursor %= conflicting.len();
+        let vertex = conflicting[self.cursor];
+        self.cursor += 1;
+        let previous_color = self.state.color_of(vertex).expect("neighbors: conflicting vertex must be colored");
+        let mut target_color = self.target_color_cursor[vertex] % self.nb_colors;
+        if target_color == previous_color { target_color = (target_color + 1) % self.nb_colors; }
+        self.target_color_cursor[vertex] = (target_color + 1) % self.nb_colors;
+        let chain = self.build_chain(vertex, target_color);
+        let nb_conflicts = self.projected_conflicts(&chain);
+        let candidate = Node {
+            vertex,
+            previous_color,
+            next_color: target_color,
+            chain: chain[1..].to_vec(),
+            nb_conflicts,
+        };
+        let is_tabu = self.tabu.contains(&candidate, &candidate);
+        if !is_tabu || nb_conflicts < self.state.nb_conflicting_edges() {
+            vec![candidate]
+        } else {
+            vec![self.dummy_node()]
+        }
+    }
+}
+
+/** performs an ejection-chain local search: starting from a feasible coloring, repeatedly
+merges the cheapest pair of color classes and resolves the conflicts that merge creates by
+displacing vertices in chains rather than recoloring them one at a time, until `stop` fires.
+Exports the best feasible coloring found via [`export_results`]. */
+pub fn coloring_ejection_chains<Stopping:StoppingCriterion>(
+inst:Rc<dyn ColoringInstance>,
+sol:&[Vec<VertexId>],
+perf_filename:Option<String>,
+sol_filename:Option<String>,
+stop:Stopping,
+) -> Vec<Vec<VertexId>> {
+    let mut solution:Vec<Vec<VertexId>> = sol.to_vec();
+    let logger = Rc::new(MetricLogger::default());
+    let search = EjectionChainLocalSearch::initialize(inst.clone(), &solution);
+    let space = Rc::new(RefCell::new(
+        StatTsCombinator::new(search).bind_logger(Rc::downgrade(&logger)),
+    ));
+    let mut ts = Greedy::new(space.clone());
+    logger.display_headers();
+    ts.run(stop);
+    space.borrow_mut().display_statistics();
+    match ts.get_manager().best() {
+        None => {
+            println!("\tlocal search failed improving...");
+        }
+        Some(node) => {
+            assert_eq!(node.nb_conflicts, 0);
+            solution = space.borrow_mut().solution(&mut node.clone());
+        }
+    }
+    let mut stats = serde_json::Value::default();
+    space.borrow_mut().json_statistics(&mut stats);
+    export_results(inst, &solution, &stats, perf_filename, sol_filename, true);
+    solution
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use dogs::search_algorithm::TimeStoppingCriterion;
+
+    use crate::{color::{checker, CheckerResult}, dimacs::DimacsInstance, solvers::coloring::greedy_dsatur::greedy_dsatur};
+
+    #[test]
+    fn test_ejection_chains_reaches_goal_on_grid2x2() {
+        let inst:Rc<dyn ColoringInstance> = Rc::new(DimacsInstance::from_file("insts/grid-instances/grid2x2"));
+        let greedy_sol = greedy_dsatur(inst.clone(), false);
+        let solution = coloring_ejection_chains(
+            inst.clone(), &greedy_sol, None, None, TimeStoppingCriterion::new(5.)
+        );
+        assert_eq!(checker(inst, &solution), CheckerResult::Ok(solution.len()));
+    }
+
+    #[test]
+    fn test_build_chain_displaces_conflicting_neighbor() {
+        // 4-cycle 0-1-3-2-0 colored 0,0,1,1 (conflicts on (0,1) and (2,3)): an ejection chain
+        // rooted at vertex 1 moving to color 1 must displace vertex 3 (its only neighbor wearing
+        // color 1, the other being 0), and the resulting chain leaves no conflict
+        let inst:Rc<dyn ColoringInstance> = Rc::new(DimacsInstance::from_file("insts/grid-instances/grid2x2"));
+        let sol = vec![vec![0, 1], vec![2, 3]];
+        let search = EjectionChainLocalSearch::initialize(inst, &sol);
+        let chain = search.build_chain(1, 1);
+        assert_eq!(chain[0], (1, 0, 1));
+        assert!(chain.iter().any(|&(v, _, _)| v == 3));
+        let nb_conflicts = search.projected_conflicts(&chain);
+        assert_eq!(nb_conflicts, 0);
+    }
+}
@@ -0,0 +1,136 @@
+use std::collections::HashMap;
+use std::rc::Rc;
+
+use crate::color::{ColoringInstance, VertexId};
+
+/** colors the vertices in the given order using the sequential ("first-fit") greedy rule:
+each vertex gets the smallest color not already used by an already-colored neighbor. */
+fn greedy_sequential_with_order(inst:&Rc<dyn ColoringInstance>, order:&[VertexId]) -> usize {
+    let n = inst.nb_vertices();
+    let mut colors:Vec<Option<usize>> = vec![None ; n];
+    let mut nb_colors = 0;
+    for u in order {
+        let mut color = 0;
+        loop {
+            let used = inst.neighbors(*u).iter().any(|v| colors[*v] == Some(color));
+            if !used { break; }
+            color += 1;
+        }
+        colors[*u] = Some(color);
+        nb_colors = nb_colors.max(color + 1);
+    }
+    nb_colors
+}
+
+/** for small graphs (`inst.nb_vertices() <= max_n`), enumerates the sequential greedy
+coloring produced by every permutation of the vertices, and returns the resulting
+distribution of color counts (`nb_colors -> nb_orderings producing it`). Used to study
+heuristic robustness (how sensitive greedy coloring is to tie-breaking) and for
+teaching/demo purposes. Runs in `O(n!)`, so `max_n` should stay small (8-10 at most). */
+pub fn enumerate_greedy_colorings(inst:Rc<dyn ColoringInstance>, max_n:usize) -> HashMap<usize, usize> {
+    let n = inst.nb_vertices();
+    assert!(n <= max_n, "enumerate_greedy_colorings: instance too large ({} > {})", n, max_n);
+    let mut distribution:HashMap<usize, usize> = HashMap::new();
+    let mut order:Vec<VertexId> = (0..n).collect();
+    permute(&mut order, 0, &mut |order| {
+        let nb_colors = greedy_sequential_with_order(&inst, order);
+        *distribution.entry(nb_colors).or_insert(0) += 1;
+    });
+    distribution
+}
+
+/// recursive swap enumeration: calls `f` on every permutation of `order[k..]`
+fn permute(order:&mut [VertexId], k:usize, f:&mut impl FnMut(&[VertexId])) {
+    if k == order.len() {
+        f(order);
+        return;
+    }
+    for i in k..order.len() {
+        order.swap(k, i);
+        permute(order, k + 1, f);
+        order.swap(k, i);
+    }
+}
+
+/** decides whether `inst` (with `inst.nb_vertices() <= max_n`) admits a proper coloring
+using at most `k` colors, by exhaustive backtracking search with forward-checking domain
+pruning: assigning a color to a vertex removes it from the domains of its uncolored
+neighbors, and the search backtracks as soon as any uncolored vertex's domain empties.
+Uncolored vertices are picked in most-constrained-first order (smallest remaining domain
+first), the standard heuristic for keeping exhaustive coloring search small. Meant for
+small `k` (<= 6) and moderate `n`, e.g. to certify the last few colors on a reduced kernel
+once a local search has done the bulk of the work; `max_n` is an explicit safety cap since
+the search is exponential in the worst case. Not called from any automated post-pass
+(neither [`crate::color::preprocess::reduce`] nor [`crate::reduce`] invokes it); exposed
+standalone. */
+pub fn is_k_colorable(inst:Rc<dyn ColoringInstance>, k:usize, max_n:usize) -> bool {
+    let n = inst.nb_vertices();
+    assert!(n <= max_n, "is_k_colorable: instance too large ({} > {})", n, max_n);
+    if k == 0 { return n == 0; }
+    let neighbors:Vec<Vec<VertexId>> = (0..n).map(|u| inst.neighbors(u)).collect();
+    let mut domains:Vec<Vec<bool>> = vec![vec![true ; k] ; n];
+    let mut colors:Vec<Option<usize>> = vec![None ; n];
+    k_colorable_backtrack(&neighbors, &mut domains, &mut colors, n)
+}
+
+/// number of colors still available in `domain`
+fn domain_size(domain:&[bool]) -> usize { domain.iter().filter(|&&available| available).count() }
+
+/// backtracking step of [`is_k_colorable`]: colors the most-constrained uncolored vertex
+/// with each color still in its domain, forward-checking neighbors before recursing
+fn k_colorable_backtrack(
+    neighbors:&[Vec<VertexId>], domains:&mut [Vec<bool>], colors:&mut [Option<usize>], n:usize
+) -> bool {
+    let next = (0..n)
+        .filter(|&v| colors[v].is_none())
+        .min_by_key(|&v| domain_size(&domains[v]));
+    let u = match next {
+        None => return true, // every vertex is colored
+        Some(u) => u,
+    };
+    if domain_size(&domains[u]) == 0 { return false; }
+    let candidates:Vec<usize> = (0..domains[u].len()).filter(|&c| domains[u][c]).collect();
+    for c in candidates {
+        colors[u] = Some(c);
+        let mut removed = Vec::new();
+        let mut failed = false;
+        for &v in &neighbors[u] {
+            if failed { break; }
+            if colors[v].is_none() && domains[v][c] {
+                domains[v][c] = false;
+                removed.push(v);
+                if domain_size(&domains[v]) == 0 { failed = true; }
+            }
+        }
+        if !failed && k_colorable_backtrack(neighbors, domains, colors, n) {
+            return true;
+        }
+        for v in removed { domains[v][c] = true; }
+        colors[u] = None;
+    }
+    false
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::dimacs::DimacsInstance;
+
+    #[test]
+    fn test_enumerate_grid2x2() {
+        let inst = Rc::new(DimacsInstance::from_file("insts/grid-instances/grid2x2"));
+        let distribution = enumerate_greedy_colorings(inst, 8);
+        let total:usize = distribution.values().sum();
+        assert_eq!(total, 24); // 4!
+        println!("distribution: {:?}", distribution);
+    }
+
+    #[test]
+    fn test_is_k_colorable_grid2x2() {
+        // grid2x2 is a 4-cycle: 2-colorable, not 1-colorable
+        let inst = Rc::new(DimacsInstance::from_file("insts/grid-instances/grid2x2"));
+        assert!(!is_k_colorable(inst.clone(), 1, 8));
+        assert!(is_k_colorable(inst, 2, 8));
+    }
+}
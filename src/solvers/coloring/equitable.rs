@@ -0,0 +1,158 @@
+//! equitable coloring: a proper coloring where color class sizes differ by at most one.
+//! [`equitable_dsatur`] adapts [`crate::solvers::coloring::greedy_dsatur::greedy_dsatur`]'s
+//! vertex-ordering heuristic but gives each vertex, among the colors it can legally take, the
+//! one whose class is smallest so far, then [`rebalance`] runs a small local search that moves
+//! vertices out of the largest class into the smallest one (whenever a conflict-free vertex
+//! exists) to mop up the cases greedy construction alone cannot satisfy. Validate
+//! equitability on the result with [`crate::color::checker_equitable`].
+
+use std::cmp::{Ordering, max};
+use std::rc::Rc;
+
+use priority_queue::PriorityQueue;
+use bit_set::BitSet;
+
+use crate::color::{ColoringInstance, Solution, VertexId};
+
+#[derive(PartialEq, Eq)]
+struct DSatInfo {
+    dsat: usize,
+    degree: usize,
+}
+
+impl Ord for DSatInfo {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.dsat.cmp(&other.dsat)
+            .then_with(|| self.degree.cmp(&other.degree))
+    }
+}
+
+impl PartialOrd for DSatInfo {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+/** builds an equitable coloring by a DSATUR-like greedy construction: vertices are picked in
+the usual saturation-degree order, but each vertex is assigned the conflict-free color with
+the *fewest* vertices so far among those it can legally take (instead of DSATUR's plain
+first-fit), which keeps class sizes close together as the coloring is built up. This alone
+does not guarantee equitability (an unlucky vertex order can still leave classes more than one
+apart), so the result is passed through [`rebalance`] before being returned. */
+pub fn equitable_dsatur(inst:Rc<dyn ColoringInstance>) -> Solution {
+    let n:usize = inst.nb_vertices();
+    let mut remaining_vertices:PriorityQueue<VertexId, DSatInfo> = PriorityQueue::new();
+    for i in 0..n {
+        remaining_vertices.push(i, DSatInfo { dsat:0, degree:inst.degree(i) });
+    }
+    let mut colors:Vec<Option<usize>> = vec![None ; n];
+    let mut adj_colors:Vec<BitSet> = vec![BitSet::default() ; n];
+    let mut class_sizes:Vec<usize> = Vec::new();
+    let mut last_color:Option<usize> = None;
+    loop {
+        let current_vertex = match remaining_vertices.pop() {
+            None => break,
+            Some(v) => v.0,
+        };
+        // among the colors current_vertex can legally take, pick the smallest class (instead
+        // of DSATUR's plain first-fit) so sizes stay as even as possible; a new color is only
+        // opened when no existing class is legal, exactly as in plain DSATUR
+        let best_existing = (0..class_sizes.len())
+            .filter(|c| !adj_colors[current_vertex].contains(*c))
+            .min_by_key(|&c| class_sizes[c]);
+        let color = match best_existing {
+            Some(c) => c,
+            None => { class_sizes.push(0); class_sizes.len() - 1 }
+        };
+        colors[current_vertex] = Some(color);
+        class_sizes[color] += 1;
+        last_color = Some(max(last_color.unwrap_or(0), color));
+        for conflict_vertex in inst.neighbors(current_vertex).iter()
+            .filter(|conflict_vertex| colors[**conflict_vertex].is_none()) {
+            if !adj_colors[*conflict_vertex].contains(color) {
+                adj_colors[*conflict_vertex].insert(color);
+                remaining_vertices.change_priority_by(conflict_vertex, |p| { p.dsat += 1; });
+            }
+        }
+    }
+    let nb_colors = last_color.map(|c| c + 1).unwrap_or(0);
+    let mut res = vec![Vec::new() ; nb_colors];
+    for (v, c) in colors.iter().enumerate() {
+        res[c.unwrap()].push(v);
+    }
+    rebalance(inst, res)
+}
+
+/** rebalances `sol` towards equitability in place (size-wise): while the largest class is more
+than one vertex larger than the smallest class, looks for a vertex in the largest class that has
+no neighbor in the smallest class and moves it there. Stops and returns the partial rebalancing
+reached once no vertex of the largest class can move into the smallest one (moves between other
+pairs of classes are not tried), since a perfectly equitable coloring is not always reachable
+without also changing the number of colors used. */
+pub fn rebalance(inst:Rc<dyn ColoringInstance>, mut sol:Solution) -> Solution {
+    if sol.is_empty() { return sol; }
+    loop {
+        let (biggest, _) = sol.iter().enumerate()
+            .max_by_key(|(_, c)| c.len())
+            .unwrap_or((0, &Vec::new()));
+        let (smallest, _) = sol.iter().enumerate()
+            .min_by_key(|(_, c)| c.len())
+            .unwrap_or((0, &Vec::new()));
+        if sol[biggest].len() <= sol[smallest].len() + 1 {
+            break;
+        }
+        let movable = sol[biggest].iter()
+            .position(|&v| !inst.neighbors(v).iter().any(|u| sol[smallest].contains(u)));
+        match movable {
+            Some(pos) => {
+                let v = sol[biggest].remove(pos);
+                sol[smallest].push(v);
+            }
+            None => break, // no move can shrink the gap any further; stop rather than loop forever
+        }
+    }
+    sol
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::color::{checker_equitable, CheckerResult};
+    use crate::dimacs::DimacsInstance;
+
+    #[test]
+    fn test_equitable_dsatur_is_proper_and_equitable() {
+        let inst:Rc<dyn ColoringInstance> = Rc::new(DimacsInstance::from_file("insts/grid-instances/grid2x2"));
+        let solution = equitable_dsatur(inst.clone());
+        assert_eq!(checker_equitable(inst, &solution), CheckerResult::Ok(solution.len()));
+    }
+
+    #[test]
+    fn test_rebalance_moves_vertex_into_undersized_class() {
+        // path 0-1-2-3-4: {0,2,4} vs {1} vs {3} is proper but very unbalanced (size 3 vs 1)
+        let inst:Rc<dyn ColoringInstance> = Rc::new(DimacsInstance::new(vec![
+            vec![1], vec![0, 2], vec![1, 3], vec![2, 4], vec![3],
+        ]));
+        let sol = vec![vec![0, 2, 4], vec![1], vec![3]];
+        let rebalanced = rebalance(inst.clone(), sol);
+        let sizes:Vec<usize> = rebalanced.iter().map(|c| c.len()).collect();
+        assert!(*sizes.iter().max().unwrap() - *sizes.iter().min().unwrap() <= 1);
+        assert_eq!(
+            crate::color::checker(inst, &rebalanced),
+            CheckerResult::Ok(rebalanced.len())
+        );
+    }
+
+    #[test]
+    fn test_rebalance_on_empty_solution_does_not_panic() {
+        let inst:Rc<dyn ColoringInstance> = Rc::new(DimacsInstance::new(vec![]));
+        assert_eq!(rebalance(inst, Vec::new()), Vec::<Vec<VertexId>>::new());
+    }
+
+    #[test]
+    fn test_equitable_dsatur_on_zero_vertex_instance_does_not_panic() {
+        let inst:Rc<dyn ColoringInstance> = Rc::new(DimacsInstance::new(vec![]));
+        assert_eq!(equitable_dsatur(inst), Vec::<Vec<VertexId>>::new());
+    }
+}
@@ -0,0 +1,172 @@
+use std::{collections::HashSet, rc::Rc};
+
+use dogs::search_algorithm::StoppingCriterion;
+
+use crate::color::{ColoringInstance, Solution, VertexId};
+use crate::solvers::clique::greedy_clique::greedy_clique;
+use crate::solvers::coloring::greedy_dsatur::greedy_dsatur;
+
+/// a group of vertices currently sharing the same color
+type Group = Vec<VertexId>;
+
+/** one node of the Zykov branch & bound tree: a partition of the vertices into color groups,
+plus every pair of groups already decided to take different colors (remembered as one
+representative vertex from each group, since group membership only ever grows). Two groups
+with no edge between their members and not yet forbidden are always free to merge;
+[`ZykovSolver::search`] branches on exactly that choice - merge them ("same color") or forbid
+the merge ("different color") - until every remaining pair of groups is adjacent, at which
+point one color per group is an optimal coloring for that branch. */
+#[derive(Clone, Debug)]
+struct ZykovNode {
+    groups: Vec<Group>,
+    forbidden: HashSet<(VertexId, VertexId)>,
+}
+
+/** exact coloring solver based on Zykov's branching scheme: pick two non-adjacent,
+not-yet-forbidden color groups and recurse on "merge them" and "forbid merging them", pruning
+a branch whenever a greedy clique found in its current quotient graph already matches or
+exceeds the best complete coloring found so far. Seeded with a [`greedy_dsatur`] upper bound
+and a [`greedy_clique`] lower bound, so small-to-medium instances can be proven optimal rather
+than merely improved. */
+pub struct ZykovSolver {
+    inst: Rc<dyn ColoringInstance>,
+    lower_bound: usize,
+    upper_bound: usize,
+    best: Vec<Group>,
+    nb_nodes: usize,
+    timed_out: bool,
+}
+
+impl std::fmt::Debug for ZykovSolver {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ZykovSolver")
+            .field("lower_bound", &self.lower_bound)
+            .field("upper_bound", &self.upper_bound)
+            .field("nb_nodes", &self.nb_nodes)
+            .field("timed_out", &self.timed_out)
+            .finish()
+    }
+}
+
+impl ZykovSolver {
+    /// builds a solver seeded with a DSATUR upper bound and a greedy clique lower bound
+    pub fn new(inst: Rc<dyn ColoringInstance>) -> Self {
+        let upper_bound_solution = greedy_dsatur(inst.clone(), false);
+        let lower_bound = greedy_clique(inst.clone()).len();
+        let upper_bound = upper_bound_solution.len();
+        Self { inst, lower_bound, upper_bound, best: upper_bound_solution, nb_nodes: 0, timed_out: false }
+    }
+
+    /// `true` if there is an edge between some member of group `i` and some member of
+    /// group `j`, or if `i` and `j` were already forbidden from merging
+    fn groups_adjacent(&self, node: &ZykovNode, i: usize, j: usize) -> bool {
+        let gi = &node.groups[i];
+        let gj = &node.groups[j];
+        if gi.iter().any(|&x| gj.iter().any(|&y| self.inst.are_adjacent(x, y))) {
+            return true;
+        }
+        node.forbidden.iter().any(|&(x, y)|
+            (gi.contains(&x) && gj.contains(&y)) || (gi.contains(&y) && gj.contains(&x))
+        )
+    }
+
+    /// a greedy clique over the quotient graph's groups: any valid completion needs at
+    /// least this many colors, since pairwise-adjacent groups must all get distinct ones
+    fn quotient_clique_lower_bound(&self, node: &ZykovNode) -> usize {
+        let mut clique = Vec::new();
+        let mut candidates: Vec<usize> = (0..node.groups.len()).collect();
+        while let Some(&g) = candidates.first() {
+            clique.push(g);
+            candidates.retain(|&c| c != g && self.groups_adjacent(node, g, c));
+        }
+        clique.len()
+    }
+
+    /// the first pair of groups with no edge between their members and not yet forbidden,
+    /// or `None` if every remaining pair is already adjacent (the quotient graph is complete)
+    fn branch_pair(&self, node: &ZykovNode) -> Option<(usize, usize)> {
+        for i in 0..node.groups.len() {
+            for j in (i + 1)..node.groups.len() {
+                if !self.groups_adjacent(node, i, j) { return Some((i, j)); }
+            }
+        }
+        None
+    }
+
+    fn search<Stop: StoppingCriterion>(&mut self, node: ZykovNode, stop: &Stop) {
+        if stop.is_finished() { self.timed_out = true; return; }
+        self.nb_nodes += 1;
+        if self.quotient_clique_lower_bound(&node) >= self.upper_bound { return; }
+        match self.branch_pair(&node) {
+            None => {
+                if node.groups.len() < self.upper_bound {
+                    self.upper_bound = node.groups.len();
+                    self.best = node.groups.clone();
+                }
+            }
+            Some((i, j)) => {
+                let mut merged = node.clone();
+                let absorbed = merged.groups.remove(j);
+                merged.groups[i].extend(absorbed);
+                self.search(merged, stop);
+                if self.timed_out || self.upper_bound <= self.lower_bound { return; }
+                let mut split = node;
+                let (rep_i, rep_j) = (split.groups[i][0], split.groups[j][0]);
+                split.forbidden.insert((rep_i.min(rep_j), rep_i.max(rep_j)));
+                self.search(split, stop);
+            }
+        }
+    }
+
+    /** runs the branch & bound until `stop` fires or the search tree is exhausted, and
+    returns the best coloring found along with whether it is proven optimal (either it
+    matches the clique lower bound, or the full search tree was exhausted before `stop`
+    fired). */
+    pub fn solve<Stop: StoppingCriterion>(&mut self, stop: Stop) -> (Solution, bool) {
+        let initial = ZykovNode {
+            groups: self.inst.vertices().map(|v| vec![v]).collect(),
+            forbidden: HashSet::new(),
+        };
+        self.search(initial, &stop);
+        let proven_optimal = self.upper_bound <= self.lower_bound || !self.timed_out;
+        (self.best.clone(), proven_optimal)
+    }
+}
+
+/// convenience wrapper around [`ZykovSolver`]: returns the best coloring found within `stop`,
+/// and prints whether it was proven optimal
+pub fn coloring_exact_zykov<Stop: StoppingCriterion>(inst: Rc<dyn ColoringInstance>, stop: Stop) -> Solution {
+    let mut solver = ZykovSolver::new(inst);
+    let (solution, proven_optimal) = solver.solve(stop);
+    println!(
+        "Zykov branch & bound: {} colors{}",
+        solution.len(),
+        if proven_optimal { " (proven optimal)" } else { " (best found, not proven optimal)" }
+    );
+    solution
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use dogs::search_algorithm::{NeverStoppingCriterion, TimeStoppingCriterion};
+    use crate::color::{checker, CheckerResult};
+    use crate::dimacs::DimacsInstance;
+
+    #[test]
+    fn test_exact_zykov_finds_optimal_coloring_on_grid2x2() {
+        let inst: Rc<dyn ColoringInstance> = Rc::new(DimacsInstance::from_file("insts/grid-instances/grid2x2"));
+        let mut solver = ZykovSolver::new(inst.clone());
+        let (solution, proven_optimal) = solver.solve(NeverStoppingCriterion::default());
+        assert!(proven_optimal);
+        assert_eq!(solution.len(), 2);
+        assert_eq!(checker(inst, &solution), CheckerResult::Ok(solution.len()));
+    }
+
+    #[test]
+    fn test_exact_zykov_respects_time_limit() {
+        let inst: Rc<dyn ColoringInstance> = Rc::new(DimacsInstance::from_file("insts/grid-instances/grid2x2"));
+        let solution = coloring_exact_zykov(inst.clone(), TimeStoppingCriterion::new(5.));
+        assert_eq!(checker(inst, &solution), CheckerResult::Ok(solution.len()));
+    }
+}
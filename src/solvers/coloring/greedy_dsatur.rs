@@ -54,9 +54,16 @@ pub fn greedy_dsatur(inst:Rc<dyn ColoringInstance>, show_completion:bool) -> Sol
             None => break,
             Some(v) => v.0
         };
-        // assign it a color
+        // assign it a color: the smallest one that is both conflict-free and, if
+        // `current_vertex` is list-colored or precolored (see crate::precoloring), allowed
         let mut color:usize = 0;
-        while adj_colors[current_vertex].contains(color) { color += 1; }
+        loop {
+            let blocked_by_conflict = adj_colors[current_vertex].contains(color);
+            let blocked_by_list = inst.allowed_colors(current_vertex).is_some_and(|allowed| !allowed.contains(color));
+            if !blocked_by_conflict && !blocked_by_list { break; }
+            color += 1;
+            assert!(color <= n, "greedy_dsatur: vertex {} has no admissible color within its allowed list", current_vertex);
+        }
         colors[current_vertex] = Some(color);
         nb_colored += 1;
         last_color = max(last_color, color); // update nb colors
@@ -86,6 +93,31 @@ mod tests {
     use super::*;
 
     use crate::cgshop::CGSHOPInstance;
+    use crate::color::{checker, AdjListInstance, CheckerResult};
+    use crate::dimacs::DimacsInstance;
+    use crate::precoloring::PrecoloredInstance;
+
+    #[test]
+    fn test_greedy_dsatur_accepts_an_in_memory_adj_list_instance() {
+        // 4-cycle 0-1-2-3-0, built without writing a DIMACS/CGSHOP file to disk
+        let inst:Rc<dyn ColoringInstance> = Rc::new(
+            AdjListInstance::from_edges(4, &[(0, 1), (1, 2), (2, 3), (3, 0)])
+        );
+        let solution = greedy_dsatur(inst.clone(), false);
+        assert_eq!(checker(inst, &solution), CheckerResult::Ok(solution.len()));
+    }
+
+    #[test]
+    fn test_greedy_dsatur_respects_fixed_color() {
+        let mut precolored = PrecoloredInstance::new(
+            Rc::new(DimacsInstance::from_file("insts/grid-instances/grid2x2"))
+        );
+        precolored.fix(0, 3);
+        let inst:Rc<dyn ColoringInstance> = Rc::new(precolored);
+        let solution = greedy_dsatur(inst.clone(), false);
+        assert_eq!(checker(inst, &solution), CheckerResult::Ok(solution.len()));
+        assert!(solution[3].contains(&0));
+    }
 
     #[test]
     fn test_read_instance_tiny() {
@@ -1,55 +1,116 @@
 use std::rc::Rc;
+use std::sync::Arc;
+use std::thread;
 
 use bit_set::BitSet;
 
-use crate::color::{ColoringInstance, Solution};
+use crate::color::{ColoringInstance, Solution, VertexId};
+
+/** bucketed candidate queue used by [`greedy_rlf`] to pick, in O(1) amortized time, the
+uncolored ("U") vertex that currently sees the most already-blocked ("W") neighbors: `buckets[k]`
+holds every U vertex with exactly `k` W-neighbors, so advancing a vertex's count is just moving
+it one bucket up, and picking the max is just popping the highest nonempty bucket - no rescan
+of every remaining vertex, unlike the naive `max_by` over `0..n` this replaced. */
+struct CandidateBuckets {
+    buckets: Vec<Vec<VertexId>>,
+    /// position[v]: `(bucket, index within that bucket)` while `v` is in U, `None` once it
+    /// leaves (colored, or pushed into W), for O(1) removal/bumping
+    position: Vec<Option<(usize, usize)>>,
+    highest_nonempty: usize,
+}
+
+impl CandidateBuckets {
+    /// a fresh queue for one RLF round: every vertex in `candidates` starts in bucket 0 (no
+    /// blocked neighbors seen yet this round); `max_bucket` must be at least the largest degree
+    /// among `candidates`, since a vertex's bucket index never exceeds its own degree
+    fn new(n:usize, candidates:&[VertexId], max_bucket:usize) -> Self {
+        let mut buckets = vec![Vec::new() ; max_bucket + 1];
+        let mut position = vec![None ; n];
+        for &v in candidates {
+            position[v] = Some((0, buckets[0].len()));
+            buckets[0].push(v);
+        }
+        Self { buckets, position, highest_nonempty: 0 }
+    }
+
+    /// removes and returns the U vertex with the most blocked neighbors (ties broken
+    /// arbitrarily), or `None` once every candidate has left U
+    fn pop_max(&mut self) -> Option<VertexId> {
+        while self.highest_nonempty > 0 && self.buckets[self.highest_nonempty].is_empty() {
+            self.highest_nonempty -= 1;
+        }
+        let v = self.buckets[self.highest_nonempty].pop()?;
+        self.position[v] = None;
+        Some(v)
+    }
+
+    /// removes `v` from a bucket, fixing up the position of whichever vertex the removal's
+    /// `swap_remove` moved into its old slot
+    fn remove_from_bucket(&mut self, bucket:usize, idx:usize) {
+        self.buckets[bucket].swap_remove(idx);
+        if let Some(&moved) = self.buckets[bucket].get(idx) {
+            self.position[moved] = Some((bucket, idx));
+        }
+    }
+
+    /// takes `v` out of U without coloring it, because it just became blocked (pushed into W)
+    fn block(&mut self, v:VertexId) {
+        if let Some((bucket, idx)) = self.position[v].take() {
+            self.remove_from_bucket(bucket, idx);
+        }
+    }
+
+    /// records that `v` (still in U) now sees one more blocked neighbor, moving it up a bucket
+    fn bump(&mut self, v:VertexId) {
+        if let Some((bucket, idx)) = self.position[v] {
+            self.remove_from_bucket(bucket, idx);
+            let new_bucket = bucket + 1;
+            self.position[v] = Some((new_bucket, self.buckets[new_bucket].len()));
+            self.buckets[new_bucket].push(v);
+            self.highest_nonempty = self.highest_nonempty.max(new_bucket);
+        }
+    }
+}
 
 /** implements a greedy RLF algorithm. That colors vertices one color at a time
     1. selects the vertex with the largest degree in the graph and mark it colored
     2. mark its neighbors unreachable
     3. select a reachable vertex that has the largest set of edges in the reachable vertices
     4. when there are no reachable vertices, start over with a new color
-*/
+
+Candidate selection within a round runs through [`CandidateBuckets`] rather than rescanning
+every uncolored vertex, so this stays usable as an initial solution generator on 70K+ vertex
+CGSHOP instances. */
 pub fn greedy_rlf(inst:Rc<dyn ColoringInstance>, show_completion:bool) -> Solution {
     let n:usize = inst.nb_vertices();
+    let max_degree = (0..n).map(|u| inst.degree(u)).max().unwrap_or(0);
     let mut colors:Vec<Option<usize>> = vec![None ; n];
-    let mut colored:BitSet<u64> = BitSet::default();
-    let mut reachable_degree:Vec<usize> = (0..n).map(|u| inst.degree(u)).collect();
+    let mut uncolored:Vec<VertexId> = (0..n).collect();
+    // ascending degree, so each round's first pop_max (bucket 0 untouched yet) is a largest-degree vertex
+    uncolored.sort_by_key(|&u| inst.degree(u));
     let mut nb_colored:usize = 0;
     let mut current_color:usize = 0;
     while nb_colored < n { // add a new color until everything is colored
-        let mut unreachable:BitSet<u64> = BitSet::default();
-        let mut reachable_degree_removal:Vec<usize> = vec![0 ; n];
-        // find not colored and reachable vertex with maximum degree
-        loop {
-            match (0..n)
-            .filter(|v| !colored.contains(*v) && !unreachable.contains(*v))
-            .max_by(|a,b| {
-                reachable_degree_removal[*a].cmp(&reachable_degree_removal[*b])
-                    .then_with(|| (reachable_degree[*a] - reachable_degree_removal[*a]).cmp(
-                        &(reachable_degree[*b] - reachable_degree_removal[*b])
-                    ))
-            }) {
-                None => { break; } // no more reachable vector, stpo and add
-                Some(current_vertex) => {
-                    if show_completion && nb_colored % 1000 == 0 { println!("colored {} / {}...", nb_colored, n); }
-                    nb_colored += 1;
-                    colored.insert(current_vertex);
-                    colors[current_vertex] = Some(current_color);
-                    // mark its neighbors unreachable and decrease their reachability degree
-                    for v in inst.neighbors(current_vertex) {
-                        if !unreachable.contains(v) && !colored.contains(v) {
-                            // every vertex that sees v sees a reachable vertex less
-                            for w in inst.neighbors(v) {
-                                reachable_degree_removal[w] += 1; // because v is now unreachable
-                            }
-                            unreachable.insert(v);
-                            reachable_degree[v] -= 1; // because current_vertex is now colored
+        let mut queue = CandidateBuckets::new(n, &uncolored, max_degree);
+        let mut in_w:BitSet<u64> = BitSet::default();
+        while let Some(current_vertex) = queue.pop_max() {
+            if show_completion && nb_colored % 1000 == 0 { println!("colored {} / {}...", nb_colored, n); }
+            nb_colored += 1;
+            colors[current_vertex] = Some(current_color);
+            // mark its uncolored neighbors blocked, bumping whatever they leave behind
+            for v in inst.neighbors(current_vertex) {
+                if colors[v].is_none() && !in_w.contains(v) {
+                    in_w.insert(v);
+                    queue.block(v);
+                    for w in inst.neighbors(v) {
+                        if colors[w].is_none() && !in_w.contains(w) {
+                            queue.bump(w);
                         }
                     }
                 }
             }
         }
+        uncolored.retain(|&v| colors[v].is_none());
         current_color += 1;
     }
     // finished. Solution completed build the solution
@@ -60,6 +121,78 @@ pub fn greedy_rlf(inst:Rc<dyn ColoringInstance>, show_completion:bool) -> Soluti
     res
 }
 
+/// grows a single maximal independent set starting from `seed`, restricted to `candidates`,
+/// by repeatedly picking the candidate with the largest degree within the remaining candidates
+/// (unlike the sequential [`greedy_rlf`], which picks the candidate with the most blocked neighbors)
+fn grow_independent_set_from_seed(adjacency:&[Vec<VertexId>], mut candidates:BitSet, seed:VertexId) -> Vec<VertexId> {
+    let mut chosen = vec![seed];
+    candidates.remove(seed);
+    for v in &adjacency[seed] { candidates.remove(*v); }
+    loop {
+        let next = candidates.iter().max_by_key(|v|
+            adjacency[*v].iter().filter(|u| candidates.contains(**u)).count()
+        );
+        match next {
+            None => break,
+            Some(v) => {
+                chosen.push(v);
+                candidates.remove(v);
+                for u in &adjacency[v] { candidates.remove(*u); }
+            }
+        }
+    }
+    chosen
+}
+
+/** concurrent variant of [`greedy_rlf`]. At each round, grows `nb_seeds` maximal independent
+sets in parallel from seed vertices spread across the remaining uncolored vertices, and keeps
+the largest one as the next color class. Threads operate on an immutable adjacency snapshot
+(built once, up-front) so no locking is required while growing.
+
+parameters:
+ - inst: reference to an instance
+ - nb_seeds: number of seeds grown in parallel at each round (use your core count)
+ - show_completion: if true, print progress towards the coloring
+*/
+pub fn greedy_rlf_concurrent(inst:Rc<dyn ColoringInstance>, nb_seeds:usize, show_completion:bool) -> Solution {
+    let n:usize = inst.nb_vertices();
+    let nb_seeds = nb_seeds.max(1);
+    let adjacency:Arc<Vec<Vec<VertexId>>> = Arc::new((0..n).map(|u| inst.neighbors(u)).collect());
+    let mut remaining:BitSet = BitSet::with_capacity(n);
+    for v in 0..n { remaining.insert(v); }
+    let mut colors:Vec<Option<usize>> = vec![None ; n];
+    let mut current_color:usize = 0;
+    let mut nb_colored:usize = 0;
+    while nb_colored < n {
+        if show_completion && nb_colored % 1000 == 0 { println!("colored {} / {}... (concurrent RLF)", nb_colored, n); }
+        let step = (remaining.len() / nb_seeds).max(1);
+        let seeds:Vec<VertexId> = remaining.iter().step_by(step).take(nb_seeds).collect();
+        let best = thread::scope(|scope| {
+            let handles:Vec<_> = seeds.iter().map(|seed| {
+                let adjacency = adjacency.clone();
+                let candidates = remaining.clone();
+                let seed = *seed;
+                scope.spawn(move || grow_independent_set_from_seed(&adjacency, candidates, seed))
+            }).collect();
+            handles.into_iter()
+                .map(|h| h.join().expect("greedy_rlf_concurrent: a seed thread panicked"))
+                .max_by_key(|set| set.len())
+                .expect("greedy_rlf_concurrent: at least one seed")
+        });
+        for v in &best {
+            colors[*v] = Some(current_color);
+            remaining.remove(*v);
+            nb_colored += 1;
+        }
+        current_color += 1;
+    }
+    let mut res = vec![vec![] ; current_color];
+    for (i,c) in colors.iter().enumerate() {
+        res[c.unwrap()].push(i);
+    }
+    res
+}
+
 
 
 
@@ -89,6 +222,15 @@ mod tests {
         println!("nb colors: {}", solution.len());
     }
 
+    #[test]
+    fn test_read_instance_tiny_concurrent() {
+        let cg_inst = Rc::new(CGSHOPInstance::from_file(
+            "./insts/CGSHOP_22_original/cgshop_2022_examples_01/tiny.json"
+        ));
+        let solution = greedy_rlf_concurrent(cg_inst, 4, false);
+        println!("nb colors: {}", solution.len());
+    }
+
     #[test]
     fn test_read_instance_sqrm() {
         let cg_inst = Rc::new(CGSHOPInstance::from_file(
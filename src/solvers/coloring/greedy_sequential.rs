@@ -0,0 +1,118 @@
+use std::cmp::max;
+use std::rc::Rc;
+
+use crate::color::{smallest_last_order, ColoringInstance, Solution, VertexId};
+
+/// vertex ordering strategies supported by [`greedy_sequential`]
+#[derive(Clone, Debug)]
+pub enum VertexOrder {
+    /// descending degree, computed once up front
+    LargestFirst,
+    /// repeatedly removes a minimum-degree vertex from the remaining subgraph, then colors in
+    /// the reverse removal order (see [`crate::color::smallest_last_order`])
+    SmallestLast,
+    /// uniformly random, seeded for reproducibility
+    Random(u64),
+    /// at each step, picks the uncolored vertex with the most already-colored neighbors
+    /// (ties broken by degree): unlike the other variants this is computed dynamically as
+    /// colors get assigned, not as a static order up front
+    Incidence,
+}
+
+/// assigns `v` the smallest color not already used by one of its colored neighbors, updating
+/// `last_color` if needed
+fn assign_smallest_color(inst:&dyn ColoringInstance, v:VertexId, colors:&mut [Option<usize>], last_color:&mut usize) {
+    let mut color = 0;
+    while inst.neighbors(v).iter().any(|&w| colors[w] == Some(color)) { color += 1; }
+    colors[v] = Some(color);
+    *last_color = max(*last_color, color);
+}
+
+/** generic sequential greedy coloring: visits vertices in the order given by `order` and
+assigns each the smallest color not already used by an already-colored neighbor. A cheap
+baseline, complementing [`greedy_dsatur`](crate::solvers::coloring::greedy_dsatur::greedy_dsatur)
+and [`greedy_rlf`](crate::solvers::coloring::greedy_rlf::greedy_rlf), meant to be fed as an
+initial solution to [`coloring_conflict_weighting`](crate::solvers::coloring::conflict_weighting::coloring_conflict_weighting)
+or [`coloring_partial_weighting`](crate::solvers::coloring::partial_weighting::coloring_partial_weighting). */
+pub fn greedy_sequential(inst:Rc<dyn ColoringInstance>, order:VertexOrder) -> Solution {
+    let n = inst.nb_vertices();
+    let mut colors:Vec<Option<usize>> = vec![None ; n];
+    let mut last_color:usize = 0;
+    match order {
+        VertexOrder::Incidence => {
+            let mut remaining:Vec<VertexId> = inst.vertices().collect();
+            while !remaining.is_empty() {
+                let idx = remaining.iter().enumerate().max_by_key(|&(_, &u)| {
+                    let nb_colored_neighbors = inst.neighbors(u).iter().filter(|&&w| colors[w].is_some()).count();
+                    (nb_colored_neighbors, inst.degree(u))
+                }).map(|(idx, _)| idx).unwrap();
+                let v = remaining.swap_remove(idx);
+                assign_smallest_color(inst.as_ref(), v, &mut colors, &mut last_color);
+            }
+        }
+        _ => {
+            let sequence:Vec<VertexId> = match order {
+                VertexOrder::LargestFirst => {
+                    let mut v:Vec<VertexId> = inst.vertices().collect();
+                    v.sort_by_key(|&u| std::cmp::Reverse(inst.degree(u)));
+                    v
+                }
+                VertexOrder::SmallestLast => smallest_last_order(inst.as_ref()),
+                VertexOrder::Random(seed) => {
+                    let mut v:Vec<VertexId> = inst.vertices().collect();
+                    fastrand::Rng::with_seed(seed).shuffle(&mut v);
+                    v
+                }
+                VertexOrder::Incidence => unreachable!(),
+            };
+            for v in sequence {
+                assign_smallest_color(inst.as_ref(), v, &mut colors, &mut last_color);
+            }
+        }
+    }
+    // finished. build the solution
+    let mut res = vec![vec![] ; last_color+1];
+    for (i,c) in colors.iter().enumerate() {
+        res[c.unwrap()].push(i);
+    }
+    res
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::color::{checker, AdjListInstance, CheckerResult};
+
+    fn cycle_4() -> Rc<dyn ColoringInstance> {
+        Rc::new(AdjListInstance::from_edges(4, &[(0, 1), (1, 2), (2, 3), (3, 0)]))
+    }
+
+    #[test]
+    fn test_greedy_sequential_largest_first_is_a_proper_coloring() {
+        let inst = cycle_4();
+        let solution = greedy_sequential(inst.clone(), VertexOrder::LargestFirst);
+        assert_eq!(checker(inst, &solution), CheckerResult::Ok(solution.len()));
+    }
+
+    #[test]
+    fn test_greedy_sequential_smallest_last_is_a_proper_coloring() {
+        let inst = cycle_4();
+        let solution = greedy_sequential(inst.clone(), VertexOrder::SmallestLast);
+        assert_eq!(checker(inst, &solution), CheckerResult::Ok(solution.len()));
+    }
+
+    #[test]
+    fn test_greedy_sequential_random_is_a_proper_coloring() {
+        let inst = cycle_4();
+        let solution = greedy_sequential(inst.clone(), VertexOrder::Random(42));
+        assert_eq!(checker(inst, &solution), CheckerResult::Ok(solution.len()));
+    }
+
+    #[test]
+    fn test_greedy_sequential_incidence_is_a_proper_coloring() {
+        let inst = cycle_4();
+        let solution = greedy_sequential(inst.clone(), VertexOrder::Incidence);
+        assert_eq!(checker(inst, &solution), CheckerResult::Ok(solution.len()));
+    }
+}
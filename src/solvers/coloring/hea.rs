@@ -0,0 +1,204 @@
+//! Hybrid Evolutionary Algorithm (HEA, Galinier & Hao) for graph coloring: a population of
+//! colorings is evolved by repeatedly crossing two parents with Greedy Partition Crossover
+//! ([`gpx`]) and repairing the offspring with a tabu-style local search.
+//!
+//! This request asks to "reuse the existing `tabucol_with_solution`", but no such function (nor
+//! any standalone `tabucol` module) exists in this tree: `solvers/mod.rs` only has a commented-out
+//! `// pub mod tabucol;`, and the TabuCol-flavored code is the tabu tenure
+//! [`super::conflict_weighting::TabuColTenure`] (with a twin in [`super::partial_weighting`]),
+//! neither exposed as a
+//! solution-repairing entry point on its own. [`super::conflict_weighting::coloring_conflict_weighting`]
+//! is the closest existing substitute: it runs the same tabu-tenure-guided local search and,
+//! like the requested `tabucol_with_solution`, accepts a starting partition that need not be
+//! conflict-free, so it is used here in place of the nonexistent function.
+
+use std::rc::Rc;
+
+use bit_set::BitSet;
+use dogs::search_algorithm::StoppingCriterion;
+use dogs::search_algorithm::TimeStoppingCriterion;
+
+use crate::color::{ColoringInstance, Solution, VertexId};
+use crate::solvers::coloring::conflict_weighting::coloring_conflict_weighting;
+use crate::util::{export_results, RunClock};
+
+/// counts edges whose endpoints end up in the same class of `sol` (`sol` need not be a proper
+/// coloring, unlike [`crate::color::checker`]'s validity check)
+fn count_conflicts(inst:&dyn ColoringInstance, sol:&[Vec<VertexId>]) -> usize {
+    let mut color_of = vec![0usize ; inst.nb_vertices()];
+    for (c, class) in sol.iter().enumerate() {
+        for &v in class { color_of[v] = c; }
+    }
+    // `edges_iter` over `edges`: conflicts are only ever counted once per call, so there is no
+    // point caching the full edge list for it (this is the closest thing to a "tabucol"
+    // conflict counter this tree has, see this module's top-level doc comment)
+    inst.edges_iter().filter(|&(u,v)| color_of[u] == color_of[v]).count()
+}
+
+/** Greedy Partition Crossover (GPX): builds a child coloring by alternately taking the largest
+still-available color class from `p1` and `p2` (removing its vertices from every remaining class
+of both parents before the next pick), then assigning every vertex neither parent contributed to
+the child into the first existing class it does not conflict with, or a fresh singleton class if
+none admits it. Every class taken directly from a parent is, by construction, a subset of one of
+that parent's conflict-free classes, and every leftover vertex is only ever placed where it
+creates no conflict, so `gpx` always returns a proper coloring (possibly using more colors than
+either parent, when leftovers cannot be packed back in). */
+fn gpx(inst:&dyn ColoringInstance, p1:&[Vec<VertexId>], p2:&[Vec<VertexId>], rng:&mut fastrand::Rng) -> Solution {
+    let n = inst.nb_vertices();
+    let to_bitsets = |p:&[Vec<VertexId>]| -> Vec<BitSet> {
+        p.iter().map(|c| c.iter().copied().collect()).collect()
+    };
+    let mut remaining = [to_bitsets(p1), to_bitsets(p2)];
+    let mut assigned = BitSet::with_capacity(n);
+    let mut child:Solution = Vec::new();
+    let mut turn = if rng.bool() { 0 } else { 1 };
+    loop {
+        let largest = remaining[turn].iter().enumerate()
+            .max_by_key(|(_,c)| c.len())
+            .filter(|(_,c)| !c.is_empty())
+            .map(|(i,_)| i);
+        match largest {
+            None => {
+                // this parent is exhausted: stop once both are, otherwise keep draining the other
+                if remaining[1-turn].iter().all(|c| c.is_empty()) { break; }
+            }
+            Some(i) => {
+                let class = remaining[turn].swap_remove(i);
+                for other in remaining.iter_mut() {
+                    for c in other.iter_mut() { *c = c.difference(&class).collect(); }
+                }
+                assigned.union_with(&class);
+                child.push(class.iter().collect());
+            }
+        }
+        turn = 1-turn;
+    }
+    for v in 0..n {
+        if assigned.contains(v) { continue; }
+        match child.iter_mut().find(|c| c.iter().all(|&u| !inst.are_adjacent(u,v))) {
+            Some(c) => c.push(v),
+            None => child.push(vec![v]),
+        }
+    }
+    child
+}
+
+/** attempts to shrink `sol` by one color: merges the two smallest classes together (introducing
+conflicts between them) and hands the result to [`coloring_conflict_weighting`] to repair; keeps
+the repaired solution only if it still uses strictly fewer colors than `sol`. */
+fn try_reduce_colors(inst:Rc<dyn ColoringInstance>, sol:&[Vec<VertexId>], repair_secs:f32) -> Solution {
+    if sol.len() < 2 { return sol.to_vec(); }
+    let mut order:Vec<usize> = (0..sol.len()).collect();
+    order.sort_by_key(|&i| sol[i].len());
+    let mut merged:Solution = Vec::with_capacity(sol.len()-1);
+    let mut combined = sol[order[0]].clone();
+    combined.extend(sol[order[1]].iter().copied());
+    merged.push(combined);
+    for &i in &order[2..] { merged.push(sol[i].clone()); }
+    let repaired = coloring_conflict_weighting(
+        inst.clone(), &merged, None, None, TimeStoppingCriterion::new(repair_secs)
+    );
+    if repaired.len() < sol.len() && count_conflicts(inst.as_ref(), &repaired) == 0 {
+        repaired
+    } else {
+        sol.to_vec()
+    }
+}
+
+/** runs an HEA with a population of `population_size` colorings (seeded from `sol`, diversified
+by randomly recoloring a handful of vertices and repairing with [`coloring_conflict_weighting`])
+until `stop` reports finished: each generation crosses two random population members with
+[`gpx`], tries to shrink the child by one color via [`try_reduce_colors`], and replaces the
+population's worst member whenever the child is at least as good. Exports the best coloring ever
+found through [`export_results`]. */
+pub fn hea<Stop:StoppingCriterion>(
+inst:Rc<dyn ColoringInstance>,
+sol:&[Vec<VertexId>],
+population_size:usize,
+repair_secs:f32,
+perf_filename:Option<String>,
+sol_filename:Option<String>,
+stop:Stop
+) -> Solution {
+    assert!(population_size >= 2, "hea: population_size must be at least 2");
+    let mut rng = fastrand::Rng::new();
+    let n = inst.nb_vertices();
+    let mut population:Vec<Solution> = vec![sol.to_vec()];
+    while population.len() < population_size {
+        let mut seed = sol.to_vec();
+        for _ in 0..(n/20).max(1) {
+            let v = rng.usize(0..n);
+            if let Some(old_class) = seed.iter().position(|c| c.contains(&v)) {
+                seed[old_class].retain(|&u| u != v);
+            }
+            let new_class = rng.usize(0..seed.len());
+            seed[new_class].push(v);
+        }
+        let repaired = coloring_conflict_weighting(
+            inst.clone(), &seed, None, None, TimeStoppingCriterion::new(repair_secs)
+        );
+        let repaired = if count_conflicts(inst.as_ref(), &repaired) == 0 { repaired } else { sol.to_vec() };
+        population.push(repaired);
+    }
+    let mut best = population.iter().min_by_key(|s| s.len()).unwrap().clone();
+    let clock = RunClock::start();
+    let mut generations = 0;
+    while !stop.is_finished() {
+        let i = rng.usize(0..population.len());
+        let mut j = rng.usize(0..population.len());
+        while j == i { j = rng.usize(0..population.len()); }
+        let child = gpx(inst.as_ref(), &population[i], &population[j], &mut rng);
+        let child = try_reduce_colors(inst.clone(), &child, repair_secs);
+        let worst = (0..population.len()).max_by_key(|&k| population[k].len()).unwrap();
+        if child.len() <= population[worst].len() {
+            population[worst] = child;
+        }
+        if let Some(candidate) = population.iter().min_by_key(|s| s.len()) {
+            if candidate.len() < best.len() { best = candidate.clone(); }
+        }
+        generations += 1;
+    }
+    let stats = serde_json::json!({
+        "wall_time_secs": clock.wall_secs(),
+        "generations": generations,
+        "population_size": population_size,
+        "peak_rss_growth_bytes": clock.peak_rss_growth_bytes(),
+    });
+    export_results(inst, &best, &stats, perf_filename, sol_filename, true);
+    best
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::color::{checker, CheckerResult};
+    use crate::dimacs::DimacsInstance;
+    use crate::solvers::coloring::greedy_dsatur::greedy_dsatur;
+
+    #[test]
+    fn test_gpx_always_returns_a_proper_coloring() {
+        let inst:Rc<dyn ColoringInstance> = Rc::new(DimacsInstance::from_file("insts/grid-instances/grid2x2"));
+        let p1 = greedy_dsatur(inst.clone(), false);
+        let p2 = {
+            let mut reordered = p1.clone();
+            reordered.reverse();
+            reordered
+        };
+        let mut rng = fastrand::Rng::new();
+        let child = gpx(inst.as_ref(), &p1, &p2, &mut rng);
+        assert_eq!(checker(inst, &child), CheckerResult::Ok(child.len()));
+    }
+
+    #[test]
+    fn test_hea_never_worsens_the_initial_solution() {
+        let inst:Rc<dyn ColoringInstance> = Rc::new(DimacsInstance::from_file("insts/grid-instances/grid2x2"));
+        let greedy_sol = greedy_dsatur(inst.clone(), false);
+        let initial_len = greedy_sol.len();
+        let best = hea(
+            inst.clone(), &greedy_sol, 4, 0.2, None, None, TimeStoppingCriterion::new(1.)
+        );
+        assert!(best.len() <= initial_len);
+        assert_eq!(checker(inst, &best), CheckerResult::Ok(best.len()));
+    }
+}
@@ -0,0 +1,140 @@
+//! Culberson-style Iterated Greedy (IG): repeatedly re-derives a vertex ordering from the
+//! current best coloring's classes (permuted by one of [`ClassPermutation`]'s three strategies)
+//! and re-runs [`sequential_greedy`] on it. Concatenating a `c`-coloring's classes in any order
+//! and feeding that ordering back through sequential greedy can never use more than `c` colors
+//! again (within each class every vertex is conflict-free with the others by construction, and
+//! sequential greedy only ever reuses or extends the colors already in play), so IG can only
+//! improve or stall, never regress.
+
+use std::rc::Rc;
+
+use bit_set::BitSet;
+use dogs::search_algorithm::StoppingCriterion;
+
+use crate::color::{ColoringInstance, Solution, VertexId};
+use crate::util::{export_results, RunClock};
+
+/// which permutation of the current solution's color classes the next [`iterated_greedy`]
+/// round's vertex ordering is built from, before re-running [`sequential_greedy`] on it
+#[derive(Clone,Copy,Debug,Eq,PartialEq)]
+pub enum ClassPermutation {
+    /// classes concatenated by decreasing size (ties broken by current class index)
+    LargestFirst,
+    /// classes concatenated in the reverse of their current order
+    Reverse,
+    /// classes concatenated in a uniformly random order
+    Random,
+}
+
+/// colors `order` sequentially: each vertex gets the smallest color neither already used by an
+/// earlier-colored neighbor nor excluded by [`ColoringInstance::allowed_colors`]
+fn sequential_greedy(inst:&dyn ColoringInstance, order:&[VertexId]) -> Solution {
+    let n = inst.nb_vertices();
+    let mut colors:Vec<Option<usize>> = vec![None ; n];
+    let mut adj_colors:Vec<BitSet> = vec![BitSet::default() ; n];
+    let mut nb_colors = 0;
+    for &v in order {
+        let mut color = 0;
+        loop {
+            let blocked_by_conflict = adj_colors[v].contains(color);
+            let blocked_by_list = inst.allowed_colors(v).is_some_and(|allowed| !allowed.contains(color));
+            if !blocked_by_conflict && !blocked_by_list { break; }
+            color += 1;
+            assert!(color <= n, "iterated_greedy: vertex {} has no admissible color within its allowed list", v);
+        }
+        colors[v] = Some(color);
+        nb_colors = nb_colors.max(color+1);
+        for u in inst.neighbors(v) {
+            if colors[u].is_none() { adj_colors[u].insert(color); }
+        }
+    }
+    let mut res = vec![Vec::new() ; nb_colors];
+    for (v,c) in colors.iter().enumerate() { res[c.unwrap()].push(v); }
+    res
+}
+
+/// builds the vertex ordering for the next round: `sol`'s classes concatenated according to
+/// `permutation` (see [`ClassPermutation`])
+fn ordering_from_classes(sol:&[Vec<VertexId>], permutation:ClassPermutation, rng:&mut fastrand::Rng) -> Vec<VertexId> {
+    let mut class_order:Vec<usize> = (0..sol.len()).collect();
+    match permutation {
+        ClassPermutation::LargestFirst => class_order.sort_by(|a,b| sol[*b].len().cmp(&sol[*a].len())),
+        ClassPermutation::Reverse => class_order.reverse(),
+        ClassPermutation::Random => rng.shuffle(&mut class_order),
+    }
+    class_order.into_iter().flat_map(|c| sol[c].clone()).collect()
+}
+
+/** runs Culberson-style Iterated Greedy from `sol` until `stop` reports finished: each round
+cycles to the next of [`ClassPermutation::LargestFirst`], [`ClassPermutation::Reverse`] and
+[`ClassPermutation::Random`] (in that order, wrapping around), re-derives a vertex ordering from
+the current best coloring's classes under it, and re-runs [`sequential_greedy`] on that
+ordering, keeping the result whenever it uses no more colors than the current best (see the
+module docs for why that is always the case, short of a future change loosening the
+invariant). */
+pub fn iterated_greedy<Stop:StoppingCriterion>(
+inst:Rc<dyn ColoringInstance>,
+sol:&[Vec<VertexId>],
+perf_filename:Option<String>,
+sol_filename:Option<String>,
+stop:Stop
+) -> Solution {
+    const STRATEGIES:[ClassPermutation ; 3] = [
+        ClassPermutation::LargestFirst, ClassPermutation::Reverse, ClassPermutation::Random
+    ];
+    let mut rng = fastrand::Rng::new();
+    let mut best = sol.to_vec();
+    let clock = RunClock::start();
+    let mut rounds = 0;
+    while !stop.is_finished() {
+        let permutation = STRATEGIES[rounds % STRATEGIES.len()];
+        let order = ordering_from_classes(&best, permutation, &mut rng);
+        let candidate = sequential_greedy(inst.as_ref(), &order);
+        if candidate.len() <= best.len() { best = candidate; }
+        rounds += 1;
+    }
+    let stats = serde_json::json!({
+        "wall_time_secs": clock.wall_secs(),
+        "rounds": rounds,
+        "peak_rss_growth_bytes": clock.peak_rss_growth_bytes(),
+    });
+    export_results(inst, &best, &stats, perf_filename, sol_filename, true);
+    best
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use dogs::search_algorithm::TimeStoppingCriterion;
+
+    use crate::color::{checker, CheckerResult};
+    use crate::dimacs::DimacsInstance;
+    use crate::solvers::coloring::greedy_dsatur::greedy_dsatur;
+
+    #[test]
+    fn test_iterated_greedy_never_worsens_the_initial_solution() {
+        let inst:Rc<dyn ColoringInstance> = Rc::new(DimacsInstance::from_file("insts/grid-instances/grid2x2"));
+        let greedy_sol = greedy_dsatur(inst.clone(), false);
+        let initial_len = greedy_sol.len();
+        let best = iterated_greedy(
+            inst.clone(), &greedy_sol, None, None, TimeStoppingCriterion::new(1.)
+        );
+        assert!(best.len() <= initial_len);
+        assert_eq!(checker(inst, &best), CheckerResult::Ok(best.len()));
+    }
+
+    #[test]
+    fn test_sequential_greedy_respects_fixed_color() {
+        use crate::precoloring::PrecoloredInstance;
+        let mut precolored = PrecoloredInstance::new(
+            Rc::new(DimacsInstance::from_file("insts/grid-instances/grid2x2"))
+        );
+        precolored.fix(0, 3);
+        let inst:Rc<dyn ColoringInstance> = Rc::new(precolored);
+        let order:Vec<VertexId> = (0..inst.nb_vertices()).collect();
+        let solution = sequential_greedy(inst.as_ref(), &order);
+        assert_eq!(checker(inst, &solution), CheckerResult::Ok(solution.len()));
+        assert!(solution[3].contains(&0));
+    }
+}
@@ -0,0 +1,95 @@
+//! conflict-free merging of colorings produced independently over disjoint vertex subsets
+//! (e.g. one per connected component, or one per worker of a distributed run), needed by any
+//! divide-and-conquer style driver before it can hand a single combined solution to
+//! [`crate::color::checker`] or an export function.
+
+use std::rc::Rc;
+
+use crate::color::{checker, CheckerResult, ColoringInstance, Solution};
+
+/** concatenates `solutions` (one coloring per disjoint vertex subset, over the same `inst`)
+into a single coloring over all of `inst`, then, if `merge_classes` is set, greedily merges
+color classes across the subset boundaries (see [`merge_color_classes`]) to remove the
+redundant colors that naturally appear when every subset was colored starting from color 0.
+Panics if the combined solution does not check out against `inst`: that means the input
+solutions were not actually vertex-disjoint, or at least one of them was not itself a proper
+coloring, either of which is a caller bug this should surface immediately rather than export
+a broken coloring silently. */
+pub fn merge_disjoint_solutions(
+    inst:Rc<dyn ColoringInstance>,
+    solutions:&[Solution],
+    merge_classes:bool,
+) -> Solution {
+    let mut merged:Solution = solutions.iter().flat_map(|s| s.iter().cloned()).collect();
+    if merge_classes {
+        merged = merge_color_classes(&inst, merged);
+    }
+    let result = checker(inst, &merged);
+    assert_eq!(result, CheckerResult::Ok(merged.len()),
+        "merge_disjoint_solutions: combined solution is invalid ({:?}); input solutions were not vertex-disjoint or not individually proper", result);
+    merged
+}
+
+/** greedily merges pairs of color classes of `classes` whenever their union stays an
+independent set (no edge between them), repeating until no such pair remains. Meant to clean
+up the redundant colors left behind by [`merge_disjoint_solutions`] concatenating solutions
+that were each colored independently starting from color 0: most of the time, a class from
+one subset has no edges at all to a class from another, since those only appear near the
+boundary between subsets (or not at all, for disconnected components). A plain `O(k^2)`
+pairwise scan per pass, restarted after every merge; fine for the modest number of classes
+a divide-and-conquer merge produces, not meant for general-purpose recoloring. */
+pub fn merge_color_classes(inst:&Rc<dyn ColoringInstance>, mut classes:Solution) -> Solution {
+    let are_compatible = |inst:&Rc<dyn ColoringInstance>, a:&[usize], b:&[usize]| {
+        a.iter().all(|&u| b.iter().all(|&v| !inst.are_adjacent(u, v)))
+    };
+    loop {
+        let mut merged_pair = None;
+        'search: for i in 0..classes.len() {
+            for j in (i + 1)..classes.len() {
+                if are_compatible(inst, &classes[i], &classes[j]) {
+                    merged_pair = Some((i, j));
+                    break 'search;
+                }
+            }
+        }
+        match merged_pair {
+            None => break,
+            Some((i, j)) => {
+                let moved = classes.remove(j);
+                classes[i].extend(moved);
+            }
+        }
+    }
+    classes
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::dimacs::DimacsInstance;
+
+    #[test]
+    fn test_merge_disjoint_solutions_across_components() {
+        // two disjoint edges (0-1) and (2-3): two components, each independently 2-colored
+        // starting from color 0, which must merge down to the graph's true chromatic number
+        let inst:Rc<dyn ColoringInstance> = Rc::new(DimacsInstance::new(
+            vec![vec![1], vec![0], vec![3], vec![2]]
+        ));
+        let component_a = vec![vec![0], vec![1]];
+        let component_b = vec![vec![2], vec![3]];
+        let merged = merge_disjoint_solutions(inst, &[component_a, component_b], true);
+        assert_eq!(merged.len(), 2);
+    }
+
+    #[test]
+    fn test_merge_disjoint_solutions_without_class_merge_keeps_every_class() {
+        let inst:Rc<dyn ColoringInstance> = Rc::new(DimacsInstance::new(
+            vec![vec![1], vec![0], vec![3], vec![2]]
+        ));
+        let component_a = vec![vec![0], vec![1]];
+        let component_b = vec![vec![2], vec![3]];
+        let merged = merge_disjoint_solutions(inst, &[component_a, component_b], false);
+        assert_eq!(merged.len(), 4);
+    }
+}
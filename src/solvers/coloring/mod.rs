@@ -11,4 +11,49 @@ pub mod conflict_weighting;
 pub mod partial_weighting;
 
 /// backtracking DSATUR for the vertex coloring problem
-pub mod backtracking_dsatur;
\ No newline at end of file
+pub mod backtracking_dsatur;
+
+/// enumeration of sequential greedy colorings over all vertex orderings (small instances)
+pub mod enumerate;
+
+/// tracks color-class stability across restarts, to identify sub-assignments worth freezing
+pub mod stability;
+
+/// shared incremental coloring bookkeeping (class bitsets, per-color conflict counts, conflicting-vertex set)
+pub mod state;
+
+/// randomized-rounding construction from a fractional set-cover class selection
+pub mod randomized_rounding;
+
+/// conflict-free merging of colorings computed independently over disjoint vertex subsets
+pub mod merge;
+
+/// latency-optimized "quick mode" construction with a hard wall-clock deadline
+pub mod quick;
+
+/// equitable coloring (color class sizes differ by at most one): DSATUR-style construction
+/// plus a size-rebalancing local search
+pub mod equitable;
+
+/// parallel multi-start driver running several seeded copies of the weighting local searches,
+/// exchanging improved color counts across threads
+pub mod portfolio;
+
+/// Culberson-style Iterated Greedy: permutes color classes and re-runs sequential greedy,
+/// never increasing the color count
+pub mod iterated_greedy;
+
+/// Hybrid Evolutionary Algorithm (population of colorings, GPX crossover, tabu-style repair)
+pub mod hea;
+
+/// ejection-chain local search: resolves a merge's conflicts by displacing vertices in chains
+/// rather than recoloring them one at a time
+pub mod ejection_chains;
+
+/// exact coloring via Zykov branch & bound (same/different branching on non-adjacent vertex
+/// groups), with a clique lower bound and a DSATUR upper bound
+pub mod exact_zykov;
+
+/// generic sequential greedy coloring over a choice of vertex orderings (largest-first,
+/// smallest-last, random, incidence), for cheap baselines and initial solutions
+pub mod greedy_sequential;
\ No newline at end of file
@@ -1,13 +1,15 @@
-use std::{cell::RefCell, rc::Rc};
+use std::{cell::RefCell, collections::{HashMap, VecDeque}, rc::Rc, time::Instant};
 
 use bit_set::BitSet;
 use fastrand::Rng;
+use serde::{Deserialize, Serialize};
 
-use dogs::{combinators::{helper::tabu_tenure::TabuTenure, stats::StatTsCombinator}, data_structures::sparse_set::SparseSet, metric_logger::MetricLogger, search_algorithm::SearchAlgorithm, search_algorithm::StoppingCriterion, search_space::{SearchSpace, TotalNeighborGeneration, GuidedSpace, ToSolution}, tree_search::greedy::Greedy};
+use dogs::{combinators::{helper::tabu_tenure::TabuTenure, stats::StatTsCombinator}, data_structures::sparse_set::SparseSet, metric_logger::MetricLogger, search_algorithm::SearchAlgorithm, search_algorithm::StoppingCriterion, search_algorithm::TimeStoppingCriterion, search_space::{SearchSpace, TotalNeighborGeneration, GuidedSpace, ToSolution}, tree_search::greedy::Greedy};
 
 use crate::{
     color::{ColoringInstance, VertexId},
-    util::export_results
+    solvers::clique::{greedy_clique::greedy_clique, partial_weighting::clique_partial_weighting},
+    util::{export_results_with_trace, log_metrics, ImprovementRecord, LogFormat, RunClock, TimeBasis}
 };
 
 type Weight = i32;
@@ -22,6 +24,71 @@ struct Node {
 }
 
 
+/** policy applied when [`TotalNeighborGeneration::neighbors`] ends with `best_nodes` holding
+only the dummy sentinel (a `vertex: None` no-op). In this search the first candidate move
+examined is always kept, tabu or not, so that happens only when no uncolored vertex has an
+allowed color in use; no fallback move exists then either, and every policy keeps the dummy.
+Left at [`EscapePolicy::Stall`] (the historical behavior), the search commits that no-op, which leaves the state unchanged and therefore produces the exact same
+empty `best_nodes` again next call, stalling until the stopping criterion intervenes. */
+#[derive(Clone,Copy,Debug,Eq,PartialEq)]
+pub enum EscapePolicy {
+    /// commit the no-op dummy node (historical behavior): the search stalls
+    Stall,
+    /// commit the best move found, ignoring its tabu status
+    BestTabuMove,
+    /// commit a uniformly random move among every move generated this call, tabu or not
+    RandomMove,
+}
+
+impl Default for EscapePolicy {
+    fn default() -> Self { EscapePolicy::Stall }
+}
+
+/** policy used by [`PartialWeightingLocalSearch::delete_color`] to pick which color class is
+emptied back into the uncolored pool once a feasible coloring is reached. The historical code
+emptied the class with the *most* vertices despite its own comment claiming the opposite
+("removes the color using the least number of vertices"), which dumps as many vertices as
+possible back into the uncolored pool on every improvement — expensive to re-place and the
+opposite of what the comment (and a tabucol-style search) intends. [`DeleteColorPolicy::SmallestClass`]
+is the fixed, intended default. */
+#[derive(Clone,Copy,Debug,Eq,PartialEq,Serialize,Deserialize)]
+pub enum DeleteColorPolicy {
+    /// empty the class with the fewest vertices (the intended historical behavior)
+    SmallestClass,
+    /// empty the class whose vertices carry the least total learned weight
+    LeastTotalWeight,
+    /// empty a uniformly random class among those tied for fewest vertices
+    RandomAmongSmallest,
+}
+
+impl Default for DeleteColorPolicy {
+    fn default() -> Self { DeleteColorPolicy::SmallestClass }
+}
+
+/** policy controlling the order in which [`TotalNeighborGeneration::neighbors`] examines
+uncolored vertices. The search keeps the first move it finds for a given weight (ties broken
+by iteration order), so this order is not a cosmetic detail: it measurably affects which
+vertex gets picked on a tie and therefore the path the search takes. `priority_vertices` are
+still always examined first regardless of this policy (see [`PartialWeightingLocalSearch::neighbors`]);
+the policy only orders the rest. */
+#[derive(Clone,Copy,Debug,Eq,PartialEq,Serialize,Deserialize)]
+pub enum UncoloredOrderingPolicy {
+    /// whatever order [`SparseSet`] happens to yield (historical behavior, roughly
+    /// insertion order, i.e. first-in-first-out)
+    Fifo,
+    /// learned weight descending: vertices the search has repeatedly failed to place get
+    /// first pick
+    WeightDescending,
+    /// instance degree descending: the classic most-constrained-vertex-first ordering
+    DegreeDescending,
+    /// freshly shuffled on every call
+    RandomShuffle,
+}
+
+impl Default for UncoloredOrderingPolicy {
+    fn default() -> Self { UncoloredOrderingPolicy::Fifo }
+}
+
 /** implements a specific tabu tenure for the graph coloring
 Is parametrized by:
  - L: minimum size of the tabu tenure (example value: 10). We use a random number between 0 and L.
@@ -45,6 +112,9 @@ pub struct TabuColTenure {
     rng: Rng,
     /// threshold value for a given iteration
     threshold: i64,
+    /// seed the random number generator was created with (tracked so [`Checkpoint::tabu_seed`]
+    /// can reproduce it across a checkpoint/resume cycle)
+    seed: u64,
 }
 
 impl TabuTenure<Node, Node> for TabuColTenure {
@@ -68,12 +138,203 @@ impl TabuColTenure {
      - c: the maximum number of colors
     */
     pub fn new(l:usize, lambda: f64, n:usize, c:usize) -> Self {
+        Self::with_seed(l, lambda, n, c, fastrand::u64(..))
+    }
+
+    /// same as [`TabuColTenure::new`], using a caller-provided random seed instead of one
+    /// drawn from the thread-local generator, so a checkpoint can reproduce it on resume
+    pub fn with_seed(l:usize, lambda: f64, n:usize, c:usize, seed:u64) -> Self {
+        Self {
+            l, lambda,
+            nb_iter: 0,
+            decisions: vec![vec![i64::MIN ; c] ; n],
+            rng: Rng::with_seed(seed),
+            threshold: 0, // will be changed later
+            seed,
+        }
+    }
+
+    /// increases the number of iterations of the tabu tenure
+    pub fn increment_iter(&mut self) { self.nb_iter += 1; }
+}
+
+/** a tabu tenure combining two granularities of memory: a short-lived forbiddance on the
+vertex alone (any color), on top of [`TabuColTenure`]'s existing per-(vertex,color) memory.
+Sparse geometric instances (e.g. CGSHOP) are reported to benefit from the coarser vertex-level
+tabu, while dense random instances favor the finer (vertex,color) one; [`TabuKind`] lets a
+solver run pick whichever fits, without forcing one choice on the other. */
+#[derive(Debug)]
+pub struct TwoLevelTabuTenure {
+    /// fixed size of the short, vertex-level tabu
+    l_vertex: usize,
+    /// dynamic factor of the short, vertex-level tabu
+    lambda_vertex: f64,
+    /// last_moved[v]: last iteration in which v was moved, regardless of color
+    last_moved: Vec<i64>,
+    /// threshold value of the vertex-level tabu, for a given iteration
+    vertex_threshold: i64,
+    /// random number generator, used to draw `vertex_threshold` like [`TabuColTenure`] does
+    rng: Rng,
+    /// number of iterations since the beginning of the search
+    nb_iter: i64,
+    /// longer, per-(vertex,color) tabu memory
+    pair: TabuColTenure,
+}
+
+impl TabuTenure<Node, Node> for TwoLevelTabuTenure {
+    fn insert(&mut self, n:&Node, d:Node) {
+        if let Some(v) = d.vertex {
+            self.last_moved[v] = self.nb_iter;
+            self.vertex_threshold = self.rng.i64(0..self.l_vertex as i64) + (self.lambda_vertex * (n.nb_uncolored as f64)) as i64;
+        }
+        self.pair.insert(n, d);
+    }
+
+    fn contains(&mut self, n:&Node, d:&Node) -> bool {
+        self.last_moved[d.vertex.unwrap()] >= self.nb_iter - self.vertex_threshold || self.pair.contains(n, d)
+    }
+}
+
+impl TwoLevelTabuTenure {
+    /** creates a two-level tabu tenure, combining a short vertex-level tabu (`l_vertex`,
+    `lambda_vertex`) with a longer (vertex,color)-level one (`l_pair`, `lambda_pair`, see
+    [`TabuColTenure::new`]). */
+    pub fn new(l_vertex:usize, lambda_vertex:f64, l_pair:usize, lambda_pair:f64, n:usize, c:usize) -> Self {
+        Self {
+            l_vertex, lambda_vertex,
+            last_moved: vec![i64::MIN ; n],
+            vertex_threshold: 0,
+            rng: Rng::with_seed(fastrand::u64(..)),
+            nb_iter: 0,
+            pair: TabuColTenure::new(l_pair, lambda_pair, n, c),
+        }
+    }
+
+    /// increases the number of iterations of both levels of the tabu tenure
+    pub fn increment_iter(&mut self) {
+        self.nb_iter += 1;
+        self.pair.increment_iter();
+    }
+}
+
+/** reactive counterpart to [`TabuColTenure`]: instead of a fixed `(l, lambda)`, maintains an
+incremental Zobrist-style hash of the vertex/color decisions taken so far (XORing in the
+`(vertex, color)` pair uncolored by every move) and keeps a bounded history of recently-seen
+hashes. Whenever the current hash collides with one still held in that history the search has
+almost certainly cycled back to a decision sequence it already visited, so tenure grows (`l`
+and `lambda` are scaled up by `growth`) to push it out of the cycle; once `decay_after`
+iterations pass without a repeat, tenure relaxes back down towards `(l_base, lambda_base)`.
+This is the well-known reactive-tenure improvement over Battiti & Tecchiolli's fixed tabu
+size, reported to help on hard DIMACS instances where a single fixed tenure either cycles
+(too small) or over-restricts the neighborhood (too large). */
+#[derive(Debug)]
+pub struct ReactiveTenure {
+    /// current fixed tabu size, grows/decays reactively (see struct doc)
+    l: usize,
+    /// current variable tabu size, grows/decays reactively alongside `l`
+    lambda: f64,
+    /// value `l` decays back towards once no repeat has been seen for `decay_after` iterations
+    l_base: usize,
+    /// value `lambda` decays back towards once no repeat has been seen for `decay_after` iterations
+    lambda_base: f64,
+    /// multiplicative factor applied to (and undone from) `l`/`lambda` on a detected repeat
+    growth: f64,
+    /// number of repeat-free iterations after which `l`/`lambda` take one decay step
+    decay_after: i64,
+    /// number of iterations since the last detected repeat (or the beginning of the search)
+    iters_since_repeat: i64,
+    /// number of iterations since the beginning of the search
+    nb_iter: i64,
+    /// decisions[v][c]: last iteration in which the decision have been taken
+    decisions: Vec<Vec<i64>>,
+    /// random number generator
+    rng: Rng,
+    /// threshold value for a given iteration
+    threshold: i64,
+    /// zobrist[v][c]: random bitstring associated to vertex v taking color c
+    zobrist: Vec<Vec<u64>>,
+    /// incremental hash of the decisions taken so far
+    current_hash: u64,
+    /// bounded, oldest-first history of recently-seen decision hashes, used to detect revisits
+    history: VecDeque<u64>,
+    /// counts[h]: number of times hash h currently appears in `history`
+    counts: HashMap<u64, usize>,
+    /// maximum number of hashes kept in `history`
+    history_capacity: usize,
+    /// seed the random number generator was created with (tracked so [`Checkpoint::tabu_seed`]
+    /// can reproduce it across a checkpoint/resume cycle)
+    seed: u64,
+}
+
+impl TabuTenure<Node, Node> for ReactiveTenure {
+    fn insert(&mut self, n:&Node, d:Node) {
+        if let Some(v) = d.vertex {
+            self.decisions[v][d.color] = self.nb_iter;
+            self.current_hash ^= self.zobrist[v][d.color];
+            let repeated = self.counts.contains_key(&self.current_hash);
+            self.history.push_back(self.current_hash);
+            *self.counts.entry(self.current_hash).or_insert(0) += 1;
+            if self.history.len() > self.history_capacity {
+                if let Some(old) = self.history.pop_front() {
+                    if let Some(c) = self.counts.get_mut(&old) {
+                        *c -= 1;
+                        if *c == 0 { self.counts.remove(&old); }
+                    }
+                }
+            }
+            if repeated {
+                self.l = (self.l as f64 * self.growth).ceil() as usize;
+                self.lambda *= self.growth;
+                self.iters_since_repeat = 0;
+            } else {
+                self.iters_since_repeat += 1;
+                if self.iters_since_repeat >= self.decay_after {
+                    self.l = self.l_base.max((self.l as f64 / self.growth) as usize);
+                    self.lambda = self.lambda_base.max(self.lambda / self.growth);
+                    self.iters_since_repeat = 0;
+                }
+            }
+            self.threshold = self.rng.i64(0..self.l.max(1) as i64) + (self.lambda * (n.nb_uncolored as f64)) as i64;
+        }
+    }
+
+    fn contains(&mut self, _n:&Node, d:&Node) -> bool {
+        self.decisions[d.vertex.unwrap()][d.color] >= self.nb_iter - self.threshold
+    }
+}
+
+impl ReactiveTenure {
+    /** creates a reactive tabu tenure given:
+     - l: base fixed tabu size, used while no cycling is detected
+     - λ: base variable tabu size, used while no cycling is detected
+     - n: the number of vertices in the graph
+     - c: the maximum number of colors
+    */
+    pub fn new(l:usize, lambda:f64, n:usize, c:usize) -> Self {
+        Self::with_seed(l, lambda, n, c, fastrand::u64(..))
+    }
+
+    /// same as [`ReactiveTenure::new`], using a caller-provided random seed instead of one
+    /// drawn from the thread-local generator, so a checkpoint can reproduce it on resume
+    pub fn with_seed(l:usize, lambda:f64, n:usize, c:usize, seed:u64) -> Self {
+        let mut rng = Rng::with_seed(seed);
+        let zobrist = (0..n).map(|_| (0..c).map(|_| rng.u64(..)).collect()).collect();
         Self {
             l, lambda,
+            l_base: l, lambda_base: lambda,
+            growth: 1.5,
+            decay_after: 20,
+            iters_since_repeat: 0,
             nb_iter: 0,
             decisions: vec![vec![i64::MIN ; c] ; n],
-            rng: Rng::new(),
+            rng,
             threshold: 0, // will be changed later
+            zobrist,
+            current_hash: 0,
+            history: VecDeque::new(),
+            counts: HashMap::new(),
+            history_capacity: 50,
+            seed,
         }
     }
 
@@ -81,9 +342,69 @@ impl TabuColTenure {
     pub fn increment_iter(&mut self) { self.nb_iter += 1; }
 }
 
-/** implements a partial weighting local search */
+/// selects which [`TabuTenure`] implementation backs a local search's tabu memory
+#[derive(Debug)]
+pub enum TabuKind {
+    /// single (vertex,color) tabu tenure (historical default)
+    Classic(TabuColTenure),
+    /// combined vertex-level and (vertex,color)-level tabu tenure (see [`TwoLevelTabuTenure`])
+    TwoLevel(TwoLevelTabuTenure),
+    /// (vertex,color) tabu tenure that reactively adapts its tenure to detected cycling (see [`ReactiveTenure`])
+    Reactive(ReactiveTenure),
+}
+
+impl TabuTenure<Node, Node> for TabuKind {
+    fn insert(&mut self, n:&Node, d:Node) {
+        match self {
+            TabuKind::Classic(t) => t.insert(n, d),
+            TabuKind::TwoLevel(t) => t.insert(n, d),
+            TabuKind::Reactive(t) => t.insert(n, d),
+        }
+    }
+
+    fn contains(&mut self, n:&Node, d:&Node) -> bool {
+        match self {
+            TabuKind::Classic(t) => t.contains(n, d),
+            TabuKind::TwoLevel(t) => t.contains(n, d),
+            TabuKind::Reactive(t) => t.contains(n, d),
+        }
+    }
+}
+
+impl TabuKind {
+    /// increases the number of iterations of the underlying tabu tenure
+    fn increment_iter(&mut self) {
+        match self {
+            TabuKind::Classic(t) => t.increment_iter(),
+            TabuKind::TwoLevel(t) => t.increment_iter(),
+            TabuKind::Reactive(t) => t.increment_iter(),
+        }
+    }
+}
+
+/** tunable parameters of a [`TabuKind::Classic`] tabu tenure (see [`TabuColTenure::new`]),
+bundled together so callers of the public API can tune tenure without forking the crate.
+[`Default`] reproduces the historical hardcoded values. */
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct TabuParams {
+    /// fixed tabu size (see [`TabuColTenure`]'s `l`)
+    pub l: usize,
+    /// variable tabu size (see [`TabuColTenure`]'s `lambda`)
+    pub lambda: f64,
+}
+
+impl Default for TabuParams {
+    fn default() -> Self { Self { l:10, lambda:0.01 } }
+}
+
+/** implements a partial weighting (tabucol-tenured) local search.
+
+Besides being driven to completion by [`coloring_partial_weighting`] and its variants, this
+state can be stepped manually through [`PartialWeightingLocalSearch::step`], letting an
+embedding application (a GUI, a service) interleave solving with its own event loop instead
+of blocking inside `run(stop)`. */
 #[derive(Debug)]
-struct PartialWeightingLocalSearch {
+pub struct PartialWeightingLocalSearch {
     /// instance object
     inst:Rc<dyn ColoringInstance>,
     /// weights[u]: weight learned for vertex u
@@ -109,19 +430,79 @@ struct PartialWeightingLocalSearch {
     /// cost_coloring[u][c]: cost of coloring vertex u with color c
     cost_coloring:Vec<Vec<Weight>>,
     /// tabu list
-    tabu:TabuColTenure,
+    tabu:TabuKind,
     /// threshold on the number of conflicts to disable the tabu tenure
     aspiration_criterion:i64,
     /// number of iterations
     nb_iter:i64,
     /// random number generator
     rng:Rng,
+    /// seed `rng` was created with, kept around so [`PartialWeightingLocalSearch::checkpoint`]
+    /// can make it reproducible across a resume (fastrand's `Rng` does not expose its current
+    /// internal state, only the seed it was last given)
+    rng_seed:u64,
+    /// vertices that should never be left uncolored if it can be avoided (e.g. vertices
+    /// known to belong to a near-maximum clique): biases color-deletion away from colors
+    /// holding them, and biases move selection towards recoloring them first
+    priority_vertices:BitSet,
+    /// vertices permanently locked to their current color (e.g. a known clique, each member
+    /// keeping the distinct color it already holds in a proper coloring): never added to
+    /// `uncolored_vertices` and never picked as the evicted side of a conflicting color class
+    fixed_vertices:BitSet,
+    /// forbidden_colors[u]: colors `u` may never take because a fixed neighbor already holds
+    /// them, so the move generator never proposes a move that would require evicting a fixed
+    /// vertex in the first place
+    forbidden_colors:Vec<BitSet>,
+    /// policy applied when no admissible non-tabu move exists (see [`EscapePolicy`])
+    escape_policy:EscapePolicy,
+    /// policy used to pick which color class [`PartialWeightingLocalSearch::delete_color`] empties
+    delete_color_policy:DeleteColorPolicy,
+    /// policy controlling the order in which uncolored vertices are examined by
+    /// [`TotalNeighborGeneration::neighbors`] (see [`UncoloredOrderingPolicy`])
+    ordering_policy:UncoloredOrderingPolicy,
+    /// number of times `escape_policy` had to kick in (i.e. [`EscapePolicy::Stall`] would
+    /// otherwise have committed a no-op), tracked for reporting alongside the search's stats
+    escape_activations:usize,
+    /// decision selected at the end of the last [`PartialWeightingLocalSearch::step`] call (or
+    /// the dummy initial decision if stepping has not started yet), carried across calls so
+    /// cooperative stepping picks up exactly where the previous call left off
+    current_node:Node,
+    /// wall-clock start of the search, used to timestamp [`PartialWeightingLocalSearch::improvement_trace`]
+    clock:RunClock,
+    /// every incumbent improvement recorded so far (see [`ImprovementRecord`]), in chronological
+    /// order. Shared behind an `Rc<RefCell<_>>` (like `logger` in [`coloring_partial_weighting`])
+    /// so a caller can keep a handle to it (via [`PartialWeightingLocalSearch::improvement_trace_handle`])
+    /// across the search being moved into a [`StatTsCombinator`]
+    improvement_trace:Rc<RefCell<Vec<ImprovementRecord>>>,
+    /// format [`PartialWeightingLocalSearch::update_current_solution`] uses to emit a
+    /// per-improvement progress line on stdout (see [`LogFormat`]); [`LogFormat::Text`] (the
+    /// default) is a no-op there, since text-mode progress is already printed elsewhere (e.g.
+    /// by [`MetricLogger`])
+    log_format:LogFormat,
+    /// optional callback invoked with `(solution, wall_time_secs)` each time
+    /// [`PartialWeightingLocalSearch::update_current_solution`] records a new incumbent, so an
+    /// embedding application can stream improving solutions (e.g. upload to a remote server)
+    /// without waiting for the run to finish
+    on_new_solution:Option<Rc<RefCell<dyn FnMut(&[Vec<VertexId>], f32)>>>,
 }
 
 impl PartialWeightingLocalSearch {
 
-    /// initializes the data-structure from an initial solution 
-    fn initialize(inst:Rc<dyn ColoringInstance>, sol:&[Vec<VertexId>]) -> Self {
+    /// initializes the data-structure from an initial solution, optionally marking
+    /// `priority_vertices` as vertices that should never be left uncolored if it can be
+    /// avoided
+    pub fn initialize_with_priority(inst:Rc<dyn ColoringInstance>, sol:&[Vec<VertexId>], priority_vertices:&BitSet) -> Self {
+        Self::initialize_with_priority_and_escape(inst, sol, priority_vertices, EscapePolicy::default())
+    }
+
+    /// same as [`PartialWeightingLocalSearch::initialize_with_priority`], additionally
+    /// choosing the [`EscapePolicy`] used when no admissible non-tabu move exists
+    pub fn initialize_with_priority_and_escape(
+        inst:Rc<dyn ColoringInstance>,
+        sol:&[Vec<VertexId>],
+        priority_vertices:&BitSet,
+        escape_policy:EscapePolicy,
+    ) -> Self {
         // build colors & colors_bitsets
         let n = inst.nb_vertices();
         let nb_colors = sol.len();
@@ -145,6 +526,18 @@ impl PartialWeightingLocalSearch {
                 }
             }
         }
+        // forbidden_colors also starts from any list-coloring/precoloring constraint declared
+        // on the instance (see crate::precoloring::PrecoloredInstance); fix_clique can add more
+        // on top of this once the search is built
+        let mut forbidden_colors = vec![BitSet::new() ; n];
+        for u in 0..n {
+            if let Some(allowed) = inst.allowed_colors(u) {
+                for c in 0..sol.len() {
+                    if !allowed.contains(c) { forbidden_colors[u].insert(c); }
+                }
+            }
+        }
+        let rng_seed = fastrand::u64(..);
         Self {
             inst,
             weights: vec![1 ; n],
@@ -158,15 +551,173 @@ impl PartialWeightingLocalSearch {
             total_weight: 0,
             uncolored_vertices: SparseSet::new(n),
             cost_coloring,
-            tabu: TabuColTenure::new(10, 0.01, n, nb_colors),
+            tabu: TabuKind::Classic(TabuColTenure::new(10, 0.01, n, nb_colors)),
             aspiration_criterion: i64::MAX,
             nb_iter: 0,
-            rng: Rng::default(),
+            rng: Rng::with_seed(rng_seed),
+            rng_seed,
+            priority_vertices: priority_vertices.clone(),
+            fixed_vertices: BitSet::new(),
+            forbidden_colors,
+            escape_policy,
+            delete_color_policy: DeleteColorPolicy::default(),
+            ordering_policy: UncoloredOrderingPolicy::default(),
+            escape_activations: 0,
+            current_node: Node { vertex:None, color:0, total_weight:0, nb_uncolored:0 },
+            clock: RunClock::start(),
+            improvement_trace: Rc::new(RefCell::new(vec![ImprovementRecord { time:0., iteration:0, value:sol.len() }])),
+            log_format: LogFormat::default(),
+            on_new_solution: None,
+        }
+    }
+
+    /// same as [`PartialWeightingLocalSearch::initialize_with_priority`], emitting a
+    /// per-improvement progress line on stdout in `log_format` (see [`LogFormat`]) instead of
+    /// the default [`LogFormat::Text`], which leaves progress reporting to the caller
+    pub fn initialize_with_priority_and_log_format(
+        inst:Rc<dyn ColoringInstance>,
+        sol:&[Vec<VertexId>],
+        priority_vertices:&BitSet,
+        log_format:LogFormat,
+    ) -> Self {
+        let mut search = Self::initialize_with_priority(inst, sol, priority_vertices);
+        search.log_format = log_format;
+        search
+    }
+
+    /// same as [`PartialWeightingLocalSearch::initialize_with_priority`], invoking `callback`
+    /// with `(solution, wall_time_secs)` each time a new incumbent is found (see
+    /// [`PartialWeightingLocalSearch::on_new_solution`])
+    pub fn initialize_with_priority_and_on_new_solution(
+        inst:Rc<dyn ColoringInstance>,
+        sol:&[Vec<VertexId>],
+        priority_vertices:&BitSet,
+        callback:Rc<RefCell<dyn FnMut(&[Vec<VertexId>], f32)>>,
+    ) -> Self {
+        let mut search = Self::initialize_with_priority(inst, sol, priority_vertices);
+        search.on_new_solution = Some(callback);
+        search
+    }
+
+    /// same as [`PartialWeightingLocalSearch::initialize_with_priority`], using the given
+    /// [`TabuKind`] instead of the historical single (vertex,color) tabu tenure
+    pub fn initialize_with_priority_and_tabu_kind(
+        inst:Rc<dyn ColoringInstance>,
+        sol:&[Vec<VertexId>],
+        priority_vertices:&BitSet,
+        tabu_kind:TabuKind,
+    ) -> Self {
+        let mut search = Self::initialize_with_priority(inst, sol, priority_vertices);
+        search.tabu = tabu_kind;
+        search
+    }
+
+    /// same as [`PartialWeightingLocalSearch::initialize_with_priority`], using a
+    /// [`TabuKind::Classic`] tenure built from `tabu_params` instead of the historical
+    /// hardcoded `(10, 0.01)`
+    pub fn initialize_with_priority_and_tabu_params(
+        inst:Rc<dyn ColoringInstance>,
+        sol:&[Vec<VertexId>],
+        priority_vertices:&BitSet,
+        tabu_params:TabuParams,
+    ) -> Self {
+        let n = inst.nb_vertices();
+        let nb_colors = sol.len();
+        let tabu = TabuKind::Classic(TabuColTenure::new(tabu_params.l, tabu_params.lambda, n, nb_colors));
+        Self::initialize_with_priority_and_tabu_kind(inst, sol, priority_vertices, tabu)
+    }
+
+    /// same as [`PartialWeightingLocalSearch::initialize_with_priority`], using the given
+    /// [`DeleteColorPolicy`] instead of the default [`DeleteColorPolicy::SmallestClass`]
+    pub fn initialize_with_priority_and_delete_color_policy(
+        inst:Rc<dyn ColoringInstance>,
+        sol:&[Vec<VertexId>],
+        priority_vertices:&BitSet,
+        delete_color_policy:DeleteColorPolicy,
+    ) -> Self {
+        let mut search = Self::initialize_with_priority(inst, sol, priority_vertices);
+        search.delete_color_policy = delete_color_policy;
+        search
+    }
+
+    /// same as [`PartialWeightingLocalSearch::initialize_with_priority`], using the given
+    /// [`UncoloredOrderingPolicy`] instead of the default [`UncoloredOrderingPolicy::Fifo`]
+    pub fn initialize_with_priority_and_ordering_policy(
+        inst:Rc<dyn ColoringInstance>,
+        sol:&[Vec<VertexId>],
+        priority_vertices:&BitSet,
+        ordering_policy:UncoloredOrderingPolicy,
+    ) -> Self {
+        let mut search = Self::initialize_with_priority(inst, sol, priority_vertices);
+        search.ordering_policy = ordering_policy;
+        search
+    }
+
+    /// same as [`PartialWeightingLocalSearch::initialize_with_priority`], additionally
+    /// permanently fixing every vertex in `clique` to the color it already holds in `sol`
+    /// (shrinking the effective search space, see [`PartialWeightingLocalSearch::fix_clique`])
+    pub fn initialize_with_priority_and_fixed_clique(
+        inst:Rc<dyn ColoringInstance>,
+        sol:&[Vec<VertexId>],
+        priority_vertices:&BitSet,
+        clique:&[VertexId],
+    ) -> Self {
+        Self::initialize_with_priority_fixed_clique_and_escape(inst, sol, priority_vertices, clique, EscapePolicy::default())
+    }
+
+    /// same as [`PartialWeightingLocalSearch::initialize_with_priority_and_fixed_clique`],
+    /// additionally choosing the [`EscapePolicy`] used when no admissible non-tabu move exists
+    pub fn initialize_with_priority_fixed_clique_and_escape(
+        inst:Rc<dyn ColoringInstance>,
+        sol:&[Vec<VertexId>],
+        priority_vertices:&BitSet,
+        clique:&[VertexId],
+        escape_policy:EscapePolicy,
+    ) -> Self {
+        let mut search = Self::initialize_with_priority_and_escape(inst, sol, priority_vertices, escape_policy);
+        search.fix_clique(clique);
+        search
+    }
+
+    /** permanently fixes every vertex in `clique` to the color it already holds: since `sol`
+    is a proper coloring, any two adjacent members of `clique` already sit in distinct color
+    classes, so fixing them needs no recoloring of its own (this is checked, not assumed, for
+    every adjacent pair). Fixed vertices are marked in `fixed_vertices` (excluding them from
+    `uncolored_vertices` and from ever being the evicted side of a conflicting color class,
+    see [`PartialWeightingLocalSearch::color_vertex`] and [`PartialWeightingLocalSearch::delete_color`]),
+    and every non-fixed neighbor of a fixed vertex has that vertex's color added to its
+    `forbidden_colors`, so the move generator never proposes a move that would require evicting
+    it. */
+    fn fix_clique(&mut self, clique:&[VertexId]) {
+        for (i, &v) in clique.iter().enumerate() {
+            let c = self.colors[v]
+                .unwrap_or_else(|| panic!("fix_clique: vertex {} must already be colored", v));
+            for &u in &clique[..i] {
+                if self.inst.are_adjacent(u, v) {
+                    assert_ne!(self.colors[u], Some(c),
+                        "fix_clique: {} and {} are adjacent but share a color, so the current solution is not proper", u, v);
+                }
+            }
+            self.fixed_vertices.insert(v);
+        }
+        let inst = self.inst.clone();
+        for &f in clique {
+            let cf = self.colors[f].unwrap();
+            inst.for_each_neighbor(f, &mut |u| {
+                if !self.fixed_vertices.contains(u) {
+                    self.forbidden_colors[u].insert(cf);
+                }
+            });
         }
     }
 
+    /// number of times [`PartialWeightingLocalSearch::step`] had to fall back on
+    /// `escape_policy` because no admissible non-tabu move existed (see [`EscapePolicy`])
+    pub fn escape_activations(&self) -> usize { self.escape_activations }
+
     /// uncolors a vertex
     fn uncolor_vertex(&mut self, u:VertexId) {
+        debug_assert!(!self.fixed_vertices.contains(u), "uncolor_vertex: vertex {} is fixed and must never be uncolored", u);
         let previous_color:usize = self.colors[u]
             .unwrap_or_else(|| panic!("{} should have a color", u));
         self.colors_vertex_number[previous_color] -= 1;
@@ -175,9 +726,8 @@ impl PartialWeightingLocalSearch {
         self.colors[u] = None;
         self.uncolored_vertices.insert(u);
         // decrease the coloring cost of the neighbors of u
-        for v in self.inst.neighbors(u) {
-            self.cost_coloring[v][previous_color] -= self.weights[u];
-        }
+        let inst = self.inst.clone();
+        inst.for_each_neighbor(u, &mut |v| self.cost_coloring[v][previous_color] -= self.weights[u]);
         // make the insertion of u tabu
         let node  = Node {vertex:Some(u), color: previous_color, total_weight: 0, nb_uncolored: 0};
         self.tabu.insert(&node, node.clone()); // make the decision tabu
@@ -195,9 +745,8 @@ impl PartialWeightingLocalSearch {
         self.colors_vertices[c].insert(u);
         self.uncolored_vertices.remove(u);
         // increase the coloring cost of the neighbors of u
-        for v in self.inst.neighbors(u) {
-            self.cost_coloring[v][c] += self.weights[u];
-        }
+        let inst = self.inst.clone();
+        inst.for_each_neighbor(u, &mut |v| self.cost_coloring[v][c] += self.weights[u]);
         // uncolors conflicting vertices
         let mut to_uncolor = Vec::new();
         for v in self.colors_vertices[c].iter() {
@@ -237,12 +786,40 @@ impl PartialWeightingLocalSearch {
         // assert_eq!(total_weight, self.total_weight);
     }
 
-    /// removes the color using the leas number of vertices
+    /// empties the color class picked by `delete_color_policy` back into the uncolored pool
     fn delete_color(&mut self) {
-        // find min-color
-        let c_min = self.colors_vertex_number.iter().enumerate()
-            .filter(|(_,c)| **c > 0) // (otherwise, the color is already not used)
-            .max_by_key(|(_,c)| **c).unwrap().0;
+        // eligible colors: never one holding a fixed vertex (those must never be uncolored),
+        // then prefer one holding no priority vertex (so that priority vertices are not
+        // forced back into the uncolored pool); `delete_color_policy` picks among the rest
+        let has_fixed_in_color = |c:usize| -> bool {
+            self.colors_vertices[c].iter().any(|v| self.fixed_vertices.contains(v))
+        };
+        let nb_priority_in_color = |c:usize| -> usize {
+            self.colors_vertices[c].iter().filter(|v| self.priority_vertices.contains(*v)).count()
+        };
+        let total_weight_in_color = |c:usize| -> Weight {
+            self.colors_vertices[c].iter().map(|v| self.weights[v]).sum()
+        };
+        let candidates:Vec<usize> = self.colors_vertex_number.iter().enumerate()
+            .filter(|(c,n)| **n > 0 && !has_fixed_in_color(*c)) // (otherwise, the color is already not used, or must never be vacated)
+            .map(|(c,_)| c)
+            .collect();
+        if candidates.is_empty() {
+            panic!("delete_color: every used color holds a fixed vertex, cannot free any more");
+        }
+        let best_priority = candidates.iter().map(|&c| nb_priority_in_color(c)).min().unwrap();
+        let eligible:Vec<usize> = candidates.into_iter().filter(|&c| nb_priority_in_color(c) == best_priority).collect();
+        let c_min = match self.delete_color_policy {
+            DeleteColorPolicy::SmallestClass => *eligible.iter()
+                .min_by_key(|&&c| self.colors_vertex_number[c]).unwrap(),
+            DeleteColorPolicy::LeastTotalWeight => *eligible.iter()
+                .min_by_key(|&&c| total_weight_in_color(c)).unwrap(),
+            DeleteColorPolicy::RandomAmongSmallest => {
+                let min_size = eligible.iter().map(|&c| self.colors_vertex_number[c]).min().unwrap();
+                let tied:Vec<usize> = eligible.into_iter().filter(|&c| self.colors_vertex_number[c] == min_size).collect();
+                tied[self.rng.usize(0..tied.len())]
+            }
+        };
         for i in self.inst.vertices() {
             match self.colors[i] {
                 None => {},
@@ -282,10 +859,172 @@ impl PartialWeightingLocalSearch {
         }
         self.current_sol = new_solution;
         self.nb_colors_best_so_far -= 1;
+        // nb_colors_best_so_far is already the next target (one below this incumbent)
+        let nb_colors = self.current_sol.iter().filter(|e| !e.is_empty()).count();
+        self.improvement_trace.borrow_mut().push(ImprovementRecord {
+            time: self.clock.wall_secs(),
+            iteration: self.nb_iter as u64,
+            value: nb_colors,
+        });
+        log_metrics(self.log_format, &serde_json::json!({
+            "iteration": self.nb_iter,
+            "colors": nb_colors,
+            "weight": self.total_weight,
+            "time": self.clock.wall_secs(),
+        }));
+        if let Some(cb) = &self.on_new_solution {
+            (*cb.borrow_mut())(&self.current_solution(), self.clock.wall_secs());
+        }
     }
 
     /// true iff state is feasible
     fn is_goal(&self) -> bool { self.total_weight == 0 }
+
+    /** advances the search by at most `n_iters` decisions, applying the best-guide candidate
+    move returned by [`TotalNeighborGeneration::neighbors`] at each step. This is the same
+    move-selection loop that [`dogs::tree_search::greedy::Greedy`] runs internally when driven
+    to completion by `run(stop)`, exposed one call at a time so an embedding application (a
+    GUI, a service) can interleave solving with its own event loop instead of blocking.
+    Returns true as soon as a feasible (zero-conflict) coloring is reached. */
+    pub fn step(&mut self, n_iters:usize) -> bool {
+        // commit() deletes a color as soon as it reaches a feasible state, so a feasible
+        // coloring reached during this call shows up as a new improvement_trace entry
+        let nb_records = self.improvement_trace.borrow().len();
+        for _ in 0..n_iters {
+            let mut node = self.current_node.clone();
+            let candidates = self.neighbors(&mut node);
+            self.current_node = candidates.into_iter()
+                .min_by_key(|c| self.guide(c))
+                .expect("step: neighbors() always returns at least the dummy sentinel node");
+            if self.is_goal() || self.improvement_trace.borrow().len() > nb_records { return true; }
+        }
+        self.is_goal()
+    }
+
+    /// returns the current best-so-far feasible coloring (same value as [`ToSolution::solution`]),
+    /// useful to poll after [`PartialWeightingLocalSearch::step`] reaches a feasible state
+    pub fn current_solution(&self) -> Vec<Vec<VertexId>> {
+        self.current_sol.iter().filter(|e| !e.is_empty()).cloned().collect()
+    }
+
+    /// per-vertex conflict weights learned so far: vertices the search has repeatedly failed
+    /// to place without conflict accumulate higher weights (see [`PartialWeightingLocalSearch::step`]),
+    /// which other heuristics can use to prioritize the "hardest" vertices, e.g.
+    /// [`crate::solvers::clique::greedy_clique::greedy_clique_from_conflict_region`]
+    pub fn weights(&self) -> &[i32] { &self.weights }
+
+    /// snapshot of every incumbent improvement recorded so far (see [`ImprovementRecord`])
+    pub fn improvement_trace(&self) -> Vec<ImprovementRecord> { self.improvement_trace.borrow().clone() }
+
+    /// shared handle to the improvement trace, kept alive across the search being moved into a
+    /// [`StatTsCombinator`] (see [`PartialWeightingLocalSearch::improvement_trace`])
+    pub fn improvement_trace_handle(&self) -> Rc<RefCell<Vec<ImprovementRecord>>> { self.improvement_trace.clone() }
+
+    /// snapshots enough state to resume the search later via [`PartialWeightingLocalSearch::resume`].
+    /// Panics if `tabu` is [`TabuKind::TwoLevel`] or [`TabuKind::Reactive`]: checkpointing those
+    /// variants is not yet supported.
+    pub fn checkpoint(&self) -> Checkpoint {
+        let tabu = match &self.tabu {
+            TabuKind::Classic(t) => t,
+            TabuKind::TwoLevel(_) => panic!("checkpoint: TabuKind::TwoLevel is not yet supported by save_checkpoint/resume"),
+            TabuKind::Reactive(_) => panic!("checkpoint: TabuKind::Reactive is not yet supported by save_checkpoint/resume"),
+        };
+        Checkpoint {
+            current_sol: self.current_solution(),
+            weights: self.weights.clone(),
+            priority_vertices: self.priority_vertices.iter().collect(),
+            fixed_vertices: self.fixed_vertices.iter().collect(),
+            delete_color_policy: self.delete_color_policy,
+            tabu_l: tabu.l,
+            tabu_lambda: tabu.lambda,
+            tabu_decisions: tabu.decisions.clone(),
+            tabu_seed: tabu.seed,
+            rng_seed: self.rng_seed,
+            nb_iter: self.nb_iter,
+        }
+    }
+
+    /// writes [`PartialWeightingLocalSearch::checkpoint`] to `filename` as JSON
+    pub fn save_checkpoint(&self, filename:&str) {
+        let content = serde_json::to_string(&self.checkpoint()).unwrap();
+        std::fs::write(filename, content)
+            .unwrap_or_else(|why| panic!("save_checkpoint: unable to write {}: {}", filename, why));
+    }
+
+    /** rebuilds a search state for `inst` from the solution, learned weights, priority
+    vertices and tabu memory saved in `checkpoint_filename` by
+    [`PartialWeightingLocalSearch::save_checkpoint`], continuing a run interrupted by a crash
+    instead of restarting it from scratch. */
+    pub fn resume(inst:Rc<dyn ColoringInstance>, checkpoint_filename:&str) -> Self {
+        let content = std::fs::read_to_string(checkpoint_filename)
+            .unwrap_or_else(|why| panic!("resume: unable to read {}: {}", checkpoint_filename, why));
+        let checkpoint:Checkpoint = serde_json::from_str(&content)
+            .unwrap_or_else(|why| panic!("resume: unable to parse {}: {}", checkpoint_filename, why));
+        let mut priority_bitset = BitSet::new();
+        for v in &checkpoint.priority_vertices { priority_bitset.insert(*v); }
+        let mut search = if checkpoint.fixed_vertices.is_empty() {
+            Self::initialize_with_priority(inst, &checkpoint.current_sol, &priority_bitset)
+        } else {
+            Self::initialize_with_priority_and_fixed_clique(
+                inst, &checkpoint.current_sol, &priority_bitset, &checkpoint.fixed_vertices
+            )
+        };
+        search.weights = checkpoint.weights;
+        // initialize built cost_coloring from unit weights: rebuild it from the restored ones
+        let mut cost_coloring = vec![vec![0 ; search.nb_initial_colors] ; search.colors.len()];
+        for u in search.inst.vertices() {
+            if let Some(c) = search.colors[u] {
+                search.inst.for_each_neighbor(u, &mut |v| cost_coloring[v][c] += search.weights[u]);
+            }
+        }
+        search.cost_coloring = cost_coloring;
+        search.delete_color_policy = checkpoint.delete_color_policy;
+        let mut tabu = TabuColTenure::with_seed(
+            checkpoint.tabu_l, checkpoint.tabu_lambda, search.colors.len(), search.nb_colors, checkpoint.tabu_seed
+        );
+        tabu.decisions = checkpoint.tabu_decisions;
+        tabu.nb_iter = checkpoint.nb_iter;
+        search.tabu = TabuKind::Classic(tabu);
+        search.rng = Rng::with_seed(crate::util::resume_rng_seed(checkpoint.rng_seed, checkpoint.nb_iter));
+        search.rng_seed = checkpoint.rng_seed;
+        search.nb_iter = checkpoint.nb_iter;
+        search
+    }
+}
+
+/** serializable subset of [`PartialWeightingLocalSearch`]'s state: the current feasible
+solution, the learned per-vertex weights, the priority vertices, the tabu memory, and both
+random number generators' seeds, written to disk periodically by
+[`coloring_partial_weighting_with_checkpointing`] so a crash during a multi-hour run loses at
+most the interval between two checkpoints, and reloaded by
+[`PartialWeightingLocalSearch::resume`] to continue from there. */
+#[derive(Clone,Debug,Serialize,Deserialize)]
+pub struct Checkpoint {
+    /// current best-so-far feasible coloring
+    current_sol: Vec<Vec<VertexId>>,
+    /// learned per-vertex weights
+    weights: Vec<Weight>,
+    /// vertices that should never be left uncolored if it can be avoided
+    priority_vertices: Vec<VertexId>,
+    /// vertices permanently locked to their current color (see
+    /// [`PartialWeightingLocalSearch::fix_clique`])
+    fixed_vertices: Vec<VertexId>,
+    /// policy used to pick which color class [`PartialWeightingLocalSearch::delete_color`] empties
+    delete_color_policy: DeleteColorPolicy,
+    /// tabu tenure fixed size
+    tabu_l: usize,
+    /// tabu tenure dynamic factor
+    tabu_lambda: f64,
+    /// tabu memory: `tabu_decisions[v][c]` is the last iteration the move "color v with c" was taken
+    tabu_decisions: Vec<Vec<i64>>,
+    /// seed of the tabu tenure's random number generator
+    tabu_seed: u64,
+    /// seed of [`PartialWeightingLocalSearch`]'s own random number generator (used for e.g.
+    /// [`UncoloredOrderingPolicy::RandomShuffle`] and tie-breaking, separate from the tabu
+    /// tenure's)
+    rng_seed: u64,
+    /// number of local search iterations performed so far
+    nb_iter: i64,
 }
 
 impl GuidedSpace<Node, i64> for PartialWeightingLocalSearch {
@@ -336,9 +1075,25 @@ impl TotalNeighborGeneration<Node> for PartialWeightingLocalSearch {
         //     }
         //     println!("After Kick: {}\t initial weight {}\t uncolored {}", self.nb_iter, self.total_weight, self.uncolored_vertices.len());
         // }
-        // for every uncolored vertex, try a possible color
-        for u in self.uncolored_vertices.iter() {
-            for (c,_) in self.colors_vertex_number.iter().enumerate().filter(|(_,n)| **n > 0) {
+        // tracked alongside best_nodes so EscapePolicy can fall back to them when no
+        // admissible non-tabu move was found (best_nodes still only holds the dummy above)
+        let mut best_ignoring_tabu:Option<Node> = None;
+        let mut random_candidate:Option<Node> = None;
+        let mut nb_candidates:u64 = 0;
+        // for every uncolored vertex, try a possible color: ordered first by `ordering_policy`
+        // (see UncoloredOrderingPolicy), then stably re-ordered so priority vertices come
+        // first regardless of policy, so that, on ties, they are the ones kept in `best_nodes`
+        let mut uncolored:Vec<VertexId> = self.uncolored_vertices.iter().collect();
+        match self.ordering_policy {
+            UncoloredOrderingPolicy::Fifo => {},
+            UncoloredOrderingPolicy::WeightDescending => uncolored.sort_by_key(|v| std::cmp::Reverse(self.weights[*v])),
+            UncoloredOrderingPolicy::DegreeDescending => uncolored.sort_by_key(|v| std::cmp::Reverse(self.inst.degree(*v))),
+            UncoloredOrderingPolicy::RandomShuffle => self.rng.shuffle(&mut uncolored),
+        }
+        uncolored.sort_by_key(|v| !self.priority_vertices.contains(*v));
+        for u in uncolored {
+            for (c,_) in self.colors_vertex_number.iter().enumerate()
+                .filter(|(c,n)| **n > 0 && !self.forbidden_colors[u].contains(*c)) {
                 let weight = self.total_weight + self.cost_coloring[u][c] - self.weights[u];
                 if weight <= best_nodes[0].total_weight {
                     let current_node = Node {
@@ -347,6 +1102,13 @@ impl TotalNeighborGeneration<Node> for PartialWeightingLocalSearch {
                         total_weight: weight,
                         nb_uncolored: self.uncolored_vertices.len(),
                     };
+                    if best_ignoring_tabu.as_ref().map_or(true, |b| weight < b.total_weight) {
+                        best_ignoring_tabu = Some(current_node.clone());
+                    }
+                    nb_candidates += 1; // reservoir sampling, for EscapePolicy::RandomMove
+                    if self.rng.u64(0..nb_candidates) == 0 {
+                        random_candidate = Some(current_node.clone());
+                    }
                     let is_tabu = self.tabu.contains(&current_node, &current_node);
                     if best_nodes[0].total_weight == Weight::MAX || !is_tabu {
                         if weight < best_nodes[0].total_weight {
@@ -357,6 +1119,19 @@ impl TotalNeighborGeneration<Node> for PartialWeightingLocalSearch {
                 }
             }
         }
+        // no admissible non-tabu move: escape per self.escape_policy instead of letting the
+        // dummy no-op stall the search (see EscapePolicy)
+        if best_nodes.len() == 1 && best_nodes[0].vertex.is_none() && self.uncolored_vertices.len() > 0 {
+            let escaped = match self.escape_policy {
+                EscapePolicy::Stall => None,
+                EscapePolicy::BestTabuMove => best_ignoring_tabu,
+                EscapePolicy::RandomMove => random_candidate,
+            };
+            if let Some(node) = escaped {
+                self.escape_activations += 1;
+                best_nodes = vec![node];
+            }
+        }
         best_nodes
     }
 }
@@ -369,13 +1144,186 @@ sol:&[Vec<VertexId>],
 perf_filename:Option<String>,
 sol_filename:Option<String>,
 stop:Stopping
+) -> Vec<Vec<VertexId>> {
+    coloring_partial_weighting_with_priority(inst, sol, &[], perf_filename, sol_filename, stop)
+}
+
+/** same as [`coloring_partial_weighting`], but additionally marks `priority_vertices` (e.g.
+vertices known to belong to a near-maximum clique, from [`crate::solvers::clique::greedy_clique::near_max_clique_vertices`])
+as vertices that should never be left uncolored if it can be avoided, tightening the
+coupling between the lower-bound (clique) and upper-bound (coloring) phases. */
+pub fn coloring_partial_weighting_with_priority<Stopping:StoppingCriterion>(
+inst:Rc<dyn ColoringInstance>,
+sol:&[Vec<VertexId>],
+priority_vertices:&[VertexId],
+perf_filename:Option<String>,
+sol_filename:Option<String>,
+stop:Stopping
+) -> Vec<Vec<VertexId>> {
+    let mut solution:Vec<Vec<VertexId>> = sol.to_vec();
+    let mut priority_bitset = BitSet::new();
+    for v in priority_vertices { priority_bitset.insert(*v); }
+    let logger = Rc::new(MetricLogger::default());
+    let search = PartialWeightingLocalSearch::initialize_with_priority(inst.clone(), &solution, &priority_bitset);
+    let trace_handle = search.improvement_trace_handle();
+    let space = Rc::new(RefCell::new(
+        StatTsCombinator::new(search).bind_logger(Rc::downgrade(&logger)),
+    ));
+    let mut ts = Greedy::new(space.clone());
+    logger.display_headers();
+    ts.run(stop);
+    // display the results afterwards
+    space.borrow_mut().display_statistics();
+    // check that the last solution is valid
+    match ts.get_manager().best() {
+        None => {
+            println!("\tlocal search failed improving...");
+        }
+        Some(node) => {
+            assert_eq!(node.total_weight, 0);
+            solution = space.borrow_mut().solution(&mut node.clone());
+        }
+    }
+    let mut stats = serde_json::Value::default();
+    space.borrow_mut().json_statistics(&mut stats);
+    export_results_with_trace(
+        inst,
+        &solution,
+        &stats,
+        perf_filename,
+        sol_filename,
+        true,
+        &trace_handle.borrow(),
+    );
+    solution
+}
+
+/** same as [`coloring_partial_weighting`], but using a [`TabuKind::Classic`] tenure built
+from `tabu_params` instead of the historical hardcoded `(10, 0.01)`, so callers can tune
+tenure without forking the crate. */
+pub fn coloring_partial_weighting_with_tabu_params<Stopping:StoppingCriterion>(
+inst:Rc<dyn ColoringInstance>,
+sol:&[Vec<VertexId>],
+perf_filename:Option<String>,
+sol_filename:Option<String>,
+stop:Stopping,
+tabu_params:TabuParams,
+) -> Vec<Vec<VertexId>> {
+    let mut solution:Vec<Vec<VertexId>> = sol.to_vec();
+    let logger = Rc::new(MetricLogger::default());
+    let search = PartialWeightingLocalSearch::initialize_with_priority_and_tabu_params(
+        inst.clone(), &solution, &BitSet::new(), tabu_params
+    );
+    let trace_handle = search.improvement_trace_handle();
+    let space = Rc::new(RefCell::new(
+        StatTsCombinator::new(search).bind_logger(Rc::downgrade(&logger)),
+    ));
+    let mut ts = Greedy::new(space.clone());
+    logger.display_headers();
+    ts.run(stop);
+    // display the results afterwards
+    space.borrow_mut().display_statistics();
+    // check that the last solution is valid
+    match ts.get_manager().best() {
+        None => {
+            println!("\tlocal search failed improving...");
+        }
+        Some(node) => {
+            assert_eq!(node.total_weight, 0);
+            solution = space.borrow_mut().solution(&mut node.clone());
+        }
+    }
+    let mut stats = serde_json::Value::default();
+    space.borrow_mut().json_statistics(&mut stats);
+    export_results_with_trace(
+        inst,
+        &solution,
+        &stats,
+        perf_filename,
+        sol_filename,
+        true,
+        &trace_handle.borrow(),
+    );
+    solution
+}
+
+/** same as [`coloring_partial_weighting`], but emitting one JSON object per improvement on
+stdout instead of [`MetricLogger`]'s tabular output, so downstream tooling can monitor a run's
+progress (iteration, colors, weight, time) without screen-scraping. Under [`LogFormat::Json`],
+[`MetricLogger`]'s own header/table is skipped so stdout stays a clean stream of JSON objects;
+[`LogFormat::Text`] reproduces [`coloring_partial_weighting`]'s historical output exactly. */
+pub fn coloring_partial_weighting_with_log_format<Stopping:StoppingCriterion>(
+inst:Rc<dyn ColoringInstance>,
+sol:&[Vec<VertexId>],
+perf_filename:Option<String>,
+sol_filename:Option<String>,
+stop:Stopping,
+log_format:LogFormat,
+) -> Vec<Vec<VertexId>> {
+    let mut solution:Vec<Vec<VertexId>> = sol.to_vec();
+    let logger = Rc::new(MetricLogger::default());
+    let search = PartialWeightingLocalSearch::initialize_with_priority_and_log_format(
+        inst.clone(), &solution, &BitSet::new(), log_format
+    );
+    let trace_handle = search.improvement_trace_handle();
+    let space = Rc::new(RefCell::new(
+        StatTsCombinator::new(search).bind_logger(Rc::downgrade(&logger)),
+    ));
+    let mut ts = Greedy::new(space.clone());
+    if log_format == LogFormat::Text { logger.display_headers(); }
+    ts.run(stop);
+    // display the results afterwards
+    if log_format == LogFormat::Text { space.borrow_mut().display_statistics(); }
+    // check that the last solution is valid
+    match ts.get_manager().best() {
+        None => {
+            println!("\tlocal search failed improving...");
+        }
+        Some(node) => {
+            assert_eq!(node.total_weight, 0);
+            solution = space.borrow_mut().solution(&mut node.clone());
+        }
+    }
+    let mut stats = serde_json::Value::default();
+    space.borrow_mut().json_statistics(&mut stats);
+    export_results_with_trace(
+        inst,
+        &solution,
+        &stats,
+        perf_filename,
+        sol_filename,
+        true,
+        &trace_handle.borrow(),
+    );
+    solution
+}
+
+/** same as [`coloring_partial_weighting_with_priority`], but additionally permanently fixes
+every vertex in `clique` (e.g. from [`crate::solvers::clique::greedy_clique::near_max_clique_vertices`])
+to the distinct color it already holds in `sol`, shrinking the effective search space by
+never considering uncoloring or recoloring them (see
+[`PartialWeightingLocalSearch::fix_clique`]). Most useful when `clique.len()` matches (or is
+close to) the number of colors being targeted, since that is when fixing the clique removes
+the most search freedom without risking ruling out the optimum. */
+pub fn coloring_partial_weighting_with_fixed_clique<Stopping:StoppingCriterion>(
+inst:Rc<dyn ColoringInstance>,
+sol:&[Vec<VertexId>],
+priority_vertices:&[VertexId],
+clique:&[VertexId],
+perf_filename:Option<String>,
+sol_filename:Option<String>,
+stop:Stopping
 ) -> Vec<Vec<VertexId>> {
     let mut solution:Vec<Vec<VertexId>> = sol.to_vec();
+    let mut priority_bitset = BitSet::new();
+    for v in priority_vertices { priority_bitset.insert(*v); }
     let logger = Rc::new(MetricLogger::default());
+    let search = PartialWeightingLocalSearch::initialize_with_priority_and_fixed_clique(
+        inst.clone(), &solution, &priority_bitset, clique
+    );
+    let trace_handle = search.improvement_trace_handle();
     let space = Rc::new(RefCell::new(
-        StatTsCombinator::new(
-            PartialWeightingLocalSearch::initialize(inst.clone(), &solution),
-        ).bind_logger(Rc::downgrade(&logger)),
+        StatTsCombinator::new(search).bind_logger(Rc::downgrade(&logger)),
     ));
     let mut ts = Greedy::new(space.clone());
     logger.display_headers();
@@ -390,29 +1338,230 @@ stop:Stopping
         Some(node) => {
             assert_eq!(node.total_weight, 0);
             solution = space.borrow_mut().solution(&mut node.clone());
-        }  
+        }
     }
     let mut stats = serde_json::Value::default();
     space.borrow_mut().json_statistics(&mut stats);
-    export_results(
+    export_results_with_trace(
         inst,
         &solution,
         &stats,
         perf_filename,
         sol_filename,
-        true
+        true,
+        &trace_handle.borrow(),
     );
     solution
 }
 
+/** same objective as [`coloring_partial_weighting`], but drives the search through repeated
+[`PartialWeightingLocalSearch::step`] calls instead of [`dogs::tree_search::greedy::Greedy`],
+writing a [`PartialWeightingLocalSearch::checkpoint`] to `checkpoint_filename` every
+`checkpoint_interval_secs` seconds of wall-clock time so a crash during a multi-hour
+competition run loses at most that much progress. If `resume` is true, `checkpoint_filename`
+is loaded as the starting state (via [`PartialWeightingLocalSearch::resume`]) instead of
+initializing fresh from `sol`. `time_limit_secs` is measured against `time_basis` (wall-clock
+or process CPU time, see [`TimeBasis`]), so single- and multi-threaded variants can be
+budgeted fairly; both clocks are reported in the exported stats regardless of which one gated
+the stop. */
+pub fn coloring_partial_weighting_with_checkpointing(
+inst:Rc<dyn ColoringInstance>,
+sol:&[Vec<VertexId>],
+time_limit_secs:f32,
+time_basis:TimeBasis,
+checkpoint_interval_secs:f32,
+checkpoint_filename:&str,
+resume:bool,
+perf_filename:Option<String>,
+sol_filename:Option<String>,
+) -> Vec<Vec<VertexId>> {
+    let mut search = if resume {
+        PartialWeightingLocalSearch::resume(inst.clone(), checkpoint_filename)
+    } else {
+        PartialWeightingLocalSearch::initialize_with_priority(inst.clone(), sol, &BitSet::new())
+    };
+    let clock = RunClock::start();
+    let mut last_checkpoint = Instant::now();
+    loop {
+        let is_over = clock.elapsed_secs(time_basis) >= time_limit_secs;
+        search.step(1_000);
+        if is_over || last_checkpoint.elapsed().as_secs_f32() >= checkpoint_interval_secs {
+            search.save_checkpoint(checkpoint_filename);
+            last_checkpoint = Instant::now();
+        }
+        if is_over { break; }
+    }
+    let solution = search.current_solution();
+    let stats = serde_json::json!({
+        "wall_time_secs": clock.wall_secs(),
+        "cpu_time_secs": clock.cpu_secs(),
+        "peak_rss_growth_bytes": clock.peak_rss_growth_bytes(),
+    });
+    export_results_with_trace(inst, &solution, &stats, perf_filename, sol_filename, true, &search.improvement_trace());
+    solution
+}
+
+/** same objective as [`coloring_partial_weighting`], but first spends `clique_time_secs`
+improving a [`greedy_clique`] with [`clique_partial_weighting`] to get a chromatic lower
+bound, records it as `"lower_bound"` in the exported stats (`"gap"` alongside it, the
+difference to the current best coloring), and stops the local search as soon as its current
+solution matches it: no coloring can use fewer colors than a clique already found in the
+graph, so the search can declare victory rather than running out the rest of
+`time_limit_secs`. `time_limit_secs` is measured against `time_basis` (see [`TimeBasis`]) and
+does not include `clique_time_secs`, spent up front. */
+pub fn coloring_partial_weighting_with_lower_bound(
+inst:Rc<dyn ColoringInstance>,
+sol:&[Vec<VertexId>],
+time_limit_secs:f32,
+time_basis:TimeBasis,
+clique_time_secs:f32,
+perf_filename:Option<String>,
+sol_filename:Option<String>,
+) -> Vec<Vec<VertexId>> {
+    let greedy = greedy_clique(inst.clone());
+    let improved_clique = clique_partial_weighting(
+        inst.clone(), &greedy, None, None, TimeStoppingCriterion::new(clique_time_secs)
+    ).remove(0);
+    let lower_bound = improved_clique.len();
+    let mut search = PartialWeightingLocalSearch::initialize_with_priority(inst.clone(), sol, &BitSet::new());
+    let clock = RunClock::start();
+    while search.current_solution().len() > lower_bound
+    && clock.elapsed_secs(time_basis) < time_limit_secs {
+        search.step(1_000);
+    }
+    let solution = search.current_solution();
+    let stats = serde_json::json!({
+        "wall_time_secs": clock.wall_secs(),
+        "cpu_time_secs": clock.cpu_secs(),
+        "lower_bound": lower_bound,
+        "gap": solution.len() as i64 - lower_bound as i64,
+        "peak_rss_growth_bytes": clock.peak_rss_growth_bytes(),
+    });
+    export_results_with_trace(inst, &solution, &stats, perf_filename, sol_filename, true, &search.improvement_trace());
+    solution
+}
+
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
-    use dogs::search_algorithm::TimeStoppingCriterion;
-    
-    use crate::{cgshop::CGSHOPInstance, solvers::coloring::greedy_dsatur::greedy_dsatur};
+    use crate::{cgshop::CGSHOPInstance, color::{checker, CheckerResult}, dimacs::DimacsInstance, solvers::coloring::greedy_dsatur::greedy_dsatur};
+
+    #[test]
+    fn test_delete_color_default_policy_empties_smallest_class() {
+        // grid2x2 is a 4-cycle (0-1-2-3-0); {1,3} is an independent set (distance 2 apart),
+        // so {0},{1,3},{2} is a valid proper 3-coloring with an uneven class split
+        let inst = Rc::new(DimacsInstance::from_file("insts/grid-instances/grid2x2"));
+        let sol = vec![vec![0], vec![1, 3], vec![2]];
+        let mut search = PartialWeightingLocalSearch::initialize_with_priority(inst, &sol, &BitSet::new());
+        search.delete_color();
+        // the smallest class (a singleton) must have been emptied, not the largest one
+        assert!(search.colors[1].is_some() && search.colors[3].is_some());
+    }
+
+    #[test]
+    fn test_pwls_respects_fixed_color() {
+        use crate::precoloring::PrecoloredInstance;
+        let mut precolored = PrecoloredInstance::new(
+            Rc::new(DimacsInstance::from_file("insts/grid-instances/grid2x2"))
+        );
+        precolored.fix(0, 3);
+        let inst:Rc<dyn ColoringInstance> = Rc::new(precolored);
+        let greedy_sol = greedy_dsatur(inst.clone(), false);
+        let mut search = PartialWeightingLocalSearch::initialize_with_priority(inst, &greedy_sol, &BitSet::new());
+        assert!(search.step(1_000));
+        assert_eq!(search.colors[0], Some(3));
+    }
+
+    #[test]
+    fn test_pwls_with_degree_descending_ordering() {
+        let inst = Rc::new(DimacsInstance::from_file("insts/grid-instances/grid2x2"));
+        let greedy_sol = greedy_dsatur(inst.clone(), false);
+        let mut search = PartialWeightingLocalSearch::initialize_with_priority_and_ordering_policy(
+            inst, &greedy_sol, &BitSet::new(), UncoloredOrderingPolicy::DegreeDescending
+        );
+        assert!(search.step(1_000));
+    }
+
+    #[test]
+    fn test_pwls_with_tabu_params() {
+        let inst = Rc::new(DimacsInstance::from_file("insts/grid-instances/grid2x2"));
+        let greedy_sol = greedy_dsatur(inst.clone(), false);
+        let mut search = PartialWeightingLocalSearch::initialize_with_priority_and_tabu_params(
+            inst, &greedy_sol, &BitSet::new(), TabuParams { l:5, lambda:0.02 }
+        );
+        assert!(search.step(1_000));
+    }
+
+    #[test]
+    fn test_pwls_with_reactive_tenure_reaches_goal() {
+        let inst = Rc::new(DimacsInstance::from_file("insts/grid-instances/grid2x2"));
+        let greedy_sol = greedy_dsatur(inst.clone(), false);
+        let n = inst.nb_vertices();
+        let nb_colors = greedy_sol.len();
+        let tabu = TabuKind::Reactive(ReactiveTenure::new(5, 0.02, n, nb_colors));
+        let mut search = PartialWeightingLocalSearch::initialize_with_priority_and_tabu_kind(
+            inst, &greedy_sol, &BitSet::new(), tabu
+        );
+        assert!(search.step(1_000));
+    }
+
+    #[test]
+    fn test_pwls_with_log_format_json_reaches_goal() {
+        let inst = Rc::new(DimacsInstance::from_file("insts/grid-instances/grid2x2"));
+        let greedy_sol = greedy_dsatur(inst.clone(), false);
+        let mut search = PartialWeightingLocalSearch::initialize_with_priority_and_log_format(
+            inst, &greedy_sol, &BitSet::new(), LogFormat::Json
+        );
+        assert!(search.step(1_000));
+    }
+
+    #[test]
+    fn test_pwls_with_on_new_solution_reports_incumbents() {
+        let inst = Rc::new(DimacsInstance::from_file("insts/grid-instances/grid2x2"));
+        let greedy_sol = greedy_dsatur(inst.clone(), false);
+        let calls = Rc::new(RefCell::new(0usize));
+        let calls_handle = calls.clone();
+        let callback:Rc<RefCell<dyn FnMut(&[Vec<VertexId>], f32)>> = Rc::new(RefCell::new(
+            move |_sol:&[Vec<VertexId>], _time:f32| { *calls_handle.borrow_mut() += 1; }
+        ));
+        let mut search = PartialWeightingLocalSearch::initialize_with_priority_and_on_new_solution(
+            inst, &greedy_sol, &BitSet::new(), callback
+        );
+        assert!(search.step(1_000));
+        assert!(*calls.borrow() > 0);
+    }
+
+    #[test]
+    fn test_pwls_with_priority() {
+        use crate::solvers::clique::greedy_clique::near_max_clique_vertices;
+        let inst = Rc::new(CGSHOPInstance::from_file(
+            "./insts/CGSHOP_22_original/cgshop_2022_examples_01/tiny.json"
+        ));
+        let priority = near_max_clique_vertices(inst.clone(), 5, 1);
+        let greedy_sol = greedy_dsatur(inst.clone(), false);
+        let stopping_criterion:TimeStoppingCriterion = TimeStoppingCriterion::new(1.);
+        let final_sol = coloring_partial_weighting_with_priority(
+            inst, &greedy_sol, &priority, None, None, stopping_criterion
+        );
+        assert!(!final_sol.is_empty());
+    }
+
+    #[test]
+    fn test_pwls_with_fixed_clique() {
+        use crate::solvers::clique::greedy_clique::greedy_clique;
+        let inst = Rc::new(CGSHOPInstance::from_file(
+            "./insts/CGSHOP_22_original/cgshop_2022_examples_01/tiny.json"
+        ));
+        let clique = greedy_clique(inst.clone());
+        let greedy_sol = greedy_dsatur(inst.clone(), false);
+        let stopping_criterion:TimeStoppingCriterion = TimeStoppingCriterion::new(1.);
+        let final_sol = coloring_partial_weighting_with_fixed_clique(
+            inst, &greedy_sol, &[], &clique, None, None, stopping_criterion
+        );
+        assert!(!final_sol.is_empty());
+    }
 
     #[test]
     fn test_pwls() {
@@ -578,4 +1727,81 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_checkpoint_resume_restores_rng_seed() {
+        let inst:Rc<dyn ColoringInstance> = Rc::new(DimacsInstance::from_file("insts/grid-instances/grid2x2"));
+        let greedy_sol = greedy_dsatur(inst.clone(), false);
+        let mut search = PartialWeightingLocalSearch::initialize_with_priority(inst.clone(), &greedy_sol, &BitSet::new());
+        search.step(10);
+        let filename = std::env::temp_dir().join("dogs_color_test_pwls_checkpoint.json");
+        let filename = filename.to_str().unwrap();
+        search.save_checkpoint(filename);
+        let resumed = PartialWeightingLocalSearch::resume(inst, filename);
+        assert_eq!(resumed.rng_seed, search.rng_seed);
+        assert_eq!(resumed.current_solution().len(), search.current_solution().len());
+    }
+
+    #[test]
+    fn test_checkpoint_resume_does_not_replay_the_original_draw_stream() {
+        let inst:Rc<dyn ColoringInstance> = Rc::new(DimacsInstance::from_file("insts/grid-instances/grid2x2"));
+        let greedy_sol = greedy_dsatur(inst.clone(), false);
+        let mut search = PartialWeightingLocalSearch::initialize_with_priority(inst.clone(), &greedy_sol, &BitSet::new());
+        search.step(10);
+        let filename = std::env::temp_dir().join("dogs_color_test_pwls_checkpoint_rng.json");
+        let filename = filename.to_str().unwrap();
+        search.save_checkpoint(filename);
+        let mut resumed = PartialWeightingLocalSearch::resume(inst, filename);
+        // rewinding to the raw original seed would replay the exact draws already consumed
+        // near the start of the original run; resuming must draw a different sequence instead
+        let mut rewound = Rng::with_seed(search.rng_seed);
+        let draws_if_rewound:Vec<u64> = (0..10).map(|_| rewound.u64(..)).collect();
+        let draws_from_resumed:Vec<u64> = (0..10).map(|_| resumed.rng.u64(..)).collect();
+        assert_ne!(draws_if_rewound, draws_from_resumed);
+    }
+
+    #[test]
+    fn test_checkpoint_resume_on_cgshop_instance() {
+        // checkpointing is exercised above on a DIMACS instance; CGSHOP runs are the ones that
+        // actually last hours and need it, so make sure the same round-trip works there too
+        let inst:Rc<dyn ColoringInstance> = Rc::new(CGSHOPInstance::from_file(
+            "./insts/cgshop_22_examples/tiny10.instance.json"
+        ));
+        let greedy_sol = greedy_dsatur(inst.clone(), false);
+        let mut search = PartialWeightingLocalSearch::initialize_with_priority(inst.clone(), &greedy_sol, &BitSet::new());
+        search.step(10);
+        let filename = std::env::temp_dir().join("dogs_color_test_pwls_cgshop_checkpoint.json");
+        let filename = filename.to_str().unwrap();
+        search.save_checkpoint(filename);
+        let resumed = PartialWeightingLocalSearch::resume(inst, filename);
+        assert_eq!(resumed.rng_seed, search.rng_seed);
+        assert_eq!(resumed.current_solution().len(), search.current_solution().len());
+    }
+
+    #[test]
+    fn test_pwls_improvement_trace_is_monotonically_decreasing() {
+        let inst:Rc<dyn ColoringInstance> = Rc::new(DimacsInstance::from_file("insts/grid-instances/grid2x2"));
+        let greedy_sol = greedy_dsatur(inst.clone(), false);
+        let mut search = PartialWeightingLocalSearch::initialize_with_priority(inst, &greedy_sol, &BitSet::new());
+        search.step(1_000);
+        let trace = search.improvement_trace();
+        assert!(trace.len() > 1);
+        for (a, b) in trace.iter().zip(trace.iter().skip(1)) {
+            assert!(b.value <= a.value);
+            assert!(b.iteration >= a.iteration);
+        }
+    }
+
+    #[test]
+    fn test_coloring_partial_weighting_with_lower_bound_reaches_clique_size_on_grid2x2() {
+        // grid2x2 is a 4-cycle: clique number 2, chromatic number 2, so the search should stop
+        // as soon as it reaches the lower bound instead of running out its full time budget
+        let inst:Rc<dyn ColoringInstance> = Rc::new(DimacsInstance::from_file("insts/grid-instances/grid2x2"));
+        let greedy_sol = greedy_dsatur(inst.clone(), false);
+        let solution = coloring_partial_weighting_with_lower_bound(
+            inst.clone(), &greedy_sol, 60., TimeBasis::Wall, 0.2, None, None
+        );
+        assert_eq!(solution.len(), 2);
+        assert_eq!(checker(inst, &solution), CheckerResult::Ok(solution.len()));
+    }
+
 }
@@ -0,0 +1,158 @@
+//! parallel multi-start driver for the weighting local searches
+//! ([`ConflictWeightingLocalSearch`], [`PartialWeightingLocalSearch`]): unlike a naive
+//! multi-start that only compares the independent workers' results once every one of them has
+//! run to completion, [`run_portfolio`]'s workers publish each improved color count into a
+//! shared [`AtomicUsize`] as soon as they reach it (via `fetch_min`, logging it when it beats
+//! every count published so far). A worker reads the shared value only when it publishes, and
+//! does not change course when another worker is ahead. Neither local search is a branching tree search, so there is
+//! no search-space bound to literally prune with the shared value the way a branch-and-bound
+//! solver would; instead, [`log_tagged`] reports each improvement as it crosses threads, which
+//! is the honest amount of coupling two independent incremental local searches can offer each
+//! other without sharing (and corrupting) their internal incremental state.
+
+use std::rc::Rc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::thread;
+
+use bit_set::BitSet;
+
+use crate::color::{Solution, VertexId};
+use crate::solvers::coloring::conflict_weighting::ConflictWeightingLocalSearch;
+use crate::solvers::coloring::partial_weighting::PartialWeightingLocalSearch;
+use crate::util::{export_results, load_instance, log_tagged, RunClock, TimeBasis};
+
+/// which weighting local search [`run_portfolio`]'s workers each run a copy of
+#[derive(Clone,Copy,Debug,Eq,PartialEq)]
+pub enum PortfolioSolver {
+    /// each worker runs [`ConflictWeightingLocalSearch`]
+    ConflictWeighting,
+    /// each worker runs [`PartialWeightingLocalSearch`]
+    PartialWeighting,
+}
+
+/// the two local searches driven by [`run_portfolio`] don't share a common public trait for
+/// stepping and reading back the current solution, so this private trait lets one generic
+/// worker loop drive either
+trait SteppableLocalSearch {
+    /// advances the search by at most `n_iters` decisions (see e.g.
+    /// [`ConflictWeightingLocalSearch::step`])
+    fn step(&mut self, n_iters:usize) -> bool;
+    /// current best-so-far feasible coloring (see e.g.
+    /// [`ConflictWeightingLocalSearch::current_solution`])
+    fn current_solution(&self) -> Solution;
+}
+
+impl SteppableLocalSearch for ConflictWeightingLocalSearch {
+    fn step(&mut self, n_iters:usize) -> bool { ConflictWeightingLocalSearch::step(self, n_iters) }
+    fn current_solution(&self) -> Solution { ConflictWeightingLocalSearch::current_solution(self) }
+}
+
+impl SteppableLocalSearch for PartialWeightingLocalSearch {
+    fn step(&mut self, n_iters:usize) -> bool { PartialWeightingLocalSearch::step(self, n_iters) }
+    fn current_solution(&self) -> Solution { PartialWeightingLocalSearch::current_solution(self) }
+}
+
+/** drives `search` to `time_limit` seconds of wall-clock time, stepping `exchange_interval_iters`
+decisions at a time so the loop can check back against `shared_best` after each chunk: every
+time `search`'s own current solution beats `shared_best`, it is published via
+[`AtomicUsize::fetch_min`] and logged with [`log_tagged`] (tagged `worker_id`) so the
+improvement is visible to (and in) every other worker's logs as soon as it happens, instead of
+only once every worker has finished. Returns the best coloring `search` itself reached. */
+fn run_worker<S:SteppableLocalSearch>(
+    mut search:S,
+    worker_id:usize,
+    shared_best:&AtomicUsize,
+    time_limit:f32,
+    exchange_interval_iters:usize,
+) -> Solution {
+    let tag = format!("worker-{}", worker_id);
+    let clock = RunClock::start();
+    let mut best = search.current_solution();
+    loop {
+        search.step(exchange_interval_iters);
+        let current = search.current_solution();
+        if !current.is_empty() && current.len() < best.len() {
+            best = current;
+            let previous_best = shared_best.fetch_min(best.len(), Ordering::Relaxed);
+            if best.len() < previous_best {
+                log_tagged(Some(&tag), &format!("new shared best: {} colors", best.len()));
+            }
+        }
+        if clock.elapsed_secs(TimeBasis::Wall) >= time_limit { break; }
+    }
+    best
+}
+
+/** runs `nb_workers` independently seeded copies of `solver` on `inst_filename` concurrently,
+one thread per worker (each re-reading its own copy of the instance via [`load_instance`],
+since a coloring instance is not shareable across threads), exchanging improved color counts
+as described in the module docs, and keeps the best (fewest colors) result across every
+worker. */
+pub fn run_portfolio(
+    inst_filename:&str,
+    sol:&[Vec<VertexId>],
+    solver:PortfolioSolver,
+    nb_workers:usize,
+    time_limit:f32,
+    exchange_interval_iters:usize,
+    perf_filename:Option<String>,
+    sol_filename:Option<String>,
+) -> Solution {
+    let nb_workers = nb_workers.max(1);
+    let shared_best = Arc::new(AtomicUsize::new(sol.len()));
+    let best = thread::scope(|scope| {
+        let handles:Vec<_> = (0..nb_workers).map(|worker_id| {
+            let sol = sol.to_vec();
+            let shared_best = Arc::clone(&shared_best);
+            scope.spawn(move || {
+                let (inst, _) = load_instance(inst_filename);
+                match solver {
+                    PortfolioSolver::ConflictWeighting => {
+                        let search = ConflictWeightingLocalSearch::initialize(inst, &sol);
+                        run_worker(search, worker_id, &shared_best, time_limit, exchange_interval_iters)
+                    }
+                    PortfolioSolver::PartialWeighting => {
+                        let search = PartialWeightingLocalSearch::initialize_with_priority(inst, &sol, &BitSet::new());
+                        run_worker(search, worker_id, &shared_best, time_limit, exchange_interval_iters)
+                    }
+                }
+            })
+        }).collect();
+        handles.into_iter()
+            .map(|h| h.join().expect("run_portfolio: a worker thread panicked"))
+            .filter(|s| !s.is_empty())
+            .min_by_key(|s| s.len())
+            .expect("run_portfolio: at least one worker produced a feasible solution")
+    });
+    let (inst, _) = load_instance(inst_filename);
+    export_results(inst, &best, &serde_json::Value::default(), perf_filename, sol_filename, true);
+    best
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::color::{checker, ColoringInstance};
+    use crate::solvers::coloring::greedy_dsatur::greedy_dsatur;
+
+    #[test]
+    fn test_run_portfolio_improves_or_matches_initial_solution() {
+        let (inst, _) = load_instance("insts/grid-instances/grid2x2");
+        let greedy_sol = greedy_dsatur(inst.clone(), false);
+        let initial_len = greedy_sol.len();
+        let best = run_portfolio(
+            "insts/grid-instances/grid2x2",
+            &greedy_sol,
+            PortfolioSolver::ConflictWeighting,
+            2,
+            1.0,
+            200,
+            None,
+            None,
+        );
+        assert!(best.len() <= initial_len);
+        assert_eq!(checker(inst, &best), crate::color::CheckerResult::Ok(best.len()));
+    }
+}
@@ -0,0 +1,72 @@
+//! a latency-optimized "quick mode" construction for interactive callers that need *a* valid
+//! coloring back within a hard deadline, and would rather get a mediocre one on time than a
+//! good one late. Skips any preprocessing beyond the adjacency `inst` already exposes, and
+//! uses plain sequential first-fit (cheaper than [`crate::solvers::coloring::greedy_dsatur::greedy_dsatur`]'s
+//! saturation-degree bookkeeping or [`crate::solvers::coloring::greedy_rlf::greedy_rlf`]'s
+//! per-class growth, at the cost of generally using more colors).
+
+use std::rc::Rc;
+use std::time::Instant;
+
+use bit_set::BitSet;
+
+use crate::color::{ColoringInstance, Solution, VertexId};
+
+/** colors `inst` by sequential first-fit, checking the clock after every vertex; the instant
+`deadline_secs` is exceeded, every remaining uncolored vertex is immediately given its own
+brand-new singleton color (always valid, O(1) each) instead of spending more time trying to
+reuse existing ones. This makes the deadline a hard guarantee: total runtime is bounded by
+`deadline_secs` plus O(n - k) for whatever k vertices were reached in time, never by how long
+first-fit itself would otherwise take to run to completion. */
+pub fn quick_coloring(inst:Rc<dyn ColoringInstance>, deadline_secs:f32) -> Solution {
+    let start = Instant::now();
+    let n = inst.nb_vertices();
+    let mut colors:Vec<Option<usize>> = vec![None ; n];
+    let mut classes:Vec<BitSet> = Vec::new();
+    for u in 0..n {
+        if start.elapsed().as_secs_f32() >= deadline_secs {
+            break;
+        }
+        let neighbors = inst.neighbors(u);
+        let c = (0..classes.len())
+            .find(|&c| neighbors.iter().all(|&v| colors[v] != Some(c)))
+            .unwrap_or_else(|| { classes.push(BitSet::new()); classes.len() - 1 });
+        colors[u] = Some(c);
+        classes[c].insert(u);
+    }
+    // anything left uncolored (the deadline hit, or the vertex was never reached) gets its own
+    // brand-new singleton color: always valid, no conflict possible
+    for u in 0..n {
+        if colors[u].is_none() {
+            classes.push(BitSet::new());
+            let c = classes.len() - 1;
+            colors[u] = Some(c);
+            classes[c].insert(u);
+        }
+    }
+    classes.iter().map(|c| c.iter().collect::<Vec<VertexId>>()).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::color::{checker, CheckerResult};
+    use crate::dimacs::DimacsInstance;
+
+    #[test]
+    fn test_quick_coloring_with_generous_deadline_is_proper() {
+        let inst:Rc<dyn ColoringInstance> = Rc::new(DimacsInstance::from_file("insts/grid-instances/grid2x2"));
+        let solution = quick_coloring(inst.clone(), 5.0);
+        assert_eq!(checker(inst, &solution), CheckerResult::Ok(solution.len()));
+    }
+
+    #[test]
+    fn test_quick_coloring_with_zero_deadline_still_proper() {
+        // an immediately-expired deadline must still yield a valid (fully singleton) coloring
+        let inst:Rc<dyn ColoringInstance> = Rc::new(DimacsInstance::from_file("insts/grid-instances/grid2x2"));
+        let solution = quick_coloring(inst.clone(), 0.0);
+        assert_eq!(checker(inst.clone(), &solution), CheckerResult::Ok(solution.len()));
+        assert_eq!(solution.len(), inst.nb_vertices());
+    }
+}
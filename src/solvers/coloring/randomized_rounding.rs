@@ -0,0 +1,95 @@
+//! randomized-rounding construction from a fractional "set cover"-style class selection:
+//! given a list of candidate independent-set classes, each with a fractional selection
+//! weight (e.g. from an LP relaxation of the set-covering formulation of graph coloring —
+//! "select a minimum-weight collection of independent sets covering every vertex"),
+//! probabilistically rounds them into a full initial coloring, then lets
+//! [`coloring_partial_weighting`] merge it down to a high-quality one. Does not depend on any
+//! particular LP solver: the fractional solution is taken as a plain input, so this is ready
+//! to be wired up once this repository gains set-cover lower-bound machinery of its own (it
+//! currently has none).
+
+use std::rc::Rc;
+
+use fastrand::Rng;
+
+use dogs::search_algorithm::TimeStoppingCriterion;
+
+use crate::color::{ColoringInstance, VertexId};
+use crate::solvers::coloring::partial_weighting::coloring_partial_weighting;
+
+/// one candidate color class of a fractional set-cover solution: an independent set of
+/// vertices together with its LP selection weight
+pub struct FractionalClass {
+    /// the independent set of vertices making up this class
+    pub vertices: Vec<VertexId>,
+    /// its fractional selection weight in the LP relaxation, typically in `[0,1]`
+    pub weight: f64,
+}
+
+/** converts a fractional class selection `classes` into a full initial coloring by
+randomized rounding, then lets [`coloring_partial_weighting`] repair and improve it for
+`repair_time_secs` seconds. Each class is independently selected with probability
+`min(1.0, class.weight * boost)` (`boost` compensates for a single rounding pass rarely
+covering every vertex outright, the usual practice when rounding a fractional set cover);
+vertices are assigned to the first selected class containing them, so selected classes being
+independent sets guarantees the result stays a proper coloring with nothing to repair
+conflict-wise. Any vertex still uncovered once every class has been drawn is given its own
+singleton class, so the result is always a valid starting coloring, at the cost of a few
+extra colors for the repair pass to merge away. */
+pub fn randomized_rounding_construction(
+    inst:Rc<dyn ColoringInstance>,
+    classes:&[FractionalClass],
+    boost:f64,
+    repair_time_secs:f32,
+) -> Vec<Vec<VertexId>> {
+    let n = inst.nb_vertices();
+    let mut rng = Rng::default();
+    let mut colors:Vec<Option<usize>> = vec![None ; n];
+    let mut partition:Vec<Vec<VertexId>> = Vec::new();
+    for class in classes {
+        if rng.f64() <= (class.weight * boost).min(1.0) {
+            let c = partition.len();
+            let mut members = Vec::new();
+            for &v in &class.vertices {
+                if colors[v].is_none() {
+                    colors[v] = Some(c);
+                    members.push(v);
+                }
+            }
+            if !members.is_empty() {
+                partition.push(members);
+            }
+        }
+    }
+    // every vertex still uncovered after rounding gets its own singleton class
+    for v in 0..n {
+        if colors[v].is_none() {
+            let c = partition.len();
+            colors[v] = Some(c);
+            partition.push(vec![v]);
+        }
+    }
+    coloring_partial_weighting(inst, &partition, None, None, TimeStoppingCriterion::new(repair_time_secs))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::color::{checker, CheckerResult};
+    use crate::dimacs::DimacsInstance;
+    use crate::solvers::coloring::greedy_dsatur::greedy_dsatur;
+
+    #[test]
+    fn test_randomized_rounding_from_full_weight_classes_is_proper() {
+        // selecting every class of an already-proper coloring with weight 1.0 (and boost 1.0,
+        // which always draws it) must round-trip to the same coloring, still proper
+        let inst:Rc<dyn ColoringInstance> = Rc::new(DimacsInstance::from_file("insts/grid-instances/grid2x2"));
+        let greedy_sol = greedy_dsatur(inst.clone(), false);
+        let classes:Vec<FractionalClass> = greedy_sol.iter()
+            .map(|c| FractionalClass { vertices: c.clone(), weight: 1.0 })
+            .collect();
+        let solution = randomized_rounding_construction(inst.clone(), &classes, 1.0, 0.5);
+        assert_eq!(checker(inst, &solution), CheckerResult::Ok(solution.len()));
+    }
+}
@@ -0,0 +1,98 @@
+use std::collections::HashMap;
+
+use crate::color::{Solution, VertexId};
+
+/** tracks, across several restarts within one run, how often each vertex ends up co-colored
+with each of a sampled set of "anchor" vertices. High stability between a vertex and an
+anchor suggests that sub-assignment keeps being reconstructed independently of the restart,
+which is useful to freeze before a final intensification phase, and as a research instrument
+to inspect how "discovered" a partial solution really is. */
+#[derive(Debug,Clone)]
+pub struct StabilityTracker {
+    anchors: Vec<VertexId>,
+    /// number of restarts recorded for this tracker
+    nb_restarts: u64,
+    /// co_coloring_counts\[(anchor_index, v)\] = number of restarts where v was co-colored with anchor
+    co_coloring_counts: HashMap<(usize, VertexId), u64>,
+}
+
+impl StabilityTracker {
+    /// builds a tracker that will watch co-coloring with the given anchor vertices
+    pub fn new(anchors:Vec<VertexId>) -> Self {
+        Self { anchors, nb_restarts:0, co_coloring_counts:HashMap::new() }
+    }
+
+    /// records one restart's final solution, updating co-coloring counts against every anchor
+    pub fn record_restart(&mut self, solution:&Solution) {
+        self.nb_restarts += 1;
+        for (anchor_index, anchor) in self.anchors.iter().enumerate() {
+            if let Some(class) = solution.iter().find(|c| c.contains(anchor)) {
+                for &v in class {
+                    if v != *anchor {
+                        *self.co_coloring_counts.entry((anchor_index, v)).or_insert(0) += 1;
+                    }
+                }
+            }
+        }
+    }
+
+    /// fraction of recorded restarts (in `[0,1]`) where `v` ended up co-colored with `anchor`;
+    /// returns `0.0` if `anchor` is not one of the tracked anchors or there were no restarts yet
+    pub fn stability(&self, anchor:VertexId, v:VertexId) -> f64 {
+        if self.nb_restarts == 0 {
+            return 0.0;
+        }
+        match self.anchors.iter().position(|a| *a == anchor) {
+            None => 0.0,
+            Some(anchor_index) => {
+                let count = self.co_coloring_counts.get(&(anchor_index, v)).copied().unwrap_or(0);
+                count as f64 / self.nb_restarts as f64
+            }
+        }
+    }
+
+    /// returns, for every anchor, the vertices whose stability score with it is at least
+    /// `threshold`: a candidate "frozen" sub-assignment to fix before a final intensification
+    pub fn frozen_groups(&self, threshold:f64) -> HashMap<VertexId, Vec<VertexId>> {
+        let mut res:HashMap<VertexId, Vec<VertexId>> = HashMap::new();
+        for (anchor_index, anchor) in self.anchors.iter().enumerate() {
+            let group:Vec<VertexId> = self.co_coloring_counts.iter()
+                .filter(|((a, _), _)| *a == anchor_index)
+                .filter(|(_, count)| **count as f64 / self.nb_restarts as f64 >= threshold)
+                .map(|((_, v), _)| *v)
+                .collect();
+            if !group.is_empty() {
+                res.insert(*anchor, group);
+            }
+        }
+        res
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_stability_tracker_perfect_agreement() {
+        let mut tracker = StabilityTracker::new(vec![0]);
+        let sol:Solution = vec![vec![0, 1, 2], vec![3, 4]];
+        tracker.record_restart(&sol);
+        tracker.record_restart(&sol);
+        assert_eq!(tracker.stability(0, 1), 1.0);
+        assert_eq!(tracker.stability(0, 3), 0.0);
+        let frozen = tracker.frozen_groups(1.0);
+        let mut group = frozen.get(&0).cloned().unwrap_or_default();
+        group.sort_unstable();
+        assert_eq!(group, vec![1, 2]);
+    }
+
+    #[test]
+    fn test_stability_tracker_partial_agreement() {
+        let mut tracker = StabilityTracker::new(vec![0]);
+        tracker.record_restart(&vec![vec![0, 1], vec![2]]);
+        tracker.record_restart(&vec![vec![0, 2], vec![1]]);
+        assert_eq!(tracker.stability(0, 1), 0.5);
+        assert_eq!(tracker.stability(0, 2), 0.5);
+    }
+}
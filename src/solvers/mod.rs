@@ -1,5 +1,73 @@
 //! Search spaces for the graph coloring problem.
 
+use std::rc::Rc;
+use std::time::Instant;
+
+use crate::color::{ColoringInstance, Solution};
+use crate::pipeline::{Pipeline, PipelineStage, PipelineStepReport};
+
+/// identifies a graph-coloring algorithm without exposing the underlying solver function's
+/// own signature, for [`solve_coloring`]
+#[derive(Clone,Debug)]
+pub enum Algorithm {
+    /// greedy DSATUR construction heuristic
+    GreedyDsatur,
+    /// Recursive Largest First construction heuristic
+    GreedyRlf,
+    /// conflict-weighting local search, started from a greedy DSATUR solution
+    ConflictWeighting,
+    /// partial-weighting local search, started from a greedy DSATUR solution
+    PartialWeighting,
+}
+
+/// outcome of [`solve_coloring`]: the solution found plus enough bookkeeping for a caller
+/// embedding this crate to report on the run without depending on any solver-specific type
+#[derive(Clone,Debug)]
+pub struct SolverResult {
+    /// the coloring found
+    pub solution: Solution,
+    /// number of colors used by `solution`
+    pub nb_colors: usize,
+    /// wall-clock time, in seconds, at which `solution` was produced. Equal to `total_time`,
+    /// since [`PipelineStepReport`] does not record when within a stage an improvement was found
+    pub time_to_best: f32,
+    /// wall-clock time, in seconds, spent solving in total
+    pub total_time: f32,
+    /// one entry per algorithm stage run (construction counts as its own stage), in order;
+    /// `ConflictWeighting`/`PartialWeighting` always run a `GreedyDsatur` stage first
+    pub history: Vec<PipelineStepReport>,
+}
+
+/** solves `inst` with `algorithm`, giving any local-search stage up to `budget` seconds
+(construction heuristics ignore `budget` and return as soon as they are done). A thin facade
+over [`crate::pipeline::Pipeline`] for downstream users embedding this crate who just want "a
+coloring from algorithm X" without learning each solver function's own signature (they
+differ in parameter order, optional guide strategies, checkpointing, ...). */
+pub fn solve_coloring(inst:Rc<dyn ColoringInstance>, algorithm:Algorithm, budget:f32) -> SolverResult {
+    let stages = match algorithm {
+        Algorithm::GreedyDsatur => vec![PipelineStage::GreedyDsatur],
+        Algorithm::GreedyRlf => vec![PipelineStage::GreedyRlf],
+        Algorithm::ConflictWeighting => vec![
+            PipelineStage::GreedyDsatur,
+            PipelineStage::ConflictWeighting { time_budget: budget },
+        ],
+        Algorithm::PartialWeighting => vec![
+            PipelineStage::GreedyDsatur,
+            PipelineStage::PartialWeighting { time_budget: budget },
+        ],
+    };
+    let start = Instant::now();
+    let (solution, history) = Pipeline { stages }.run(inst);
+    let total_time = start.elapsed().as_secs_f32();
+    SolverResult {
+        nb_colors: solution.len(),
+        solution,
+        time_to_best: total_time,
+        total_time,
+        history,
+    }
+}
+
 /// CGSHOP competition specific solvers
 pub mod cgshop;
 
@@ -9,6 +77,9 @@ pub mod clique;
 /// Vertex Coloring problem solvers
 pub mod coloring;
 
+/// alternating clique/coloring bound-tightening workflow, reporting gap shrinkage over time
+pub mod bound_tightening;
+
 
 
 // /// greedy DSATUR algorithm
@@ -47,4 +118,26 @@ pub mod coloring;
 // pub mod partialcol;
 
 // /// DSATUR adapted for the large CGSHOP instances
-// pub mod cgshop_dsatur;
\ No newline at end of file
+// pub mod cgshop_dsatur;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dimacs::DimacsInstance;
+
+    #[test]
+    fn test_solve_coloring_greedy_dsatur() {
+        let inst:Rc<dyn ColoringInstance> = Rc::new(DimacsInstance::from_file("insts/grid-instances/grid2x2"));
+        let result = solve_coloring(inst, Algorithm::GreedyDsatur, 1.0);
+        assert_eq!(result.history.len(), 1);
+        assert_eq!(result.nb_colors, result.solution.len());
+    }
+
+    #[test]
+    fn test_solve_coloring_partial_weighting() {
+        let inst:Rc<dyn ColoringInstance> = Rc::new(DimacsInstance::from_file("insts/grid-instances/grid2x2"));
+        let result = solve_coloring(inst, Algorithm::PartialWeighting, 1.0);
+        assert_eq!(result.history.len(), 2);
+        assert_eq!(result.nb_colors, result.solution.len());
+    }
+}
\ No newline at end of file
@@ -0,0 +1,266 @@
+//! detection and exploitation of vertex modules ("twins"): pairs of vertices sharing the
+//! same neighborhood (up to each other) do not need to be decided independently by a
+//! solver. True twins (adjacent, same closed neighborhood) must always differ in color;
+//! false twins (non-adjacent, same open neighborhood) can always share the same color as
+//! their representative. Collapsing them before solving shrinks the instance a solver
+//! actually has to reason about; [`expand_solution`] restores the original vertex set
+//! afterwards. Geometric (CGSHOP) instances in particular contain many duplicated-
+//! neighborhood segments this eliminates.
+
+use std::rc::Rc;
+use std::thread;
+
+use bit_set::BitSet;
+
+use crate::color::{ColoringInstance, Solution, VertexId};
+
+/// whether a twin relation requires the two vertices to differ in color (`True`, since
+/// they are adjacent) or allows them to always share one (`False`, since they are not)
+#[derive(Clone,Copy,Debug,PartialEq,Eq)]
+pub enum TwinKind {
+    /// adjacent vertices with identical closed neighborhoods: must differ in color
+    True,
+    /// non-adjacent vertices with identical open neighborhoods: can always share a color
+    False,
+}
+
+/// a vertex module: a `representative` and the other vertices found to be its twin,
+/// together with the kind of twin relation each of them has with the representative
+#[derive(Clone,Debug)]
+pub struct TwinGroup {
+    /// the vertex kept to stand in for the whole group while solving
+    pub representative: VertexId,
+    /// the other vertices of the group, paired with their twin relation to `representative`
+    pub members: Vec<(VertexId, TwinKind)>,
+}
+
+/// detects twin vertices in `inst`: groups vertices sharing a common neighborhood (modulo
+/// each other) under one representative. Runs in `O(n^2)` bitset comparisons, similar in
+/// cost to the domination pre-computation already done for CGSHOP instances.
+pub fn find_twins(inst:&dyn ColoringInstance) -> Vec<TwinGroup> {
+    let n = inst.nb_vertices();
+    let neighbor_sets:Vec<BitSet> = (0..n).map(|u| {
+        let mut b = BitSet::new();
+        for v in inst.neighbors(u) { b.insert(v); }
+        b
+    }).collect();
+    group_twins(inst, &neighbor_sets)
+}
+
+/** same as [`find_twins`], but builds `neighbor_sets` across up to `nb_threads` threads
+instead of a single one (the `O(n^2)` matching pass itself stays single-threaded, see
+`group_twins`). `inst` is not shared across
+threads (`dyn ColoringInstance` is not required to be `Sync`): each vertex's neighbor list is
+first collected sequentially into a plain `Vec`, following the same pattern as
+[`crate::solvers::coloring::greedy_rlf::greedy_rlf_concurrent`], and only that owned data is
+split across threads. Each thread writes into its own disjoint, contiguous slice of the
+pre-allocated output and nothing else, so there is no merge step whose associativity or
+scheduling order could affect the result: `group_twins` then runs, unchanged, over the fully
+built table, making this bit-for-bit identical to [`find_twins`] regardless of `nb_threads`
+(see `test_find_twins_concurrent_matches_single_threaded`). */
+pub fn find_twins_concurrent(inst:&dyn ColoringInstance, nb_threads:usize) -> Vec<TwinGroup> {
+    let n = inst.nb_vertices();
+    let adjacency:Vec<Vec<VertexId>> = (0..n).map(|u| inst.neighbors(u)).collect();
+    let nb_threads = nb_threads.max(1);
+    let chunk_size = n.div_ceil(nb_threads).max(1);
+    let mut neighbor_sets:Vec<BitSet> = vec![BitSet::new() ; n];
+    thread::scope(|scope| {
+        for (adj_chunk, set_chunk) in adjacency.chunks(chunk_size).zip(neighbor_sets.chunks_mut(chunk_size)) {
+            scope.spawn(move || {
+                for (neighbors, slot) in adj_chunk.iter().zip(set_chunk.iter_mut()) {
+                    for &v in neighbors { slot.insert(v); }
+                }
+            });
+        }
+    });
+    group_twins(inst, &neighbor_sets)
+}
+
+/// shared twin-matching pass of [`find_twins`] and [`find_twins_concurrent`]: runs
+/// single-threaded once every `neighbor_sets[u]` entry is known, so both produce identical
+/// groups regardless of how `neighbor_sets` itself was computed
+fn group_twins(inst:&dyn ColoringInstance, neighbor_sets:&[BitSet]) -> Vec<TwinGroup> {
+    let n = neighbor_sets.len();
+    let mut assigned = BitSet::new();
+    let mut groups = Vec::new();
+    for u in 0..n {
+        if assigned.contains(u) { continue; }
+        let mut members = Vec::new();
+        for v in (u + 1)..n {
+            if assigned.contains(v) { continue; }
+            let adjacent = inst.are_adjacent(u, v);
+            let mut su = neighbor_sets[u].clone();
+            let mut sv = neighbor_sets[v].clone();
+            if adjacent {
+                su.remove(v);
+                sv.remove(u);
+            }
+            if su == sv {
+                members.push((v, if adjacent { TwinKind::True } else { TwinKind::False }));
+            }
+        }
+        if !members.is_empty() {
+            for (v, _) in &members { assigned.insert(*v); }
+            groups.push(TwinGroup { representative: u, members });
+        }
+    }
+    groups
+}
+
+/// vertex ids (in `inst`) kept for solving once twin groups are collapsed to their
+/// representative, alongside the groups themselves (needed to expand the solution back)
+pub fn reduce_vertex_set(inst:&dyn ColoringInstance) -> (Vec<VertexId>, Vec<TwinGroup>) {
+    let groups = find_twins(inst);
+    let mut excluded = BitSet::new();
+    for g in &groups {
+        for (v, _) in &g.members {
+            excluded.insert(*v);
+        }
+    }
+    let kept:Vec<VertexId> = (0..inst.nb_vertices()).filter(|v| !excluded.contains(*v)).collect();
+    (kept, groups)
+}
+
+/** a view of `base` restricted to the kept (representative) vertices after collapsing twin
+groups, re-indexed to `0..kept.len()`. Solve on this instance, then lift the solution back
+through `kept` and pass it to [`expand_solution`] to recover a solution over `base`'s full
+vertex set. */
+#[derive(Debug)]
+pub struct TwinCollapsedInstance {
+    base: Rc<dyn ColoringInstance>,
+    /// kept[reduced_id] = corresponding vertex id in `base`
+    kept: Vec<VertexId>,
+    edges: Vec<(VertexId, VertexId)>,
+}
+
+impl ColoringInstance for TwinCollapsedInstance {
+    fn nb_vertices(&self) -> usize { self.kept.len() }
+
+    fn degree(&self, u:VertexId) -> usize { self.neighbors(u).len() }
+
+    fn neighbors(&self, u:VertexId) -> Vec<VertexId> {
+        (0..self.kept.len()).filter(|v| *v != u && self.are_adjacent(u, *v)).collect()
+    }
+
+    fn are_adjacent(&self, u:VertexId, v:VertexId) -> bool {
+        self.base.are_adjacent(self.kept[u], self.kept[v])
+    }
+
+    fn display_statistics(&self) {
+        println!("\ttwin-collapsed view: {} / {} vertices kept", self.kept.len(), self.base.nb_vertices());
+    }
+
+    fn write_solution(&self, filename:&str, solution:&[Vec<usize>]) {
+        let mapped = crate::color::original_ids(self, solution);
+        self.base.write_solution(filename, &mapped);
+    }
+
+    fn edges(&self) -> &[(VertexId, VertexId)] { &self.edges }
+
+    fn original_id(&self, u:VertexId) -> VertexId {
+        self.base.original_id(self.kept[u])
+    }
+}
+
+/// collapses the twin groups of `inst` into a smaller [`TwinCollapsedInstance`], returning
+/// it alongside the groups needed to expand solutions found on it back to `inst`'s vertices
+pub fn collapse(inst:Rc<dyn ColoringInstance>) -> (Rc<dyn ColoringInstance>, Vec<TwinGroup>) {
+    let (kept, groups) = reduce_vertex_set(inst.as_ref());
+    let mut edges = Vec::new();
+    for i in 0..kept.len() {
+        for j in (i + 1)..kept.len() {
+            if inst.are_adjacent(kept[i], kept[j]) {
+                edges.push((i, j));
+            }
+        }
+    }
+    (Rc::new(TwinCollapsedInstance { base: inst, kept, edges }), groups)
+}
+
+/// remaps a solution found on a [`TwinCollapsedInstance`] back to the original vertex ids
+/// of the `kept` vertices it was built from (does not yet restore the collapsed members:
+/// use [`expand_solution`] for that)
+pub fn lift_solution(reduced_solution:&Solution, kept:&[VertexId]) -> Solution {
+    reduced_solution.iter().map(|c| c.iter().map(|v| kept[*v]).collect()).collect()
+}
+
+/** restores the full vertex set of a solution computed only on representatives: false
+twins are inserted into their representative's color class (always safe, since they share
+its whole neighborhood), and true twins are each given their own brand-new singleton color
+(always safe, since it conflicts with nothing). `solution` must already be expressed in
+`base`'s original vertex ids (see [`lift_solution`]). */
+pub fn expand_solution(solution:&Solution, groups:&[TwinGroup]) -> Solution {
+    let mut res = solution.to_vec();
+    for g in groups {
+        let rep_class = res.iter().position(|c| c.contains(&g.representative));
+        for (v, kind) in &g.members {
+            match kind {
+                TwinKind::False => {
+                    if let Some(idx) = rep_class {
+                        res[idx].push(*v);
+                    }
+                }
+                TwinKind::True => {
+                    res.push(vec![*v]);
+                }
+            }
+        }
+    }
+    res
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::color::checker;
+    use crate::color::CheckerResult;
+    use crate::dimacs::DimacsInstance;
+    use crate::solvers::coloring::greedy_dsatur::greedy_dsatur;
+
+    #[test]
+    fn test_find_twins_false_twins() {
+        // a graph where 1 and 2 are false twins (same neighbor: 0, not adjacent to each other)
+        let inst = DimacsInstance::new(vec![vec![1, 2], vec![0], vec![0]]);
+        let groups = find_twins(&inst);
+        assert_eq!(groups.len(), 1);
+        assert_eq!(groups[0].members.len(), 1);
+        assert_eq!(groups[0].members[0].1, TwinKind::False);
+    }
+
+    #[test]
+    fn test_find_twins_concurrent_matches_single_threaded() {
+        // a handful of false-twin pairs (1,2 share neighbor 0; 4,5 share neighbor 3) and a
+        // true-twin pair (6,7, adjacent with the same closed neighborhood)
+        let inst = DimacsInstance::new(vec![
+            vec![1, 2],    // 0
+            vec![0],       // 1
+            vec![0],       // 2
+            vec![4, 5],    // 3
+            vec![3],       // 4
+            vec![3],       // 5
+            vec![7],       // 6
+            vec![6],       // 7
+        ]);
+        let sequential = find_twins(&inst);
+        for nb_threads in [1, 2, 3, 8] {
+            let concurrent = find_twins_concurrent(&inst, nb_threads);
+            assert_eq!(concurrent.len(), sequential.len());
+            for (a, b) in sequential.iter().zip(concurrent.iter()) {
+                assert_eq!(a.representative, b.representative);
+                assert_eq!(a.members, b.members);
+            }
+        }
+    }
+
+    #[test]
+    fn test_collapse_and_expand_roundtrip() {
+        let inst:Rc<dyn ColoringInstance> = Rc::new(DimacsInstance::from_file("insts/grid-instances/grid2x2"));
+        let (kept, groups) = reduce_vertex_set(inst.as_ref());
+        let (reduced, groups2) = collapse(inst.clone());
+        assert_eq!(groups.len(), groups2.len());
+        let reduced_sol = greedy_dsatur(reduced.clone(), false);
+        let lifted = lift_solution(&reduced_sol, &kept);
+        let expanded = expand_solution(&lifted, &groups);
+        assert_eq!(checker(inst, &expanded), CheckerResult::Ok(expanded.len()));
+    }
+}
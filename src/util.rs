@@ -1,13 +1,18 @@
+use std::collections::HashSet;
+use std::hash::{Hash, Hasher};
 use std::rc::Rc;
 
 use bit_set::BitSet;
 use clap::ArgMatches;
+use fastrand::Rng;
+use serde::{Deserialize, Serialize};
 use serde_json::Value;
 
 use crate::{
     cgshop::CGSHOPInstance,
     dimacs::DimacsInstance,
-    color::{ColoringInstance, VertexId, checker},
+    edgelist,
+    color::{ColoringInstance, VertexId, checker, degeneracy},
 };
 
 /** reads command line input and returns the instance name, time, solution_filename, stats_filename */
@@ -40,13 +45,211 @@ pub fn read_params(main_args:ArgMatches) -> (String, Rc<dyn ColoringInstance>, f
         "cgshop" => { // read CGSHOP instance
             Rc::new(CGSHOPInstance::from_file(inst_filename))
         },
+        "edgelist" => { // read edge-list instance (SNAP, Matrix Market edge lists)
+            Rc::new(edgelist::from_file(inst_filename))
+        },
         _ => panic!("instance type unknown {}", instance_type)
     };
     instance.display_statistics();
+    println!("\t{} \t degeneracy (chromatic number upper bound)", degeneracy(instance.as_ref()));
     println!("=======================");
     (inst_filename.to_string(), instance, t, instance_type.to_string(), sol_file, perf_file)
 }
 
+/** loads a coloring instance, auto-detecting whether `path` points to a DIMACS, CGSHOP or
+edge-list ([`edgelist`]) file: by extension first (`.json` for CGSHOP, `.edges`/`.snap`/`.mtx`
+for edge lists), falling back to sniffing the file's first non-whitespace character (`{` for
+CGSHOP's JSON, anything else for DIMACS) when the extension doesn't settle it. Returns the
+instance together with the detected type string ("dimacs", "cgshop" or "edgelist"), matching
+the `--type` values already accepted on the command line, so callers don't have to pick a
+loader by hand every time they switch between instance families. */
+pub fn load_instance(path:&str) -> (Rc<dyn ColoringInstance>, String) {
+    if path.ends_with(".edges") || path.ends_with(".snap") || path.ends_with(".mtx") {
+        return (Rc::new(edgelist::from_file(path)), "edgelist".to_string());
+    }
+    let looks_like_json = path.ends_with(".json") || match std::fs::read_to_string(path) {
+        Err(_) => false,
+        Ok(content) => content.trim_start().starts_with('{'),
+    };
+    if looks_like_json {
+        (Rc::new(CGSHOPInstance::from_file(path)), "cgshop".to_string())
+    } else {
+        (Rc::new(DimacsInstance::from_file(path)), "dimacs".to_string())
+    }
+}
+
+/** loads a solution file, auto-detecting whether `path` is a CGSHOP JSON solution or the
+plain DIMACS-style one-class-per-line format, the same way [`load_instance`] detects the
+instance format. */
+pub fn load_solution(path:&str) -> Vec<Vec<VertexId>> {
+    let looks_like_json = path.ends_with(".json") || match std::fs::read_to_string(path) {
+        Err(_) => false,
+        Ok(content) => content.trim_start().starts_with('{'),
+    };
+    if looks_like_json {
+        crate::cgshop::CGSHOPSolution::from_file(path).to_solution()
+    } else {
+        DimacsInstance::read_solution_from_file(path)
+    }
+}
+
+/** which clock a time-based budget (`--time`, `--checkpoint-interval`, ...) is measured
+against: wall-clock seconds (the historical default) or process CPU seconds. Needed so that
+runs comparing a single-threaded variant against a multi-threaded one are budgeted fairly (a
+multi-threaded solver burns CPU-seconds faster than wall-clock seconds for the same wall-clock
+budget), and so cluster CPU-time accounting stays accurate regardless of how busy the host
+machine was meanwhile. */
+#[derive(Clone,Copy,Debug,Eq,PartialEq)]
+pub enum TimeBasis {
+    /// wall-clock time (historical default)
+    Wall,
+    /// process CPU time (user + system), summed across all threads
+    Cpu,
+}
+
+impl Default for TimeBasis {
+    fn default() -> Self { TimeBasis::Wall }
+}
+
+/** output format for the per-improvement progress lines a local search can emit as it runs
+(see [`log_metrics`]): `Text` (the historical behavior, where progress is left to whatever the
+search already prints, e.g. [`dogs::metric_logger::MetricLogger`]'s column table) or `Json`,
+where every line is instead a single JSON object on stdout, so downstream tooling can tail and
+parse a run's progress without screen-scraping a human-oriented table. */
+#[derive(Clone,Copy,Debug,Eq,PartialEq)]
+pub enum LogFormat {
+    /// historical free-form/tabular output (default)
+    Text,
+    /// one JSON object per line on stdout
+    Json,
+}
+
+impl Default for LogFormat {
+    fn default() -> Self { LogFormat::Text }
+}
+
+/// emits one progress line for `fields` (e.g. iteration/colors/conflicts/weight/time) as a
+/// single-line JSON object when `format` is [`LogFormat::Json`]; a no-op under
+/// [`LogFormat::Text`], since that format's progress lines are already printed elsewhere (e.g.
+/// by [`dogs::metric_logger::MetricLogger`] as the search runs)
+pub fn log_metrics(format:LogFormat, fields:&Value) {
+    if format == LogFormat::Json {
+        println!("{}", fields);
+    }
+}
+
+/** total CPU time (user + system) consumed by this process so far, in seconds, read from
+`/proc/self/stat` (Linux only; returns 0 on any other platform or if the file can't be read
+or parsed, so callers degrade to "no CPU time elapsed" rather than panicking). */
+pub fn process_cpu_time_secs() -> f64 {
+    let stat = match std::fs::read_to_string("/proc/self/stat") {
+        Ok(s) => s,
+        Err(_) => return 0.,
+    };
+    // the 2nd field (comm, the executable name) is parenthesized and may itself contain
+    // spaces, so skip past its closing paren before splitting the remaining fields on whitespace
+    let after_comm = match stat.rfind(')') {
+        Some(i) => &stat[i + 1..],
+        None => return 0.,
+    };
+    let fields:Vec<&str> = after_comm.split_whitespace().collect();
+    // utime and stime are overall fields 14 and 15 (1-indexed); fields[] starts at overall
+    // field 3, so they land at indices 11 and 12 here
+    let field = |i:usize| fields.get(i).and_then(|f| f.parse::<u64>().ok()).unwrap_or(0);
+    // USER_HZ (clock ticks per second) is 100 on every Linux platform this crate targets
+    (field(11) + field(12)) as f64 / 100.
+}
+
+/** this process's peak resident set size ("high water mark") so far, in bytes, read from
+`/proc/self/status`'s `VmHWM` field (Linux only; returns 0 on any other platform or if the
+file can't be read or parsed, so callers degrade to "no memory measured" rather than
+panicking). */
+pub fn process_peak_rss_bytes() -> u64 {
+    let status = match std::fs::read_to_string("/proc/self/status") {
+        Ok(s) => s,
+        Err(_) => return 0,
+    };
+    status.lines()
+        .find(|line| line.starts_with("VmHWM:"))
+        .and_then(|line| line.split_whitespace().nth(1))
+        .and_then(|kb| kb.parse::<u64>().ok())
+        .map(|kb| kb * 1024)
+        .unwrap_or(0)
+}
+
+/** derives the seed a resumed local search's RNG should restart from, given the *original*
+run's seed and how many iterations it had performed when the checkpoint was taken. fastrand's
+`Rng` doesn't expose its stream position, so a checkpoint can't literally rewind to where the
+original run left off; re-seeding from `checkpoint.rng_seed` directly would instead replay the
+exact draws the original run already consumed near its start. Hashing `(seed, nb_iter)`
+together gives each resume point a distinct, deterministic seed instead, so a resumed run draws
+a fresh pseudo-random sequence rather than repeating one already spent. */
+pub(crate) fn resume_rng_seed(seed:u64, nb_iter:i64) -> u64 {
+    let mut hasher = std::collections::hash_map::DefaultHasher::new();
+    seed.hash(&mut hasher);
+    nb_iter.hash(&mut hasher);
+    hasher.finish()
+}
+
+/** measures both wall-clock and CPU time elapsed since it was started, so a run can be
+budgeted and reported against either clock (see [`TimeBasis`]), as well as how much this
+process's peak memory use has grown meanwhile (see [`RunClock::peak_rss_growth_bytes`]). */
+pub struct RunClock {
+    start: std::time::Instant,
+    start_cpu_secs: f64,
+    start_peak_rss_bytes: u64,
+}
+
+impl RunClock {
+    /// starts the clock now
+    pub fn start() -> Self {
+        Self {
+            start: std::time::Instant::now(),
+            start_cpu_secs: process_cpu_time_secs(),
+            start_peak_rss_bytes: process_peak_rss_bytes(),
+        }
+    }
+
+    /// seconds elapsed on `basis` since [`RunClock::start`]
+    pub fn elapsed_secs(&self, basis:TimeBasis) -> f32 {
+        match basis {
+            TimeBasis::Wall => self.start.elapsed().as_secs_f32(),
+            TimeBasis::Cpu => (process_cpu_time_secs() - self.start_cpu_secs) as f32,
+        }
+    }
+
+    /// wall-clock seconds elapsed since [`RunClock::start`]
+    pub fn wall_secs(&self) -> f32 { self.elapsed_secs(TimeBasis::Wall) }
+
+    /// CPU seconds elapsed since [`RunClock::start`]
+    pub fn cpu_secs(&self) -> f32 { self.elapsed_secs(TimeBasis::Cpu) }
+
+    /** growth in this process's peak RSS since [`RunClock::start`], in bytes. Since the
+    kernel-tracked watermark only ever increases, this under-reports a phase's own peak when
+    an earlier phase already drove memory just as high, but it is still a useful, zero-overhead
+    signal for comparing how much *more* memory one phase needed than another (e.g. DSATUR vs.
+    a weighting local search on the same 100K-segment instance) when run as separate processes
+    or as the first thing a process does. */
+    pub fn peak_rss_growth_bytes(&self) -> u64 {
+        process_peak_rss_bytes().saturating_sub(self.start_peak_rss_bytes)
+    }
+}
+
+/** one incumbent improvement recorded during a local search run: when it happened (wall-clock
+seconds since the search started) and at which iteration, together with the value that
+improved (number of colors for a coloring search, clique size for a clique search). Collected
+in a `Vec<ImprovementRecord>` by the weighting local searches and merged into the perf JSON by
+[`export_results_with_trace`], so an anytime curve can be plotted without re-parsing stdout. */
+#[derive(Clone,Copy,Debug,Serialize,Deserialize)]
+pub struct ImprovementRecord {
+    /// wall-clock seconds since the search started
+    pub time: f32,
+    /// number of local search iterations performed so far
+    pub iteration: u64,
+    /// value that improved (number of colors, clique size, ...)
+    pub value: usize,
+}
+
 /// exports search results to files
 pub fn export_results(
     instance:Rc<dyn ColoringInstance>,
@@ -56,16 +259,82 @@ pub fn export_results(
     sol_file:Option<String>,
     check_result:bool,
 ) {
+    export_results_with_tag(instance, solution, stats, perf_file, sol_file, check_result, None);
+}
+
+/** same as [`export_results`], additionally embedding `trace` into the perf JSON as a
+top-level `"improvement_trace"` field (when `stats` is a JSON object), so the anytime curve of
+incumbent improvements recorded during the search survives alongside the final statistics. */
+pub fn export_results_with_trace(
+    instance:Rc<dyn ColoringInstance>,
+    solution:&[Vec<VertexId>],
+    stats:&Value,
+    perf_file:Option<String>,
+    sol_file:Option<String>,
+    check_result:bool,
+    trace:&[ImprovementRecord],
+) {
+    export_results_with_tag_and_trace(instance, solution, stats, perf_file, sol_file, check_result, None, Some(trace));
+}
+
+/** same as [`export_results`], additionally embedding `tag` into every file this call
+produces: it is merged into the perf JSON as a top-level `"tag"` field (when `stats` is a
+JSON object), and written alongside the solution file as `<sol_file>.tag` (a plain-text
+sidecar, since the DIMACS and CGSHOP formats [`ColoringInstance::write_solution`] writes are
+both fixed external formats that cannot safely carry extra metadata inline). Combined with
+[`log_tagged`] for status lines and [`RunSummary`]'s own `tag` field for the cumulative CSV,
+lets results from concurrent batch jobs writing to a shared directory be disentangled by
+`tag` afterwards. */
+pub fn export_results_with_tag(
+    instance:Rc<dyn ColoringInstance>,
+    solution:&[Vec<VertexId>],
+    stats:&Value,
+    perf_file:Option<String>,
+    sol_file:Option<String>,
+    check_result:bool,
+    tag:Option<&str>,
+) {
+    export_results_with_tag_and_trace(instance, solution, stats, perf_file, sol_file, check_result, tag, None);
+}
+
+/** same as [`export_results_with_tag`], additionally merging `trace` into the perf JSON as a
+top-level `"improvement_trace"` field (when `stats` is a JSON object and `trace` is `Some`),
+see [`export_results_with_trace`]. */
+pub fn export_results_with_tag_and_trace(
+    instance:Rc<dyn ColoringInstance>,
+    solution:&[Vec<VertexId>],
+    stats:&Value,
+    perf_file:Option<String>,
+    sol_file:Option<String>,
+    check_result:bool,
+    tag:Option<&str>,
+    trace:Option<&[ImprovementRecord]>,
+) {
+    // clean up the coloring (merge compatible classes, move vertices down, drop empties)
+    // before it gets checked or written out
+    let solution = crate::postprocess::compact_coloring(&instance, solution);
+    let solution = solution.as_slice();
     // export statistics and solution
     match perf_file {
         None => {},
         Some(filename) => {
+            let mut tagged_stats = stats.clone();
+            if let Some(obj) = tagged_stats.as_object_mut() {
+                if let Some(tag) = tag {
+                    obj.insert("tag".to_string(), Value::String(tag.to_string()));
+                }
+                if let Some(trace) = trace {
+                    obj.insert("improvement_trace".to_string(), serde_json::to_value(trace).unwrap());
+                }
+                obj.insert("degeneracy".to_string(), Value::from(degeneracy(instance.as_ref())));
+                obj.insert("peak_rss_bytes".to_string(), Value::from(process_peak_rss_bytes()));
+            }
             let mut file = match std::fs::File::create(filename.as_str()) {
                 Err(why) => panic!("couldn't create {}: {}", filename, why),
                 Ok(file) => file
             };
             if let Err(why) = std::io::Write::write(
-                &mut file, serde_json::to_string(stats).unwrap().as_bytes()
+                &mut file, serde_json::to_string(&tagged_stats).unwrap().as_bytes()
             ) { panic!("couldn't write: {}",why) };
         }
     }
@@ -82,8 +351,377 @@ pub fn export_results(
                 };
             }
             instance.write_solution(filename.as_str(), solution);
+            if let Some(tag) = tag {
+                let tag_filename = format!("{}.tag", filename);
+                if let Err(why) = std::fs::write(&tag_filename, tag) {
+                    println!("couldn't write tag sidecar {}: {}", tag_filename, why);
+                }
+            }
+        }
+    }
+}
+
+/// prints `message`, prefixed with `[tag] ` when `tag` is set: a drop-in replacement for the
+/// ad hoc `println!` status lines scattered through the solver binaries, so a run's log lines
+/// can be told apart from others writing to the same shared console/log file (see
+/// [`export_results_with_tag`] for the perf JSON / solution file side of the same need)
+pub fn log_tagged(tag:Option<&str>, message:&str) {
+    match tag {
+        Some(tag) => println!("[{}] {}", tag, message),
+        None => println!("{}", message),
+    }
+}
+
+/** summary of a solver run, meant to be displayed once at the end of the program
+and/or appended to a cumulative results CSV, instead of scattering println's
+throughout the solving code. */
+#[derive(Debug,Clone)]
+pub struct RunSummary {
+    /// instance filename
+    pub instance: String,
+    /// name of the benchmark preset used for this run, if any (see [`crate::presets`])
+    pub preset: Option<String>,
+    /// number of colors of the best coloring found (if any)
+    pub best_colors: Option<usize>,
+    /// size of the best clique found (if any, used as a lower bound)
+    pub best_clique: Option<usize>,
+    /// number of local search iterations performed, if tracked by the solver
+    pub iterations: Option<u64>,
+    /// number of restarts performed, if the solver restarts
+    pub restarts: Option<u64>,
+    /// time (seconds) at which the best solution was found
+    pub time_to_best: f32,
+    /// total time (seconds) spent solving
+    pub total_time: f32,
+    /// total CPU time (seconds, user + system) spent solving, if tracked (see [`TimeBasis`]);
+    /// differs from `total_time` for multi-threaded solvers and under host contention
+    pub total_cpu_time: Option<f32>,
+    /// solution file written, if any
+    pub sol_file: Option<String>,
+    /// performance profile file written, if any
+    pub perf_file: Option<String>,
+    /// experiment tag/label this run was invoked with, if any (see [`export_results_with_tag`]
+    /// and [`log_tagged`]), so rows from concurrent batch jobs sharing one CSV can be told apart
+    pub tag: Option<String>,
+}
+
+impl RunSummary {
+    /// gap between the best coloring and the best clique (0 means proven optimal)
+    pub fn gap(&self) -> Option<i64> {
+        match (self.best_colors, self.best_clique) {
+            (Some(c), Some(q)) => Some(c as i64 - q as i64),
+            _ => None,
         }
     }
+
+    /// renders the summary as a pretty table on stdout
+    pub fn display(&self) {
+        let rows:Vec<(&str,String)> = vec![
+            ("instance", self.instance.clone()),
+            ("preset", self.preset.clone().unwrap_or_else(|| "-".to_string())),
+            ("best colors", opt_to_string(self.best_colors)),
+            ("best clique", opt_to_string(self.best_clique)),
+            ("gap", opt_to_string(self.gap())),
+            ("iterations", opt_to_string(self.iterations)),
+            ("restarts", opt_to_string(self.restarts)),
+            ("time to best (s)", format!("{:.3}", self.time_to_best)),
+            ("total time (s)", format!("{:.3}", self.total_time)),
+            ("total CPU time (s)", opt_to_string(self.total_cpu_time.map(|t| format!("{:.3}", t)))),
+            ("solution file", self.sol_file.clone().unwrap_or_else(|| "-".to_string())),
+            ("perf file", self.perf_file.clone().unwrap_or_else(|| "-".to_string())),
+            ("tag", self.tag.clone().unwrap_or_else(|| "-".to_string())),
+        ];
+        let label_width = rows.iter().map(|(l,_)| l.len()).max().unwrap_or(0);
+        let value_width = rows.iter().map(|(_,v)| v.len()).max().unwrap_or(0);
+        let sep = format!("+-{}-+-{}-+", "-".repeat(label_width), "-".repeat(value_width));
+        println!("{}", sep);
+        for (label,value) in &rows {
+            println!("| {:<label_width$} | {:<value_width$} |", label, value, label_width=label_width, value_width=value_width);
+        }
+        println!("{}", sep);
+    }
+
+    /// appends this summary as a single row to a cumulative results CSV file (creates it with a header if missing)
+    pub fn append_csv(&self, filename:&str) {
+        let header = "instance,preset,best_colors,best_clique,gap,iterations,restarts,time_to_best,total_time,total_cpu_time,sol_file,perf_file,tag\n";
+        let needs_header = !std::path::Path::new(filename).exists();
+        let mut file = std::fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(filename)
+            .unwrap_or_else(|why| panic!("RunSummary::append_csv: unable to open {}: {}", filename, why));
+        if needs_header {
+            std::io::Write::write_all(&mut file, header.as_bytes())
+                .unwrap_or_else(|why| panic!("RunSummary::append_csv: unable to write header: {}", why));
+        }
+        let row = format!(
+            "{},{},{},{},{},{},{},{},{},{},{},{},{}\n",
+            self.instance,
+            self.preset.clone().unwrap_or_default(),
+            opt_to_string(self.best_colors),
+            opt_to_string(self.best_clique),
+            opt_to_string(self.gap()),
+            opt_to_string(self.iterations),
+            opt_to_string(self.restarts),
+            self.time_to_best,
+            self.total_time,
+            opt_to_string(self.total_cpu_time),
+            self.sol_file.clone().unwrap_or_default(),
+            self.perf_file.clone().unwrap_or_default(),
+            self.tag.clone().unwrap_or_default(),
+        );
+        std::io::Write::write_all(&mut file, row.as_bytes())
+            .unwrap_or_else(|why| panic!("RunSummary::append_csv: unable to write row: {}", why));
+    }
+}
+
+/// formats an `Option` for display, using "-" for `None`
+fn opt_to_string<T:ToString>(o:Option<T>) -> String {
+    match o {
+        None => "-".to_string(),
+        Some(v) => v.to_string(),
+    }
+}
+
+/** estimates the number of conflicting edges of a candidate coloring (`colors[v]`: color of
+vertex v) by sampling `sample_size` vertices uniformly at random and checking how many of their
+incident edges are monochromatic, instead of scanning all m edges. Useful to cheaply score
+perturbed solutions in LNS before paying for a full evaluation.
+returns `(estimated_nb_conflicting_edges, ci95_half_width)`, the 95% confidence interval
+half-width being derived from the normal approximation of the sampled conflict ratio. */
+pub fn estimate_conflicting_edges(
+    inst:&dyn ColoringInstance,
+    colors:&[usize],
+    sample_size:usize,
+    rng:&mut Rng,
+) -> (f64, f64) {
+    let n = inst.nb_vertices();
+    let sample_size = sample_size.clamp(1, n);
+    let mut conflicting_incidences:usize = 0;
+    let mut total_incidences:usize = 0;
+    for _ in 0..sample_size {
+        let v = rng.usize(0..n);
+        let neighbors = inst.neighbors(v);
+        total_incidences += neighbors.len();
+        conflicting_incidences += neighbors.iter().filter(|u| colors[**u] == colors[v]).count();
+    }
+    let mean_ratio = if total_incidences == 0 { 0. } else {
+        conflicting_incidences as f64 / total_incidences as f64
+    };
+    let estimated_m:f64 = (0..n).map(|v| inst.degree(v) as f64).sum::<f64>() / 2.;
+    let variance = mean_ratio * (1. - mean_ratio) / sample_size as f64;
+    let ci_half_width = 1.96 * variance.sqrt() * estimated_m;
+    (mean_ratio * estimated_m, ci_half_width)
+}
+
+/// which ILP model [`write_lp`] / [`write_mps`] build for a [`ColoringInstance`]
+#[derive(Clone,Debug)]
+pub enum IlpModel {
+    /** the standard vertex-coloring assignment ILP: binary `x_{v,c}` ("vertex `v` takes color
+    `c`") and `y_c` ("color `c` is used at all"), minimizing `sum_c y_c` subject to
+    `x_{u,c} + x_{v,c} <= y_c` for every edge `(u,v)` and color `c` (forbids both endpoints
+    taking `c`, and forces `y_c` on whenever some vertex does) and `x_{v,c} <= y_c` for every
+    vertex (so isolated vertices still count their color as used). `max_colors` bounds the
+    candidate colors `0..max_colors` made available (typically a greedy upper bound);
+    `fix_clique`, if non-empty, pins each of its vertices to its own color `0..fix_clique.len()`
+    (a valid clique needs that many distinct colors anyway, so nothing optimal is lost).
+    `y_c >= y_{c+1}` symmetry-breaking constraints are added over all candidate colors, whether
+    or not `fix_clique` is empty. */
+    Coloring {
+        /// number of candidate colors made available to the model
+        max_colors: usize,
+        /// clique vertices to pin to colors `0..fix_clique.len()`, one each
+        fix_clique: Vec<VertexId>,
+    },
+    /// the clique ILP: binary `x_v` ("vertex `v` is in the clique"), maximizing `sum_v x_v`
+    /// subject to `x_u + x_v <= 1` for every non-adjacent pair `(u,v)`
+    Clique,
+}
+
+/// comparison operator of an [`IlpRow`]
+#[derive(Clone,Copy,Debug)]
+enum RowSense { Eq, Le, Ge }
+
+/// one constraint row of an [`IlpProblem`]: `sum terms (sense) rhs`
+struct IlpRow {
+    name: String,
+    terms: Vec<(String,f64)>,
+    sense: RowSense,
+    rhs: f64,
+}
+
+/// a small in-memory ILP model, built once by [`build_coloring_ilp`]/[`build_clique_ilp`] and
+/// rendered by [`IlpProblem::to_lp`]/[`IlpProblem::to_mps`], so both output formats agree on
+/// exactly the same rows instead of re-deriving the model twice
+struct IlpProblem {
+    maximize: bool,
+    objective: Vec<(String,f64)>,
+    rows: Vec<IlpRow>,
+    binaries: Vec<String>,
+    fixed_to_one: Vec<String>,
+}
+
+fn x_var(v:VertexId, c:usize) -> String { format!("x_{}_{}", v, c) }
+fn y_var(c:usize) -> String { format!("y_{}", c) }
+
+fn build_coloring_ilp(inst:&dyn ColoringInstance, max_colors:usize, fix_clique:&[VertexId]) -> IlpProblem {
+    let n = inst.nb_vertices();
+    let objective = (0..max_colors).map(|c| (y_var(c), 1.)).collect();
+    let mut rows = Vec::new();
+    for v in 0..n {
+        rows.push(IlpRow {
+            name: format!("assign_{}", v),
+            terms: (0..max_colors).map(|c| (x_var(v,c), 1.)).collect(),
+            sense: RowSense::Eq, rhs: 1.,
+        });
+    }
+    for v in 0..n {
+        for w in (v+1)..n {
+            if inst.are_adjacent(v, w) {
+                for c in 0..max_colors {
+                    rows.push(IlpRow {
+                        name: format!("edge_{}_{}_{}", v, w, c),
+                        terms: vec![(x_var(v,c), 1.), (x_var(w,c), 1.), (y_var(c), -1.)],
+                        sense: RowSense::Le, rhs: 0.,
+                    });
+                }
+            }
+        }
+    }
+    for v in 0..n {
+        for c in 0..max_colors {
+            rows.push(IlpRow {
+                name: format!("usage_{}_{}", v, c),
+                terms: vec![(x_var(v,c), 1.), (y_var(c), -1.)],
+                sense: RowSense::Le, rhs: 0.,
+            });
+        }
+    }
+    for c in 0..max_colors.saturating_sub(1) {
+        rows.push(IlpRow {
+            name: format!("symmetry_{}", c),
+            terms: vec![(y_var(c), 1.), (y_var(c+1), -1.)],
+            sense: RowSense::Ge, rhs: 0.,
+        });
+    }
+    let mut binaries = Vec::new();
+    for v in 0..n { for c in 0..max_colors { binaries.push(x_var(v,c)); } }
+    for c in 0..max_colors { binaries.push(y_var(c)); }
+    let mut fixed_to_one = Vec::new();
+    for (i, &v) in fix_clique.iter().enumerate() {
+        fixed_to_one.push(x_var(v, i));
+        fixed_to_one.push(y_var(i));
+    }
+    IlpProblem { maximize: false, objective, rows, binaries, fixed_to_one }
+}
+
+fn build_clique_ilp(inst:&dyn ColoringInstance) -> IlpProblem {
+    let n = inst.nb_vertices();
+    let x = |v:VertexId| format!("x_{}", v);
+    let objective = (0..n).map(|v| (x(v), 1.)).collect();
+    let mut rows = Vec::new();
+    for v in 0..n {
+        for w in (v+1)..n {
+            if !inst.are_adjacent(v, w) {
+                rows.push(IlpRow {
+                    name: format!("nonadj_{}_{}", v, w),
+                    terms: vec![(x(v), 1.), (x(w), 1.)],
+                    sense: RowSense::Le, rhs: 1.,
+                });
+            }
+        }
+    }
+    let binaries = (0..n).map(x).collect();
+    IlpProblem { maximize: true, objective, rows, binaries, fixed_to_one: Vec::new() }
+}
+
+impl IlpProblem {
+    /// renders this model in the free-form CPLEX LP format
+    fn to_lp(&self) -> String {
+        let term = |(v,c):&(String,f64)| format!("{:+} {}", c, v);
+        let mut s = String::new();
+        s += if self.maximize { "Maximize\n" } else { "Minimize\n" };
+        s += &format!(" obj: {}\n", self.objective.iter().map(term).collect::<Vec<_>>().join(" "));
+        s += "Subject To\n";
+        for row in &self.rows {
+            let sense = match row.sense { RowSense::Eq => "=", RowSense::Le => "<=", RowSense::Ge => ">=" };
+            s += &format!(" {}: {} {} {}\n", row.name, row.terms.iter().map(term).collect::<Vec<_>>().join(" "), sense, row.rhs);
+        }
+        if !self.fixed_to_one.is_empty() {
+            s += "Bounds\n";
+            for v in &self.fixed_to_one { s += &format!(" {} = 1\n", v); }
+        }
+        s += "Binaries\n";
+        for v in &self.binaries { s += &format!(" {}\n", v); }
+        s += "End\n";
+        s
+    }
+
+    /// renders this model in the free MPS format
+    fn to_mps(&self) -> String {
+        let mut vars:Vec<&str> = Vec::new();
+        let mut seen = std::collections::HashSet::new();
+        for (v, _) in &self.objective { if seen.insert(v.as_str()) { vars.push(v); } }
+        for row in &self.rows { for (v, _) in &row.terms { if seen.insert(v.as_str()) { vars.push(v); } } }
+        let mut s = String::new();
+        s += "NAME PROBLEM\n";
+        s += "ROWS\n";
+        s += " N COST\n";
+        for row in &self.rows {
+            let sense = match row.sense { RowSense::Eq => "E", RowSense::Le => "L", RowSense::Ge => "G" };
+            s += &format!(" {} {}\n", sense, row.name);
+        }
+        s += "COLUMNS\n";
+        s += " MARKER M1 'MARKER' 'INTORG'\n";
+        for v in &vars {
+            if let Some((_, coeff)) = self.objective.iter().find(|(name,_)| name == v) {
+                s += &format!(" {} COST {}\n", v, coeff);
+            }
+            for row in &self.rows {
+                if let Some((_, coeff)) = row.terms.iter().find(|(name,_)| name == v) {
+                    s += &format!(" {} {} {}\n", v, row.name, coeff);
+                }
+            }
+        }
+        s += " MARKER M2 'MARKER' 'INTEND'\n";
+        s += "RHS\n";
+        for row in &self.rows {
+            if row.rhs != 0. { s += &format!(" RHS {} {}\n", row.name, row.rhs); }
+        }
+        s += "BOUNDS\n";
+        let fixed:HashSet<&str> = self.fixed_to_one.iter().map(|s| s.as_str()).collect();
+        for v in &self.binaries {
+            if fixed.contains(v.as_str()) {
+                s += &format!(" FX BND {} 1\n", v);
+            } else {
+                s += &format!(" UP BND {} 1\n", v);
+            }
+        }
+        s += "ENDATA\n";
+        s
+    }
+}
+
+/** writes `inst`'s ILP `model` (see [`IlpModel`]) to `filename` in the free-form CPLEX LP
+format, for use by an external MILP solver (CPLEX/Gurobi/HiGHS/...). */
+pub fn write_lp(inst:&dyn ColoringInstance, filename:&str, model:&IlpModel) {
+    let problem = match model {
+        IlpModel::Coloring { max_colors, fix_clique } => build_coloring_ilp(inst, *max_colors, fix_clique),
+        IlpModel::Clique => build_clique_ilp(inst),
+    };
+    std::fs::write(filename, problem.to_lp())
+        .unwrap_or_else(|why| panic!("write_lp: unable to write {}: {}", filename, why));
+}
+
+/** writes `inst`'s ILP `model` (see [`IlpModel`]) to `filename` in the free MPS format, for
+solvers that don't accept the LP format [`write_lp`] produces. */
+pub fn write_mps(inst:&dyn ColoringInstance, filename:&str, model:&IlpModel) {
+    let problem = match model {
+        IlpModel::Coloring { max_colors, fix_clique } => build_coloring_ilp(inst, *max_colors, fix_clique),
+        IlpModel::Clique => build_clique_ilp(inst),
+    };
+    std::fs::write(filename, problem.to_mps())
+        .unwrap_or_else(|why| panic!("write_mps: unable to write {}: {}", filename, why));
 }
 
 /// transforms a clique defined by a vector, to a clique defined by a vector of vector